@@ -0,0 +1,93 @@
+//! Compares `EnumMap` iteration, which scans the occupancy bitmask for runs of occupied slots,
+//! against stepping an `Enumeration` and looking up each key, as iteration used to.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use enumeration::{Enum, EnumMap};
+
+#[rustfmt::skip]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+enum Wide {
+    V00, V01, V02, V03, V04, V05, V06, V07, V08, V09,
+    V10, V11, V12, V13, V14, V15, V16, V17, V18, V19,
+    V20, V21, V22, V23, V24, V25, V26, V27, V28, V29,
+    V30, V31, V32, V33, V34, V35, V36, V37, V38, V39,
+    V40, V41, V42, V43, V44, V45, V46, V47, V48, V49,
+    V50, V51, V52, V53, V54, V55, V56, V57, V58, V59,
+    V60, V61, V62, V63, V64, V65, V66, V67, V68, V69,
+    V70, V71, V72, V73, V74, V75, V76, V77, V78, V79,
+    V80, V81, V82, V83, V84, V85, V86, V87, V88, V89,
+    V90, V91, V92, V93, V94, V95, V96, V97, V98, V99,
+}
+
+/// Implements `Enum` by hand without overriding `from_index`, which therefore searches.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Manual(Wide);
+
+impl Enum for Manual {
+    type Rep = <Wide as Enum>::Rep;
+    const SIZE: usize = Wide::SIZE;
+    const MIN: Self = Self(Wide::MIN);
+    const MAX: Self = Self(Wide::MAX);
+    const BITMASK: Self::Rep = Wide::BITMASK;
+
+    fn succ(self) -> Option<Self> {
+        self.0.succ().map(Self)
+    }
+
+    fn pred(self) -> Option<Self> {
+        self.0.pred().map(Self)
+    }
+
+    fn bit(self) -> Self::Rep {
+        self.0.bit()
+    }
+
+    fn index(self) -> usize {
+        self.0.index()
+    }
+}
+
+/// A map holding every `step`th key.
+fn map_of<K: Enum>(step: usize) -> EnumMap<K, u64> {
+    K::enumerate_all(..)
+        .step_by(step)
+        .map(|k| (k, k.index() as u64))
+        .collect()
+}
+
+fn bench_for<K: Enum>(c: &mut Criterion, name: &str) {
+    let mut group = c.benchmark_group(name);
+    for (density, step) in [("full", 1), ("sparse", 7)] {
+        let map = map_of::<K>(step);
+        group.bench_with_input(
+            BenchmarkId::new("enumerate_get", density),
+            &map,
+            |b, map| {
+                b.iter(|| {
+                    K::enumerate_all(..)
+                        .filter_map(|k| Some((k, map.get(k)?)))
+                        .fold(0, |sum, (k, v)| sum + k.index() as u64 + v)
+                });
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("iter", density), &map, |b, map| {
+            b.iter(|| map.iter().fold(0, |sum, (k, v)| sum + k.index() as u64 + v));
+        });
+        group.bench_with_input(BenchmarkId::new("retain", density), &map, |b, map| {
+            b.iter(|| {
+                let mut map = map.clone();
+                map.retain(|k, _| black_box(k.index()) % 2 == 0);
+                map
+            });
+        });
+    }
+    group.finish();
+}
+
+fn iteration(c: &mut Criterion) {
+    bench_for::<Wide>(c, "derived");
+    bench_for::<Manual>(c, "manual");
+}
+
+criterion_group!(benches, iteration);
+criterion_main!(benches);
@@ -0,0 +1,51 @@
+use core::ops::Deref;
+
+use super::enum_trait::Enum;
+use super::error::OutOfRange;
+
+/// A value of an [`Enum`] that was converted from an index with [`TryFrom<usize>`].
+///
+/// `TryFrom<usize>` cannot be implemented for every `Enum` at once, so this wrapper provides it
+/// for code that is generic over `Enum`, or that goes through `try_into`. Types that derive
+/// `Enum` with `#[enumeration(conversions)]` implement `TryFrom<usize>` directly.
+///
+/// # Examples
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use enumeration::{Checked, OutOfRange};
+///
+/// let Checked(ordering) = Checked::<Ordering>::try_from(0).unwrap();
+/// assert_eq!(ordering, Ordering::Less);
+///
+/// let result: Result<Checked<Ordering>, OutOfRange> = 5.try_into();
+/// assert_eq!(result, Err(OutOfRange { index: 5, size: 3 }));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checked<T>(pub T);
+
+impl<T> Checked<T> {
+    /// Returns the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Enum> TryFrom<usize> for Checked<T> {
+    type Error = OutOfRange;
+
+    #[inline]
+    fn try_from(index: usize) -> Result<Self, Self::Error> {
+        T::try_from_index(index).map(Checked)
+    }
+}
+
+impl<T> Deref for Checked<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
@@ -1,9 +1,11 @@
-use std::cmp::Ordering;
-use std::iter::Iterator;
-use std::ops::{Bound, RangeBounds};
+use core::cmp::Ordering;
+use core::iter::{Iterator, Rev};
+use core::ops::RangeBounds;
 
+use super::error::OutOfRange;
 use super::iter::Enumeration;
-use crate::wordlike::Wordlike;
+use crate::set::EnumSet;
+use crate::wordlike::{Wordlike, Words};
 
 pub trait Enum: Copy + Ord {
     /// Bitwise representation of the type.
@@ -29,6 +31,44 @@ pub trait Enum: Copy + Ord {
     /// Note: the standard implementation is `!0 >> (Self::Rep::BITS - Self::SIZE as u32)`.
     const BITMASK: Self::Rep;
 
+    /// Names of the values, in order of [`index`](Enum::index).
+    ///
+    /// Rule: `Self::NAMES.is_empty() || Self::NAMES.len() == Self::SIZE`.
+    ///
+    /// Note: `#[derive(Enum)]` provides the variant names. Manual implementations that do not
+    /// override this constant have no names, so name-based serialization is unavailable for them.
+    const NAMES: &'static [&'static str] = &[];
+
+    /// Whether [`stable_index`](Enum::stable_index) is pinned independently of declaration order.
+    ///
+    /// Note: `#[derive(Enum)]` sets this to `true` if any variant has an
+    /// `#[enumeration(index = N)]` attribute.
+    const STABLE_INDICES: bool = false;
+
+    /// Set containing every value of the type. Equivalent to [`EnumSet::all()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Permission { Read, Write, Delete }
+    ///
+    /// const DEFAULT_PERMISSIONS: EnumSet<Permission> = Permission::ALL_SET;
+    /// assert_eq!(DEFAULT_PERMISSIONS.len(), 3);
+    /// ```
+    const ALL_SET: EnumSet<Self> = EnumSet::all();
+
+    /// Set containing no values of the type. Equivalent to [`EnumSet::new()`].
+    const EMPTY_SET: EnumSet<Self> = EnumSet::new();
+
+    /// Values that are being phased out. They keep their indices, but are skipped by
+    /// [`enumerate`](Enum::enumerate) and [`enumerate_rev`](Enum::enumerate_rev).
+    ///
+    /// Note: `#[derive(Enum)]` fills this with the variants marked `#[enumeration(deprecated)]`.
+    const DEPRECATED: EnumSet<Self> = EnumSet::new();
+
     /// Returns `self`'s successor, or `None` if `self == Self::MAX`.
     ///
     /// Rule: for all `x`, `(x == Self::MAX) == x.succ().is_none()`.
@@ -39,6 +79,40 @@ pub trait Enum: Copy + Ord {
     /// Rule: for all `x`, `(x == Self::MIN) == x.pred().is_none()`.
     fn pred(self) -> Option<Self>;
 
+    /// Returns the value `n` steps after `self`, or `None` if that is past [`MAX`](Self::MAX).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::Enum;
+    ///
+    /// assert_eq!(Ordering::Less.forward(2), Some(Ordering::Greater));
+    /// assert_eq!(Ordering::Equal.forward(2), None);
+    /// assert_eq!(Ordering::Equal.forward(0), Some(Ordering::Equal));
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn forward(self, n: usize) -> Option<Self> {
+        self.index().checked_add(n).and_then(Self::from_index)
+    }
+
+    /// Returns the value `n` steps before `self`, or `None` if that is before
+    /// [`MIN`](Self::MIN).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::Enum;
+    ///
+    /// assert_eq!(Ordering::Greater.backward(2), Some(Ordering::Less));
+    /// assert_eq!(Ordering::Equal.backward(2), None);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn backward(self, n: usize) -> Option<Self> {
+        self.index().checked_sub(n).and_then(Self::from_index)
+    }
+
     /// Bitwise representation of the value.
     fn bit(self) -> Self::Rep;
 
@@ -48,41 +122,189 @@ pub trait Enum: Copy + Ord {
     /// Inverse of `index`. Returns `None` if out of range.
     #[cfg_attr(feature = "inline-more", inline)]
     fn from_index(i: usize) -> Option<Self> {
-        Self::enumerate(..).find(|e| e.index() == i)
+        Self::enumerate_all(..).find(|e| e.index() == i)
+    }
+
+    /// Inverse of `index`, for indices known to be in range.
+    ///
+    /// The default implementation calls [`from_index`](Enum::from_index) and panics if it
+    /// returns `None`. `#[enumeration(unchecked_from_index)]` derives an implementation without
+    /// that check, for callers that already know the index came from `index`.
+    ///
+    /// # Safety
+    ///
+    /// `i` must be less than [`SIZE`](Enum::SIZE).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::Enum;
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// #[enumeration(unchecked_from_index)]
+    /// pub enum Opcode { Load, Store, Move }
+    ///
+    /// // SAFETY: 2 is less than `Opcode::SIZE`.
+    /// assert_eq!(unsafe { Opcode::from_index_unchecked(2) }, Opcode::Move);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    unsafe fn from_index_unchecked(i: usize) -> Self {
+        Self::from_index(i).expect("got None from calling Enum::from_index() where < Enum::SIZE")
+    }
+
+    /// Like [`from_index`](Enum::from_index), but returns an [`OutOfRange`] error that can be
+    /// propagated with `?`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::{Enum, OutOfRange};
+    ///
+    /// assert_eq!(Ordering::try_from_index(2), Ok(Ordering::Greater));
+    /// assert_eq!(Ordering::try_from_index(3), Err(OutOfRange { index: 3, size: 3 }));
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn try_from_index(i: usize) -> Result<Self, OutOfRange> {
+        Self::from_index(i).ok_or(OutOfRange {
+            index: i,
+            size: Self::SIZE,
+        })
+    }
+
+    /// The value's index for persisted data. Defaults to [`index`](Enum::index).
+    ///
+    /// Unlike `index`, a stable index does not change when variants are reordered or inserted,
+    /// as long as every variant is pinned with `#[enumeration(index = N)]`. A variant without
+    /// the attribute takes the index after the previous variant's. Stable indices need not be
+    /// contiguous.
+    ///
+    /// Rule: no two values have the same stable index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::Enum;
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Opcode {
+    ///     #[enumeration(index = 0)]
+    ///     Load,
+    ///     // Added in version 2.
+    ///     #[enumeration(index = 2)]
+    ///     Move,
+    ///     #[enumeration(index = 1)]
+    ///     Store,
+    /// }
+    ///
+    /// assert!(Opcode::STABLE_INDICES);
+    /// assert_eq!(Opcode::Move.index(), 1);
+    /// assert_eq!(Opcode::Move.stable_index(), 2);
+    /// assert_eq!(Opcode::from_stable_index(1), Some(Opcode::Store));
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn stable_index(self) -> usize {
+        self.index()
+    }
+
+    /// Inverse of `stable_index`. Returns `None` if no value has the given stable index.
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn from_stable_index(i: usize) -> Option<Self> {
+        Self::from_index(i)
+    }
+
+    /// Returns the name of the value, or `None` if the type does not provide names.
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn name(self) -> Option<&'static str> {
+        Self::NAMES.get(self.index()).copied()
     }
 
+    /// Inverse of `name`. Returns `None` if no value has the given name.
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn from_name(name: &str) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .position(|&x| x == name)
+            .and_then(Self::from_index)
+    }
+
+    /// Enumerates the values in `range` in ascending order, skipping
+    /// [deprecated](Enum::DEPRECATED) values. Use [`enumerate_all`](Enum::enumerate_all) to
+    /// include them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::Enum;
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Codec {
+    ///     Raw,
+    ///     #[enumeration(deprecated)]
+    ///     Lzw,
+    ///     Zstd,
+    /// }
+    ///
+    /// assert_eq!(Codec::enumerate(..).collect::<Vec<_>>(), [Codec::Raw, Codec::Zstd]);
+    /// assert_eq!(Codec::enumerate(..).len(), 2);
+    /// ```
     fn enumerate<R: RangeBounds<Self>>(range: R) -> Enumeration<Self> {
-        fn invalid_enum<T: Enum>() -> Enumeration<T> {
-            Enumeration {
-                start: T::MIN,
-                end: T::MIN,
-                finished: true,
-            }
-        }
-        let start = match range.start_bound() {
-            Bound::Unbounded => Self::MIN,
-            Bound::Included(&t) => t,
-            Bound::Excluded(&t) => match t.succ() {
-                Some(succ) => succ,
-                None => return invalid_enum(),
-            },
-        };
-        let end = match range.end_bound() {
-            Bound::Unbounded => Self::MAX,
-            Bound::Included(&t) => t,
-            Bound::Excluded(&t) => match t.pred() {
-                Some(pred) => pred,
-                None => return invalid_enum(),
-            },
-        };
-        if start.index() > end.index() {
-            return invalid_enum();
-        }
-        Enumeration {
-            start,
-            end,
-            finished: false,
-        }
+        Enumeration::new(range, true)
+    }
+
+    /// Like [`enumerate`](Enum::enumerate), but also yields [deprecated](Enum::DEPRECATED)
+    /// values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::Enum;
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Codec {
+    ///     Raw,
+    ///     #[enumeration(deprecated)]
+    ///     Lzw,
+    ///     Zstd,
+    /// }
+    ///
+    /// assert!(Codec::Lzw.is_deprecated());
+    /// assert_eq!(Codec::Zstd.index(), 2);
+    /// assert_eq!(
+    ///     Codec::enumerate_all(..).collect::<Vec<_>>(),
+    ///     [Codec::Raw, Codec::Lzw, Codec::Zstd]
+    /// );
+    /// ```
+    fn enumerate_all<R: RangeBounds<Self>>(range: R) -> Enumeration<Self> {
+        Enumeration::new(range, false)
+    }
+
+    /// Returns `true` if the value is in [`DEPRECATED`](Enum::DEPRECATED).
+    #[inline]
+    fn is_deprecated(self) -> bool {
+        Self::DEPRECATED.contains(self)
+    }
+
+    /// Enumerates values in descending order, from the start of `range` down to its end.
+    ///
+    /// The range is read in the direction of iteration, so `D..=A` yields `D, C, B, A`. An
+    /// unbounded start begins at [`MAX`](Self::MAX) and an unbounded end stops at
+    /// [`MIN`](Self::MIN). A range whose start is below its end is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::Enum;
+    ///
+    /// let values: Vec<_> = Ordering::enumerate_rev(Ordering::Greater..=Ordering::Less).collect();
+    /// assert_eq!(values, [Ordering::Greater, Ordering::Equal, Ordering::Less]);
+    ///
+    /// let values: Vec<_> = Ordering::enumerate_rev(..Ordering::Less).collect();
+    /// assert_eq!(values, [Ordering::Greater, Ordering::Equal]);
+    /// ```
+    fn enumerate_rev<R: RangeBounds<Self>>(range: R) -> Rev<Enumeration<Self>> {
+        Self::enumerate((range.end_bound().cloned(), range.start_bound().cloned())).rev()
     }
 }
 
@@ -92,6 +314,7 @@ impl Enum for bool {
     const MIN: Self = false;
     const MAX: Self = true;
     const BITMASK: Self::Rep = !0 >> (Self::Rep::BITS - 2);
+    const NAMES: &'static [&'static str] = &["false", "true"];
 
     #[cfg_attr(feature = "inline-more", inline)]
     fn succ(self) -> Option<Self> {
@@ -146,6 +369,7 @@ impl Enum for Ordering {
     const MIN: Self = Ordering::Less;
     const MAX: Self = Ordering::Greater;
     const BITMASK: Self::Rep = !0 >> (Self::Rep::BITS - 3);
+    const NAMES: &'static [&'static str] = &["Less", "Equal", "Greater"];
 
     #[cfg_attr(feature = "inline-more", inline)]
     fn succ(self) -> Option<Self> {
@@ -187,9 +411,57 @@ impl Enum for Ordering {
     }
 }
 
+/// Implements `Enum` for a byte type, whose 256 values are indexed in ascending order. This
+/// lets an `EnumMap` over bytes serve as a lookup table.
+macro_rules! impl_enum_for_byte {
+    ($t:ty, $to_index:expr, $from_index:expr) => {
+        impl Enum for $t {
+            type Rep = Words<4>;
+            const SIZE: usize = 256;
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+            const BITMASK: Self::Rep = Words::low_mask(256);
+
+            #[cfg_attr(feature = "inline-more", inline)]
+            fn succ(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            #[cfg_attr(feature = "inline-more", inline)]
+            fn pred(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            #[cfg_attr(feature = "inline-more", inline)]
+            fn bit(self) -> Self::Rep {
+                Words::bit(self.index())
+            }
+
+            #[cfg_attr(feature = "inline-more", inline)]
+            fn index(self) -> usize {
+                $to_index(self)
+            }
+
+            #[cfg_attr(feature = "inline-more", inline)]
+            fn from_index(i: usize) -> Option<Self> {
+                u8::try_from(i).ok().map($from_index)
+            }
+        }
+    };
+}
+
+impl_enum_for_byte!(u8, usize::from, |i| i);
+// Offsetting by 128 maps `i8::MIN..=i8::MAX` onto `0..=255`, preserving order.
+impl_enum_for_byte!(
+    i8,
+    |x: i8| usize::from(x.cast_unsigned() ^ 0x80),
+    |i: u8| (i ^ 0x80).cast_signed()
+);
+
 #[cfg(test)]
 mod tests {
-    use std::fmt::Debug;
+    use core::fmt::Debug;
+    use core::ops::Bound;
 
     use super::*;
 
@@ -205,6 +477,10 @@ mod tests {
     #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
     enum ManyEnum { A, B, C, D, E, F, G, H, I, J }
 
+    #[rustfmt::skip] #[allow(dead_code)]
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    enum SparseEnum { A = -4, B, C = 10, D = 1 << 6 }
+
     // Enum tests
 
     fn assert_eqs<T: Eq + Debug, X: Iterator<Item = T>, Y: Iterator<Item = T>>(x: X, y: Y) {
@@ -226,6 +502,7 @@ mod tests {
         test::<SingleEnum>();
         test::<DoubleEnum>();
         test::<ManyEnum>();
+        test::<SparseEnum>();
     }
 
     #[test]
@@ -236,6 +513,7 @@ mod tests {
         test::<SingleEnum>();
         test::<DoubleEnum>();
         test::<ManyEnum>();
+        test::<SparseEnum>();
     }
 
     #[test]
@@ -246,6 +524,7 @@ mod tests {
         test::<SingleEnum>();
         test::<DoubleEnum>();
         test::<ManyEnum>();
+        test::<SparseEnum>();
     }
 
     #[test]
@@ -257,6 +536,7 @@ mod tests {
         test::<SingleEnum>();
         test::<DoubleEnum>();
         test::<ManyEnum>();
+        test::<SparseEnum>();
     }
 
     #[test]
@@ -267,6 +547,7 @@ mod tests {
         test::<SingleEnum>();
         test::<DoubleEnum>();
         test::<ManyEnum>();
+        test::<SparseEnum>();
     }
 
     #[test]
@@ -277,6 +558,7 @@ mod tests {
         test::<SingleEnum>();
         test::<DoubleEnum>();
         test::<ManyEnum>();
+        test::<SparseEnum>();
     }
 
     #[test]
@@ -287,6 +569,54 @@ mod tests {
         test::<SingleEnum>();
         test::<DoubleEnum>();
         test::<ManyEnum>();
+        test::<SparseEnum>();
+    }
+
+    #[test]
+    fn test_try_from_index() {
+        fn test<E: Debug + Enum>() {
+            assert_all(|e: E| E::try_from_index(e.index()) == Ok(e));
+            assert_eq!(
+                E::try_from_index(E::SIZE),
+                Err(OutOfRange {
+                    index: E::SIZE,
+                    size: E::SIZE
+                })
+            );
+        }
+        test::<SingleEnum>();
+        test::<DoubleEnum>();
+        test::<ManyEnum>();
+        test::<SparseEnum>();
+    }
+
+    #[test]
+    fn test_from_name() {
+        fn test<E: Debug + Enum>() {
+            assert_eq!(E::NAMES.len(), E::SIZE);
+            assert_all(|e: E| E::from_name(e.name().unwrap()) == Some(e));
+            assert_eq!(E::from_name(""), None);
+        }
+        test::<SingleEnum>();
+        test::<DoubleEnum>();
+        test::<ManyEnum>();
+        test::<SparseEnum>();
+        test::<bool>();
+        test::<Ordering>();
+    }
+
+    #[test]
+    fn test_sets() {
+        fn test<E: Debug + Enum>() {
+            assert_eqs(E::ALL_SET.into_iter(), E::enumerate(..));
+            assert!(E::EMPTY_SET.is_empty());
+        }
+        test::<SingleEnum>();
+        test::<DoubleEnum>();
+        test::<ManyEnum>();
+        test::<SparseEnum>();
+        test::<bool>();
+        test::<Ordering>();
     }
 
     #[test]
@@ -306,6 +636,139 @@ mod tests {
         test::<SingleEnum>();
         test::<DoubleEnum>();
         test::<ManyEnum>();
+        test::<SparseEnum>();
+    }
+
+    #[test]
+    fn test_enumerate_rev() {
+        fn test<E: Debug + Enum>() {
+            for x in E::enumerate(..) {
+                for y in E::enumerate(..) {
+                    assert_eqs(E::enumerate_rev(y..=x), E::enumerate(x..=y).rev());
+                    assert_eqs(
+                        E::enumerate_rev(y..x),
+                        E::enumerate((Bound::Excluded(x), Bound::Included(y))).rev(),
+                    );
+                }
+                assert_eqs(E::enumerate_rev(x..), E::enumerate(..=x).rev());
+                assert_eqs(E::enumerate_rev(..=x), E::enumerate(x..).rev());
+            }
+            assert_eqs(E::enumerate_rev(..), E::enumerate(..).rev());
+        }
+        test::<SingleEnum>();
+        test::<DoubleEnum>();
+        test::<ManyEnum>();
+        test::<SparseEnum>();
+    }
+
+    #[test]
+    fn test_discriminant() {
+        assert_all(|e: SparseEnum| SparseEnum::from_discriminant(e.discriminant()) == Some(e));
+        assert_eq!(SparseEnum::C.index(), 2);
+        assert_eq!(SparseEnum::C.discriminant(), 10);
+        assert_eq!(SparseEnum::B.discriminant(), -3);
+        assert_eq!(SparseEnum::from_discriminant(2), None);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_laws() {
+        fn test<E: Debug + Enum>() {
+            crate::testing::assert_enum_laws::<E>();
+            crate::testing::assert_set_roundtrip::<E>();
+            crate::testing::assert_map_roundtrip::<E>();
+            assert_eq!(crate::testing::validate_enum_impl::<E>(), Ok(()));
+        }
+        test::<SingleEnum>();
+        test::<DoubleEnum>();
+        test::<ManyEnum>();
+        test::<SparseEnum>();
+        test::<bool>();
+        test::<Ordering>();
+        test::<u8>();
+        test::<i8>();
+    }
+
+    #[cfg(feature = "alloc")]
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Colliding {
+        A,
+        B,
+    }
+
+    #[cfg(feature = "alloc")]
+    impl Enum for Colliding {
+        type Rep = u8;
+        const SIZE: usize = 2;
+        const MIN: Self = Self::A;
+        const MAX: Self = Self::B;
+        const BITMASK: Self::Rep = 0b11;
+
+        fn succ(self) -> Option<Self> {
+            match self {
+                Self::A => Some(Self::B),
+                Self::B => None,
+            }
+        }
+
+        fn pred(self) -> Option<Self> {
+            match self {
+                Self::A => None,
+                Self::B => Some(Self::A),
+            }
+        }
+
+        fn bit(self) -> Self::Rep {
+            1
+        }
+
+        fn index(self) -> usize {
+            self as usize
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_validate_colliding() {
+        assert_eq!(
+            crate::testing::validate_enum_impl::<Colliding>(),
+            Err("B.bit() overlaps the bit of another value".to_owned())
+        );
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct Wide;
+
+    impl Enum for Wide {
+        type Rep = u8;
+        const SIZE: usize = 1;
+        const MIN: Self = Wide;
+        const MAX: Self = Wide;
+        const BITMASK: Self::Rep = 0b11;
+
+        fn succ(self) -> Option<Self> {
+            None
+        }
+
+        fn pred(self) -> Option<Self> {
+            None
+        }
+
+        fn bit(self) -> Self::Rep {
+            0b11
+        }
+
+        fn index(self) -> usize {
+            0
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic = "Enum::bit() must return a single bit within Enum::BITMASK"]
+    fn test_set_checks_bits() {
+        let mut set = EnumSet::new();
+        set.insert(Wide);
     }
 
     #[test]
@@ -319,5 +782,6 @@ mod tests {
         test::<SingleEnum>();
         test::<DoubleEnum>();
         test::<ManyEnum>();
+        test::<SparseEnum>();
     }
 }
@@ -0,0 +1,43 @@
+use core::error::Error;
+use core::fmt::{self, Display, Formatter};
+
+/// The error returned when parsing a string that is not the name of any variant.
+///
+/// This is returned by the [`FromStr`](std::str::FromStr) implementation that
+/// `#[enumeration(names)]` generates.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ParseEnumError;
+
+impl Display for ParseEnumError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("unknown variant name")
+    }
+}
+
+impl Error for ParseEnumError {}
+
+/// The error returned when converting an index that does not correspond to any value of an
+/// [`Enum`](crate::Enum).
+///
+/// This is returned by [`Enum::try_from_index`](crate::Enum::try_from_index), by the
+/// [`TryFrom<usize>`] implementation of [`Checked`](crate::Checked), and by the one that
+/// `#[enumeration(conversions)]` generates.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OutOfRange {
+    /// The index that was converted.
+    pub index: usize,
+    /// The number of values in the type, which `index` is not less than.
+    pub size: usize,
+}
+
+impl Display for OutOfRange {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "index {} is out of range for an enum of size {}",
+            self.index, self.size
+        )
+    }
+}
+
+impl Error for OutOfRange {}
@@ -1,15 +1,175 @@
-use std::fmt::Debug;
-use std::hash::Hash;
-use std::iter::{ExactSizeIterator, FusedIterator, Iterator};
+use core::fmt::Debug;
+use core::hash::Hash;
+use core::iter::{ExactSizeIterator, FusedIterator, Iterator};
+use core::ops::{Bound, RangeBounds};
 
 use super::enum_trait::Enum;
+use crate::wordlike::Wordlike;
 
 #[must_use = "iterators are lazy and do nothing unless consumed"]
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Enumeration<T> {
-    pub(super) finished: bool,
-    pub(super) start: T,
-    pub(super) end: T,
+    finished: bool,
+    start: T,
+    end: T,
+    /// Whether [deprecated](Enum::DEPRECATED) values are skipped. If so, and the enumeration is
+    /// not finished, neither `start` nor `end` is deprecated.
+    skip_deprecated: bool,
+}
+
+impl<T: Enum> Enumeration<T> {
+    pub(super) fn new<R: RangeBounds<T>>(range: R, skip_deprecated: bool) -> Self {
+        let start = match range.start_bound() {
+            Bound::Unbounded => T::MIN,
+            Bound::Included(&t) => t,
+            Bound::Excluded(&t) => match t.succ() {
+                Some(succ) => succ,
+                None => return Self::empty(),
+            },
+        };
+        let end = match range.end_bound() {
+            Bound::Unbounded => T::MAX,
+            Bound::Included(&t) => t,
+            Bound::Excluded(&t) => match t.pred() {
+                Some(pred) => pred,
+                None => return Self::empty(),
+            },
+        };
+        let mut enumeration = Self {
+            start,
+            end,
+            finished: false,
+            skip_deprecated,
+        };
+        while enumeration.start.index() <= enumeration.end.index() {
+            if !enumeration.skips(enumeration.start) {
+                // Stops at `start` at the latest.
+                while enumeration.skips(enumeration.end) {
+                    enumeration.end = enumeration
+                        .end
+                        .pred()
+                        .expect("got None from calling Enum::pred() where > Enum::MIN");
+                }
+                return enumeration;
+            }
+            match enumeration.start.succ() {
+                Some(succ) => enumeration.start = succ,
+                None => break,
+            }
+        }
+        Self::empty()
+    }
+
+    #[inline]
+    pub(super) fn empty() -> Self {
+        Self {
+            start: T::MIN,
+            end: T::MIN,
+            finished: true,
+            skip_deprecated: false,
+        }
+    }
+
+    #[inline]
+    fn skips(&self, x: T) -> bool {
+        self.skip_deprecated && x.is_deprecated()
+    }
+
+    /// Returns the number of skipped values between `start` and `end`, inclusive.
+    #[inline]
+    fn skipped(&self) -> usize {
+        if !self.skip_deprecated || T::DEPRECATED.is_empty() {
+            return 0;
+        }
+        let in_range = <T::Rep as Wordlike>::low_mask(self.end.index() + 1)
+            & !<T::Rep as Wordlike>::low_mask(self.start.index());
+        Wordlike::count_ones(T::DEPRECATED.to_raw() & in_range)
+    }
+
+    /// Divides the enumeration into two at a position.
+    ///
+    /// The first enumeration yields the first `n` remaining values, and the second yields the
+    /// rest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n > self.len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::Enum;
+    ///
+    /// let (left, right) = Ordering::enumerate(..).split_at(1);
+    /// assert_eq!(left.collect::<Vec<_>>(), [Ordering::Less]);
+    /// assert_eq!(right.collect::<Vec<_>>(), [Ordering::Equal, Ordering::Greater]);
+    /// ```
+    pub fn split_at(self, n: usize) -> (Self, Self) {
+        let len = self.len();
+        assert!(
+            n <= len,
+            "split position {n} is out of bounds for length {len}"
+        );
+        if n == 0 {
+            return (Self::empty(), self);
+        }
+        if n == len {
+            return (self, Self::empty());
+        }
+        let mut right = self.clone();
+        let last = right
+            .nth(n - 1)
+            .expect("got None from an Enumeration before reaching its length");
+        let left = Self { end: last, ..self };
+        (left, right)
+    }
+
+    /// Divides the enumeration into two halves. If the length is odd, the second half yields
+    /// one more value than the first.
+    ///
+    /// Splitting recursively partitions the values for divide-and-conquer processing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::Enum;
+    ///
+    /// let (left, right) = Ordering::enumerate(..).split_in_half();
+    /// assert_eq!(left.len(), 1);
+    /// assert_eq!(right.len(), 2);
+    /// ```
+    #[inline]
+    pub fn split_in_half(self) -> (Self, Self) {
+        let half = self.len() / 2;
+        self.split_at(half)
+    }
+
+    /// Collects the remaining values into an array, or returns `None` if there are not exactly
+    /// `N` of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::Enum;
+    ///
+    /// let all: [Ordering; 3] = Ordering::enumerate(..).collect_array().unwrap();
+    /// assert_eq!(all, [Ordering::Less, Ordering::Equal, Ordering::Greater]);
+    ///
+    /// assert_eq!(Ordering::enumerate(..).collect_array::<2>(), None);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn collect_array<const N: usize>(mut self) -> Option<[T; N]> {
+        if self.len() != N {
+            return None;
+        }
+        Some(core::array::from_fn(|_| {
+            self.next()
+                .expect("got None from an Enumeration before reaching its length")
+        }))
+    }
 }
 
 impl<T: Enum> Iterator for Enumeration<T> {
@@ -24,10 +184,16 @@ impl<T: Enum> Iterator for Enumeration<T> {
             Some(self.start)
         } else {
             let at = self.start;
-            self.start = at
-                .succ()
-                .expect("got None from calling Enum::succ() where < Enum::MAX");
-            Some(at)
+            // `end` is not skipped, so this stops there at the latest.
+            loop {
+                self.start = self
+                    .start
+                    .succ()
+                    .expect("got None from calling Enum::succ() where < Enum::MAX");
+                if !self.skips(self.start) {
+                    return Some(at);
+                }
+            }
         }
     }
 
@@ -42,7 +208,9 @@ impl<T: Enum> Iterator for Enumeration<T> {
         let mut accum = init;
         let mut val = self.start;
         loop {
-            accum = fold(accum, val);
+            if !self.skips(val) {
+                accum = fold(accum, val);
+            }
             if val == self.end {
                 return accum;
             }
@@ -58,6 +226,25 @@ impl<T: Enum> Iterator for Enumeration<T> {
         self.len()
     }
 
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len() {
+            self.finished = true;
+            return None;
+        }
+        if self.skipped() != 0 {
+            for _ in 0..n {
+                self.next();
+            }
+            return self.next();
+        }
+        self.start = self
+            .start
+            .forward(n)
+            .expect("got None from calling Enum::forward() within an Enumeration");
+        self.next()
+    }
+
     #[cfg_attr(feature = "inline-more", inline)]
     fn size_hint(&self) -> (usize, Option<usize>) {
         let exact = self.len();
@@ -74,11 +261,36 @@ impl<T: Enum> DoubleEndedIterator for Enumeration<T> {
             Some(self.end)
         } else {
             let at = self.end;
-            self.end = at
-                .pred()
-                .expect("got None from calling Enum::pred() where > Enum::MIN");
-            Some(at)
+            // `start` is not skipped, so this stops there at the latest.
+            loop {
+                self.end = self
+                    .end
+                    .pred()
+                    .expect("got None from calling Enum::pred() where > Enum::MIN");
+                if !self.skips(self.end) {
+                    return Some(at);
+                }
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len() {
+            self.finished = true;
+            return None;
+        }
+        if self.skipped() != 0 {
+            for _ in 0..n {
+                self.next_back();
+            }
+            return self.next_back();
         }
+        self.end = self
+            .end
+            .backward(n)
+            .expect("got None from calling Enum::backward() within an Enumeration");
+        self.next_back()
     }
 
     #[cfg_attr(feature = "inline-more", inline)]
@@ -92,7 +304,9 @@ impl<T: Enum> DoubleEndedIterator for Enumeration<T> {
         let mut accum = init;
         let mut val = self.end;
         loop {
-            accum = fold(accum, val);
+            if !self.skips(val) {
+                accum = fold(accum, val);
+            }
             if val == self.start {
                 return accum;
             }
@@ -110,7 +324,7 @@ impl<T: Enum> ExactSizeIterator for Enumeration<T> {
         if self.finished {
             0
         } else {
-            self.end.index() + 1 - self.start.index()
+            self.end.index() + 1 - self.start.index() - self.skipped()
         }
     }
 }
@@ -188,6 +402,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_split_at() {
+        for x in DemoEnum::enumerate(..) {
+            for y in DemoEnum::enumerate(x..) {
+                let range = DemoEnum::enumerate(x..=y);
+                for n in 0..=range.len() {
+                    let (left, right) = range.clone().split_at(n);
+                    assert_eq!(left.len(), n);
+                    assert_eqs(left.chain(right), range.clone());
+                }
+            }
+        }
+        let (left, right) = DemoEnum::enumerate(..).split_in_half();
+        assert_eq!(left, DemoEnum::enumerate(..=DemoEnum::E));
+        assert_eq!(right, DemoEnum::enumerate(DemoEnum::F..));
+    }
+
     #[test]
     fn test_rev() {
         let forward: Vec<_> = DemoEnum::enumerate(..).collect();
@@ -195,4 +426,65 @@ mod tests {
         backward.reverse();
         assert_eq!(forward, backward);
     }
+
+    #[test]
+    fn test_forward_backward() {
+        for x in DemoEnum::enumerate(..) {
+            for n in 0..=DemoEnum::SIZE {
+                assert_eq!(x.forward(n), DemoEnum::enumerate(x..).nth(n));
+                assert_eq!(x.backward(n), DemoEnum::enumerate(..=x).rev().nth(n));
+            }
+        }
+        assert_eq!(DemoEnum::A.forward(usize::MAX), None);
+    }
+
+    #[test]
+    fn test_nth() {
+        let all: Vec<_> = DemoEnum::enumerate(..).collect();
+        for skip in 0..=DemoEnum::SIZE {
+            for n in 0..=DemoEnum::SIZE {
+                let mut iter = DemoEnum::enumerate(..);
+                iter.nth(skip);
+                let mut naive = all.iter().copied();
+                naive.nth(skip);
+                assert_eq!(iter.nth(n), naive.nth(n));
+                assert_eqs(iter.clone(), naive.clone());
+                assert_eq!(iter.nth_back(n), naive.nth_back(n));
+                assert_eqs(iter, naive);
+            }
+        }
+    }
+
+    #[rustfmt::skip] #[allow(dead_code)]
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Enum)]
+    enum Sparse {
+        #[enumeration(deprecated)] A, B, #[enumeration(deprecated)] C,
+        #[enumeration(deprecated)] D, E, F, #[enumeration(deprecated)] G,
+    }
+
+    #[test]
+    fn test_skips_deprecated() {
+        for x in Sparse::enumerate_all(..) {
+            for y in Sparse::enumerate_all(..) {
+                let naive: Vec<_> = Sparse::enumerate_all(x..=y)
+                    .filter(|x| !x.is_deprecated())
+                    .collect();
+                let iter = Sparse::enumerate(x..=y);
+                assert_eq!(iter.len(), naive.len());
+                assert_eqs(iter.clone(), naive.iter().copied());
+                assert_eqs(iter.clone().rev(), naive.iter().rev().copied());
+                assert_eq!(iter.clone().fold(0, |n, _| n + 1), naive.len());
+                assert_eq!(iter.clone().rfold(0, |n, _| n + 1), naive.len());
+                for n in 0..=naive.len() {
+                    assert_eq!(iter.clone().nth(n), naive.get(n).copied());
+                    assert_eq!(iter.clone().nth_back(n), naive.iter().rev().nth(n).copied());
+                    let (left, right) = iter.clone().split_at(n);
+                    assert_eqs(left, naive[..n].iter().copied());
+                    assert_eqs(right, naive[n..].iter().copied());
+                }
+            }
+        }
+        assert_eq!(Sparse::enumerate(..).len(), 3);
+        assert_eq!(Sparse::enumerate_all(..).len(), Sparse::SIZE);
+    }
 }
@@ -0,0 +1,184 @@
+/// Implements [`Enum`](crate::Enum) for a fieldless enum by listing its variants in order.
+///
+/// This is a lightweight alternative to `#[derive(Enum)]` for builds that avoid procedural
+/// macros. The generated implementation follows the same rules as the derived one: indices
+/// follow the listed order, and [`NAMES`](crate::Enum::NAMES) holds the variant names. The
+/// type must be `Copy + Ord`, with variants declared in the order they are listed, and every
+/// variant must be listed.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::{Enum, EnumSet, impl_enum_for};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// pub enum Suit { Clubs, Diamonds, Hearts, Spades }
+///
+/// impl_enum_for! { Suit { Clubs, Diamonds, Hearts, Spades } }
+///
+/// assert_eq!(Suit::SIZE, 4);
+/// assert_eq!(Suit::Hearts.index(), 2);
+/// assert_eq!(Suit::from_name("Spades"), Some(Suit::Spades));
+/// assert_eq!(EnumSet::<Suit>::all().len(), 4);
+/// ```
+///
+/// Omitting a variant is a compile error:
+///
+/// ```compile_fail
+/// use enumeration::impl_enum_for;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// pub enum Suit { Clubs, Diamonds, Hearts, Spades }
+///
+/// impl_enum_for! { Suit { Clubs, Diamonds, Hearts } }
+/// ```
+#[macro_export]
+macro_rules! impl_enum_for {
+    ($t:ty { $($variant:ident),+ $(,)? }) => {
+        // Fails to compile if a variant is missing from the list.
+        const _: fn($t) = |value| match value {
+            $(<$t>::$variant => {})+
+        };
+
+        impl $crate::Enum for $t {
+            type Rep = <$crate::Width<{ [$(stringify!($variant)),+].len() }> as $crate::RangeWidth>::Rep;
+            const SIZE: usize = [$(stringify!($variant)),+].len();
+            const MIN: Self = [$(<$t>::$variant),+][0];
+            const MAX: Self = [$(<$t>::$variant),+][<Self as $crate::Enum>::SIZE - 1];
+            const BITMASK: Self::Rep =
+                <$crate::Width<{ [$(stringify!($variant)),+].len() }> as $crate::RangeWidth>::BITMASK;
+            const NAMES: &'static [&'static str] = &[$(stringify!($variant)),+];
+
+            #[inline]
+            fn succ(self) -> ::core::option::Option<Self> {
+                <Self as $crate::Enum>::from_index(<Self as $crate::Enum>::index(self) + 1)
+            }
+
+            #[inline]
+            fn pred(self) -> ::core::option::Option<Self> {
+                match <Self as $crate::Enum>::index(self).checked_sub(1) {
+                    ::core::option::Option::Some(i) => <Self as $crate::Enum>::from_index(i),
+                    ::core::option::Option::None => ::core::option::Option::None,
+                }
+            }
+
+            #[inline]
+            fn bit(self) -> Self::Rep {
+                <$crate::Width<{ [$(stringify!($variant)),+].len() }> as $crate::RangeWidth>::bit(
+                    <Self as $crate::Enum>::index(self),
+                )
+            }
+
+            #[allow(unused_assignments)]
+            #[inline]
+            fn index(self) -> usize {
+                let mut i = 0;
+                $(
+                    if let <$t>::$variant = self {
+                        return i;
+                    }
+                    i += 1;
+                )+
+                ::core::unreachable!()
+            }
+
+            #[inline]
+            fn from_index(i: usize) -> ::core::option::Option<Self> {
+                [$(<$t>::$variant),+].get(i).copied()
+            }
+        }
+    };
+}
+
+/// Converts between two enums that share variant names, such as two versions of a protocol
+/// enum.
+///
+/// `map_enum! { Old => New { A, B, C } }` implements `From<Old> for New`, mapping each variant
+/// to the variant of the same name. Every variant of `Old` must be listed, and every listed
+/// variant must exist in `New`, or the build fails. It also implements `TryFrom<New> for Old`,
+/// which fails with the original value for variants of `New` that are not listed.
+///
+/// [`EnumMap::migrate_keys`](crate::EnumMap::migrate_keys) uses the `From` conversion to move a
+/// whole map over.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::map_enum;
+///
+/// mod v1 {
+///     #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+///     pub enum Status { Active, Suspended }
+/// }
+///
+/// mod v2 {
+///     #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+///     pub enum Status { Pending, Active, Suspended }
+/// }
+///
+/// map_enum! { v1::Status => v2::Status { Active, Suspended } }
+///
+/// assert_eq!(v2::Status::from(v1::Status::Suspended), v2::Status::Suspended);
+/// assert_eq!(v1::Status::try_from(v2::Status::Active), Ok(v1::Status::Active));
+/// assert_eq!(v1::Status::try_from(v2::Status::Pending), Err(v2::Status::Pending));
+/// ```
+///
+/// Leaving out a variant of the source enum is a compile error:
+///
+/// ```compile_fail
+/// use enumeration::map_enum;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+/// pub enum Old { Active, Suspended }
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+/// pub enum New { Active, Suspended }
+///
+/// map_enum! { Old => New { Active } }
+/// ```
+#[macro_export]
+macro_rules! map_enum {
+    ($from:ty => $to:ty { $($variant:ident),+ $(,)? }) => {
+        impl ::core::convert::From<$from> for $to {
+            #[inline]
+            fn from(value: $from) -> Self {
+                match value {
+                    $(<$from>::$variant => <$to>::$variant,)+
+                }
+            }
+        }
+
+        impl ::core::convert::TryFrom<$to> for $from {
+            type Error = $to;
+
+            #[inline]
+            fn try_from(value: $to) -> ::core::result::Result<Self, Self::Error> {
+                match value {
+                    $(<$to>::$variant => ::core::result::Result::Ok(<$from>::$variant),)+
+                    #[allow(unreachable_patterns)]
+                    other => ::core::result::Result::Err(other),
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    use crate::testing::{assert_enum_laws, assert_map_roundtrip, validate_enum_impl};
+
+    #[rustfmt::skip]
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum DemoEnum { A, B, C, D, E, F, G, H, I, J }
+
+    impl_enum_for! { DemoEnum { A, B, C, D, E, F, G, H, I, J } }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_laws() {
+        assert_enum_laws::<DemoEnum>();
+        assert_map_roundtrip::<DemoEnum>();
+        assert_eq!(validate_enum_impl::<DemoEnum>(), Ok(()));
+    }
+}
@@ -1,5 +1,17 @@
+#[macro_use]
+mod macros;
+
+mod checked;
+pub use checked::Checked;
+
 mod enum_trait;
 pub use enum_trait::Enum;
 
+mod error;
+pub use error::{OutOfRange, ParseEnumError};
+
 mod iter;
 pub use iter::Enumeration;
+
+mod range;
+pub use range::{EnumRange, RangeWidth, Width};
@@ -0,0 +1,330 @@
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+
+use super::enum_trait::Enum;
+use crate::wordlike::{const_set_bit, const_test_bit, Wordlike};
+use crate::EnumSet;
+
+/// Marker for the number of values in an [`EnumRange`].
+///
+/// [`Width<N>`] implements [`RangeWidth`] for every `N` from 1 to 128.
+pub struct Width<const N: usize>;
+
+/// Selects the smallest [`Wordlike`] representation that fits `N` values.
+pub trait RangeWidth {
+    /// Bitwise representation of a range with this width.
+    type Rep: Wordlike;
+
+    /// Bitmask with the lowest `N` bits set to one.
+    const BITMASK: Self::Rep;
+
+    /// Returns the bit for the value at offset `i`.
+    fn bit(i: usize) -> Self::Rep;
+}
+
+macro_rules! impl_width {
+    ($t:ty: $($n:literal),+) => {
+        $(
+            impl RangeWidth for Width<$n> {
+                type Rep = $t;
+                const BITMASK: $t = !0 >> (<$t>::BITS - $n);
+
+                #[inline]
+                fn bit(i: usize) -> $t {
+                    1 << i
+                }
+            }
+        )+
+    };
+}
+
+impl_width!(u8: 1, 2, 3, 4, 5, 6, 7, 8);
+impl_width!(u16: 9, 10, 11, 12, 13, 14, 15, 16);
+impl_width!(u32: 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32);
+impl_width!(u64: 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64);
+impl_width!(u128: 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 96, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 115, 116, 117, 118, 119, 120, 121, 122, 123, 124, 125, 126, 127, 128);
+
+/// A contiguous window of `LEN` values of `T`, starting at index `START`.
+///
+/// `EnumRange` implements [`Enum`] with [`SIZE`](Enum::SIZE) equal to `LEN`, so sets and maps
+/// over it only cover the window and use the smallest representation that fits, regardless of
+/// how large `T` is. Values of `T` that are [deprecated](Enum::DEPRECATED) are deprecated in the
+/// window as well, so [`enumerate`](Enum::enumerate) skips them and
+/// [`enumerate_all`](Enum::enumerate_all) still yields them.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::{Enum, EnumRange, EnumSet};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Opcode { Nop, Load, Store, Add, Sub, Mul, Div, Halt }
+///
+/// // Version 1 of the protocol only allows arithmetic.
+/// type Arithmetic = EnumRange<Opcode, 3, 4>;
+///
+/// let add = Arithmetic::new(Opcode::Add).unwrap();
+/// assert_eq!(add.get(), Opcode::Add);
+/// assert_eq!(Arithmetic::new(Opcode::Halt), None);
+///
+/// let all: Vec<_> = Arithmetic::enumerate(..).map(Arithmetic::get).collect();
+/// assert_eq!(all, [Opcode::Add, Opcode::Sub, Opcode::Mul, Opcode::Div]);
+/// assert_eq!(std::mem::size_of::<EnumSet<Arithmetic>>(), 1);
+/// ```
+pub struct EnumRange<T: Enum, const START: usize, const LEN: usize> {
+    offset: usize,
+    marker: PhantomData<T>,
+}
+
+impl<T: Enum, const START: usize, const LEN: usize> EnumRange<T, START, LEN> {
+    const VALID: () = assert!(
+        LEN > 0 && START + LEN <= T::SIZE,
+        "EnumRange window is out of bounds"
+    );
+
+    #[inline]
+    const fn at(offset: usize) -> Self {
+        Self {
+            offset,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the value of the window corresponding to `value`, or `None` if `value` lies
+    /// outside the window.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumRange;
+    ///
+    /// type NotLess = EnumRange<Ordering, 1, 2>;
+    /// assert!(NotLess::new(Ordering::Greater).is_some());
+    /// assert!(NotLess::new(Ordering::Less).is_none());
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new(value: T) -> Option<Self> {
+        let () = Self::VALID;
+        let offset = value.index().checked_sub(START)?;
+        if offset < LEN {
+            Some(Self::at(offset))
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if `value` lies inside the window.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumRange;
+    ///
+    /// type NotLess = EnumRange<Ordering, 1, 2>;
+    /// assert!(NotLess::contains(Ordering::Equal));
+    /// assert!(!NotLess::contains(Ordering::Less));
+    /// ```
+    #[inline]
+    pub fn contains(value: T) -> bool {
+        Self::new(value).is_some()
+    }
+
+    /// Returns the underlying value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::{Enum, EnumRange};
+    ///
+    /// type NotLess = EnumRange<Ordering, 1, 2>;
+    /// assert_eq!(NotLess::MIN.get(), Ordering::Equal);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get(self) -> T {
+        match T::from_index(START + self.offset) {
+            Some(value) => value,
+            None => unreachable!("EnumRange window is out of bounds"),
+        }
+    }
+}
+
+impl<T: Enum, const START: usize, const LEN: usize> Enum for EnumRange<T, START, LEN>
+where
+    Width<LEN>: RangeWidth,
+{
+    type Rep = <Width<LEN> as RangeWidth>::Rep;
+    const SIZE: usize = LEN;
+    const MIN: Self = {
+        let () = Self::VALID;
+        Self::at(0)
+    };
+    const MAX: Self = {
+        let () = Self::VALID;
+        Self::at(LEN - 1)
+    };
+    const BITMASK: Self::Rep = <Width<LEN> as RangeWidth>::BITMASK;
+    const NAMES: &'static [&'static str] = if T::NAMES.is_empty() {
+        &[]
+    } else {
+        T::NAMES.split_at(START).1.split_at(LEN).0
+    };
+    const DEPRECATED: EnumSet<Self> = {
+        let deprecated = T::DEPRECATED.to_raw();
+        let mut raw = <Self::Rep as Wordlike>::ZERO;
+        let mut i = 0;
+        while i < LEN {
+            if const_test_bit(&deprecated, START + i) {
+                const_set_bit(&mut raw, i);
+            }
+            i += 1;
+        }
+        EnumSet::from_raw_unchecked(raw)
+    };
+
+    #[inline]
+    fn succ(self) -> Option<Self> {
+        Self::from_index(self.offset + 1)
+    }
+
+    #[inline]
+    fn pred(self) -> Option<Self> {
+        self.offset.checked_sub(1).map(Self::at)
+    }
+
+    #[inline]
+    fn bit(self) -> Self::Rep {
+        <Width<LEN> as RangeWidth>::bit(self.offset)
+    }
+
+    #[inline]
+    fn index(self) -> usize {
+        self.offset
+    }
+
+    #[inline]
+    fn from_index(i: usize) -> Option<Self> {
+        let () = Self::VALID;
+        if i < LEN {
+            Some(Self::at(i))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Enum, const START: usize, const LEN: usize> Copy for EnumRange<T, START, LEN> {}
+
+#[allow(clippy::expl_impl_clone_on_copy)]
+impl<T: Enum, const START: usize, const LEN: usize> Clone for EnumRange<T, START, LEN> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Enum, const START: usize, const LEN: usize> PartialEq for EnumRange<T, START, LEN> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.offset == other.offset
+    }
+}
+
+impl<T: Enum, const START: usize, const LEN: usize> Eq for EnumRange<T, START, LEN> {}
+
+impl<T: Enum, const START: usize, const LEN: usize> PartialOrd for EnumRange<T, START, LEN> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Enum, const START: usize, const LEN: usize> Ord for EnumRange<T, START, LEN> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.offset.cmp(&other.offset)
+    }
+}
+
+impl<T: Enum, const START: usize, const LEN: usize> Hash for EnumRange<T, START, LEN> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.offset.hash(state);
+    }
+}
+
+impl<T: Enum + Debug, const START: usize, const LEN: usize> Debug for EnumRange<T, START, LEN> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rustfmt::skip] #[allow(dead_code)]
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Enum)]
+    enum DemoEnum { A, B, C, D, E, F, G, H, I, J }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_laws() {
+        crate::testing::assert_enum_laws::<EnumRange<DemoEnum, 0, 10>>();
+        crate::testing::assert_enum_laws::<EnumRange<DemoEnum, 3, 4>>();
+        crate::testing::assert_enum_laws::<EnumRange<DemoEnum, 9, 1>>();
+        crate::testing::assert_set_roundtrip::<EnumRange<DemoEnum, 2, 5>>();
+        crate::testing::assert_map_roundtrip::<EnumRange<DemoEnum, 2, 5>>();
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    enum Versioned {
+        A,
+        #[enumeration(deprecated)]
+        B,
+        C,
+        #[enumeration(deprecated)]
+        D,
+        E,
+    }
+
+    #[test]
+    fn test_deprecated() {
+        type Window = EnumRange<Versioned, 1, 4>;
+        fn get(values: impl Iterator<Item = Window>) -> Vec<Versioned> {
+            values.map(Window::get).collect()
+        }
+
+        assert_eq!(Window::DEPRECATED.len(), 2);
+        assert!(Window::new(Versioned::D).unwrap().is_deprecated());
+        assert!(!Window::new(Versioned::C).unwrap().is_deprecated());
+        assert_eq!(Window::enumerate(..).len(), 2);
+        assert_eq!(
+            Window::enumerate(..).nth(1).map(Window::get),
+            Some(Versioned::E)
+        );
+        assert_eq!(Window::enumerate(..).nth(2), None);
+        assert_eq!(get(Window::enumerate(..)), [Versioned::C, Versioned::E]);
+        assert_eq!(get(Window::enumerate_rev(..)), [Versioned::E, Versioned::C]);
+        assert_eq!(Window::enumerate_all(..).len(), 4);
+        assert_eq!(
+            Window::enumerate_all(..).nth(2).map(Window::get),
+            Some(Versioned::D)
+        );
+        assert_eq!(
+            get(Window::enumerate_all(..)),
+            [Versioned::B, Versioned::C, Versioned::D, Versioned::E]
+        );
+    }
+
+    #[test]
+    fn test_names() {
+        type Window = EnumRange<DemoEnum, 3, 2>;
+        assert_eq!(Window::NAMES, ["D", "E"]);
+        assert_eq!(Window::from_name("E").map(Window::get), Some(DemoEnum::E));
+        assert_eq!(Window::from_name("A"), None);
+    }
+}
@@ -0,0 +1,132 @@
+use alloc::vec::Vec;
+use core::mem;
+
+use bytemuck::Pod;
+
+use crate::{Enum, EnumMap, EnumSet, Wordlike};
+
+/// Reads the value stored at slot `i` of a buffer of consecutive values.
+fn read_slot<V: Pod>(bytes: &[u8], i: usize) -> V {
+    let size = mem::size_of::<V>();
+    bytemuck::pod_read_unaligned(&bytes[i * size..(i + 1) * size])
+}
+
+impl<K: Enum, V: Pod> EnumMap<K, V> {
+    /// Returns the raw bytes of the values in key order, without copying, or `None` unless
+    /// every key is occupied. [`from_bytes`](Self::from_bytes) restores the map.
+    ///
+    /// The bytes use the platform's layout for `V`, so they are only meaningful to a reader
+    /// with the same endianness and layout. Like [`as_full_slice`](Self::as_full_slice), this
+    /// needs the `uninit-storage` feature; [`to_masked_bytes`](Self::to_masked_bytes) copies
+    /// the values instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(Ordering::Less, 1u16), (Ordering::Equal, 2), (Ordering::Greater, 3)]);
+    /// let bytes = map.as_bytes().unwrap();
+    /// assert_eq!(bytes.len(), 6);
+    /// assert_eq!(EnumMap::from_bytes(bytes), Some(map));
+    ///
+    /// assert_eq!(EnumMap::<Ordering, u16>::new().as_bytes(), None);
+    /// ```
+    #[cfg(feature = "uninit-storage")]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(all(feature = "bytemuck", feature = "uninit-storage")))
+    )]
+    #[inline]
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        self.as_full_slice().map(bytemuck::cast_slice)
+    }
+
+    /// Creates a full map from bytes holding one `V` per key in key order, as returned by
+    /// `as_bytes`. Returns `None` if `bytes` does not hold exactly one `V` per key. `bytes` need
+    /// not be aligned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let bytes: Vec<u8> = [1u16, 2, 3].iter().flat_map(|x| x.to_ne_bytes()).collect();
+    /// let map = EnumMap::<Ordering, u16>::from_bytes(&bytes).unwrap();
+    /// assert_eq!(map[Ordering::Equal], 2);
+    /// assert_eq!(EnumMap::<Ordering, u16>::from_bytes(&bytes[1..]), None);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != K::SIZE * mem::size_of::<V>() {
+            return None;
+        }
+        Some(
+            K::enumerate_all(..)
+                .map(|k| (k, read_slot(bytes, k.index())))
+                .collect(),
+        )
+    }
+
+    /// Serializes the map, including vacant keys, into bytes that
+    /// [`from_masked_bytes`](Self::from_masked_bytes) restores.
+    ///
+    /// The bytes begin with the set of occupied keys, as little-endian 64-bit
+    /// [`words`](EnumSet::words), followed by one `V` per key in key order. Vacant keys are
+    /// written as zeroes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(Ordering::Greater, 7u32)]);
+    /// let bytes = map.to_masked_bytes();
+    /// assert_eq!(bytes.len(), 8 + 3 * 4);
+    /// assert_eq!(bytes[0], 0b100);
+    /// assert_eq!(EnumMap::from_masked_bytes(&bytes), Some(map));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+    pub fn to_masked_bytes(&self) -> Vec<u8> {
+        let size = mem::size_of::<V>();
+        let mut bytes = Vec::with_capacity(K::Rep::WORDS * 8 + K::SIZE * size);
+        for word in EnumSet::from(self).words() {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        for k in K::enumerate_all(..) {
+            match self.get(k) {
+                Some(value) => bytes.extend_from_slice(bytemuck::bytes_of(value)),
+                None => bytes.resize(bytes.len() + size, 0),
+            }
+        }
+        bytes
+    }
+
+    /// Creates a map from bytes produced by [`to_masked_bytes`](Self::to_masked_bytes).
+    /// Returns `None` if the length of `bytes` is wrong, or if the occupancy mask has bits set
+    /// that do not correspond to any key. `bytes` need not be aligned.
+    #[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+    pub fn from_masked_bytes(bytes: &[u8]) -> Option<Self> {
+        let mask_len = K::Rep::WORDS * 8;
+        if bytes.len() != mask_len + K::SIZE * mem::size_of::<V>() {
+            return None;
+        }
+        let (mask, values) = bytes.split_at(mask_len);
+        let words: Vec<u64> = mask
+            .chunks_exact(8)
+            .map(|word| u64::from_le_bytes(word.try_into().unwrap()))
+            .collect();
+        let keys = EnumSet::<K>::from_words(&words);
+        if !keys.words().eq(words.iter().copied()) {
+            return None;
+        }
+        Some(
+            keys.into_iter()
+                .map(|k| (k, read_slot(values, k.index())))
+                .collect(),
+        )
+    }
+}
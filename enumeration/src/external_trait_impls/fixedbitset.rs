@@ -0,0 +1,61 @@
+use fixedbitset::FixedBitSet;
+
+use crate::{Enum, EnumSet, OutOfRange};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "fixedbitset")))]
+impl<T: Enum> From<EnumSet<T>> for FixedBitSet {
+    /// Converts a set into a `FixedBitSet` of length `T::SIZE`, with bit `i` set if the set
+    /// contains the value at index `i`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, enums};
+    /// use fixedbitset::FixedBitSet;
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
+    ///
+    /// let bits = FixedBitSet::from(enums![TextStyle::Italic, TextStyle::Underline]);
+    /// assert_eq!(bits.len(), 4);
+    /// assert_eq!(bits.ones().collect::<Vec<_>>(), [1, 3]);
+    /// ```
+    fn from(set: EnumSet<T>) -> Self {
+        let mut bits = Self::with_capacity(T::SIZE);
+        for x in set {
+            bits.insert(x.index());
+        }
+        bits
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "fixedbitset")))]
+impl<T: Enum> TryFrom<&FixedBitSet> for EnumSet<T> {
+    type Error = OutOfRange;
+
+    /// Converts a `FixedBitSet` into a set, failing on the first set bit that does not
+    /// correspond to any value of `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, OutOfRange, enums};
+    /// use fixedbitset::FixedBitSet;
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
+    ///
+    /// let mut bits = FixedBitSet::with_capacity(10);
+    /// bits.insert(2);
+    /// assert_eq!(EnumSet::try_from(&bits), Ok(enums![TextStyle::Strikeout]));
+    ///
+    /// bits.insert(7);
+    /// assert_eq!(
+    ///     EnumSet::<TextStyle>::try_from(&bits),
+    ///     Err(OutOfRange { index: 7, size: 4 })
+    /// );
+    /// ```
+    fn try_from(bits: &FixedBitSet) -> Result<Self, Self::Error> {
+        bits.ones().map(T::try_from_index).collect()
+    }
+}
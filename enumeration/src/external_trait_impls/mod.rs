@@ -1,2 +1,14 @@
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
+#[cfg(feature = "fixedbitset")]
+mod fixedbitset;
+#[cfg(feature = "rand")]
+mod rand;
+#[cfg(feature = "rayon")]
+mod rayon;
 #[cfg(feature = "serde")]
-mod serde;
+pub(crate) mod serde;
+#[cfg(feature = "smallvec")]
+mod smallvec;
+#[cfg(feature = "wasm")]
+mod wasm;
@@ -0,0 +1,65 @@
+use rand::Rng;
+
+use crate::{Enum, EnumMap};
+
+impl<K: Enum, V> EnumMap<K, V> {
+    /// Picks an entry at random, with each entry's chance proportional to its weight. Entries
+    /// whose weight is not a positive finite number are never picked. Returns `None` if no
+    /// entry can be picked.
+    ///
+    /// `weight` is called twice for each entry, and must return the same result both times.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumMap};
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Rarity { Common, Rare, Legendary }
+    ///
+    /// let drop_rates = EnumMap::from([(Rarity::Common, 0.9), (Rarity::Rare, 0.1)]);
+    /// let mut rng = StdRng::seed_from_u64(7);
+    ///
+    /// let (rarity, _) = drop_rates.sample_weighted(&mut rng, |&rate| rate).unwrap();
+    /// assert_ne!(rarity, Rarity::Legendary);
+    ///
+    /// let no_drops = EnumMap::from([(Rarity::Common, 0.0)]);
+    /// assert_eq!(no_drops.sample_weighted(&mut rng, |&rate| rate), None);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    pub fn sample_weighted<R, F>(&self, rng: &mut R, weight: F) -> Option<(K, &V)>
+    where
+        R: Rng + ?Sized,
+        F: Fn(&V) -> f64,
+    {
+        let valid = |v: &V| {
+            let w = weight(v);
+            if w.is_finite() && w > 0.0 {
+                w
+            } else {
+                0.0
+            }
+        };
+        let total: f64 = self.values().map(valid).sum();
+        if !total.is_finite() || total <= 0.0 {
+            return None;
+        }
+        let mut remaining = rng.random::<f64>() * total;
+        let mut last = None;
+        for (k, v) in self {
+            let w = valid(v);
+            if w == 0.0 {
+                continue;
+            }
+            if remaining < w {
+                return Some((k, v));
+            }
+            remaining -= w;
+            last = Some((k, v));
+        }
+        // Rounding can leave a sliver past the last weight.
+        last
+    }
+}
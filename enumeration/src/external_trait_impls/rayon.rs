@@ -0,0 +1,38 @@
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+
+use crate::map::Chunk;
+use crate::{Enum, EnumMap, Enumeration};
+
+impl<K: Enum + Send, V: Sync> EnumMap<K, V> {
+    /// A parallel iterator over windows of `size` consecutive keys, paired with iterators over
+    /// the values at those keys. This is the parallel equivalent of [`chunks`](Self::chunks).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    /// use rayon::iter::ParallelIterator;
+    ///
+    /// let map = EnumMap::from([(Ordering::Less, 1), (Ordering::Greater, 3)]);
+    /// let sums: Vec<i32> = map
+    ///     .par_chunks(2)
+    ///     .map(|(_, values)| values.flatten().sum())
+    ///     .collect();
+    /// assert_eq!(sums, [1, 3]);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    pub fn par_chunks(
+        &self,
+        size: usize,
+    ) -> impl IndexedParallelIterator<Item = (Enumeration<K>, Chunk<'_, V>)> {
+        let chunks = self.chunks(size);
+        (0..chunks.len())
+            .into_par_iter()
+            .map(move |i| chunks.chunk(i))
+    }
+}
@@ -1,10 +1,11 @@
-use std::fmt::{self, Formatter};
-use std::marker::PhantomData;
+use alloc::string::String;
+use core::fmt::{self, Formatter};
+use core::marker::PhantomData;
 
-use serde::de::{MapAccess, Visitor};
+use serde::de::{MapAccess, SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{Enum, EnumMap, EnumSet};
+use crate::{Enum, EnumMap, EnumSet, LossySet};
 
 impl<T> Serialize for EnumSet<T>
 where
@@ -74,7 +75,7 @@ where
     V: Deserialize<'de>,
 {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        struct MapVisitor<K, V> {
+        struct MapVisitor<K: Enum, V> {
             marker: PhantomData<EnumMap<K, V>>,
         }
 
@@ -105,6 +106,419 @@ where
     }
 }
 
+impl<T: Enum> Serialize for LossySet<T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.names())
+    }
+}
+
+impl<'de, T: Enum> Deserialize<'de> for LossySet<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SeqVisitor<T> {
+            marker: PhantomData<T>,
+        }
+
+        impl<'de, T: Enum> Visitor<'de> for SeqVisitor<T> {
+            type Value = LossySet<T>;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                formatter.write_str("a sequence of variant names")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut values = LossySet::new();
+                while let Some(name) = seq.next_element::<String>()? {
+                    values.insert_name(&name);
+                }
+                Ok(values)
+            }
+        }
+
+        let visitor = SeqVisitor {
+            marker: PhantomData,
+        };
+        deserializer.deserialize_seq(visitor)
+    }
+}
+
+/// Encoding of a single value, shared by [`by_name`] and [`by_index`].
+trait KeyCodec {
+    /// Describes a sequence of encoded values, for error messages.
+    const SEQ: &'static str;
+    /// Describes a map keyed by encoded values, for error messages.
+    const MAP: &'static str;
+
+    fn serialize_key<T: Enum, S: Serializer>(value: T, serializer: S) -> Result<S::Ok, S::Error>;
+
+    fn deserialize_key<'de, T: Enum, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error>;
+}
+
+/// A value (de)serialized through the codec `C`.
+struct Key<C, T>(T, PhantomData<C>);
+
+impl<C, T> Key<C, T> {
+    #[inline]
+    fn new(value: T) -> Self {
+        Self(value, PhantomData)
+    }
+}
+
+impl<C: KeyCodec, T: Enum> Serialize for Key<C, T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        C::serialize_key(self.0, serializer)
+    }
+}
+
+impl<'de, C: KeyCodec, T: Enum> Deserialize<'de> for Key<C, T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        C::deserialize_key(deserializer).map(Self::new)
+    }
+}
+
+/// Collections whose keys can be written through a [`KeyCodec`].
+trait SerializeKeyed {
+    fn serialize_keyed<C: KeyCodec, S: Serializer>(&self, serializer: S)
+        -> Result<S::Ok, S::Error>;
+}
+
+/// Collections whose keys can be read through a [`KeyCodec`].
+trait DeserializeKeyed<'de>: Sized {
+    fn deserialize_keyed<C: KeyCodec, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error>;
+}
+
+impl<T: Enum> SerializeKeyed for EnumSet<T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn serialize_keyed<C: KeyCodec, S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.into_iter().map(Key::<C, T>::new))
+    }
+}
+
+impl<'de, T: Enum> DeserializeKeyed<'de> for EnumSet<T> {
+    fn deserialize_keyed<C: KeyCodec, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        struct SeqVisitor<C, T> {
+            marker: PhantomData<(C, T)>,
+        }
+
+        impl<'de, C: KeyCodec, T: Enum> Visitor<'de> for SeqVisitor<C, T> {
+            type Value = EnumSet<T>;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                formatter.write_str(C::SEQ)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut values = EnumSet::new();
+                while let Some(Key::<C, T>(value, _)) = seq.next_element()? {
+                    values.insert(value);
+                }
+                Ok(values)
+            }
+        }
+
+        let visitor = SeqVisitor::<C, T> {
+            marker: PhantomData,
+        };
+        deserializer.deserialize_seq(visitor)
+    }
+}
+
+impl<K: Enum, V: Serialize> SerializeKeyed for EnumMap<K, V> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn serialize_keyed<C: KeyCodec, S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter().map(|(k, v)| (Key::<C, K>::new(k), v)))
+    }
+}
+
+impl<'de, K: Enum, V: Deserialize<'de>> DeserializeKeyed<'de> for EnumMap<K, V> {
+    fn deserialize_keyed<C: KeyCodec, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        struct MapVisitor<C, K: Enum, V> {
+            marker: PhantomData<(C, EnumMap<K, V>)>,
+        }
+
+        impl<'de, C: KeyCodec, K: Enum, V: Deserialize<'de>> Visitor<'de> for MapVisitor<C, K, V> {
+            type Value = EnumMap<K, V>;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                formatter.write_str(C::MAP)
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut values = EnumMap::new();
+                while let Some((Key::<C, K>(k, _), v)) = map.next_entry()? {
+                    values.insert(k, v);
+                }
+                Ok(values)
+            }
+        }
+
+        let visitor = MapVisitor::<C, K, V> {
+            marker: PhantomData,
+        };
+        deserializer.deserialize_map(visitor)
+    }
+}
+
+/// Name-based (de)serialization for use with `#[serde(with = "enumeration::serde::by_name")]`.
+///
+/// Keys of an [`EnumMap`] and elements of an [`EnumSet`] are written as the variant names
+/// provided by [`Enum::NAMES`], so the enum itself does not need to implement `Serialize` or
+/// `Deserialize`. Serialization fails if the enum does not provide names.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::{Enum, EnumMap, EnumSet, enums};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Season { Winter, Spring, Summer, Fall }
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Forecast {
+///     #[serde(with = "enumeration::serde::by_name")]
+///     rainy: EnumSet<Season>,
+///     #[serde(with = "enumeration::serde::by_name")]
+///     rainfall: EnumMap<Season, u32>,
+/// }
+///
+/// let forecast = Forecast {
+///     rainy: enums![Season::Spring, Season::Fall],
+///     rainfall: EnumMap::from([(Season::Spring, 13)]),
+/// };
+/// let json = serde_json::to_string(&forecast).unwrap();
+/// assert_eq!(json, r#"{"rainy":["Spring","Fall"],"rainfall":{"Spring":13}}"#);
+/// assert_eq!(serde_json::from_str::<Forecast>(&json).unwrap(), forecast);
+/// ```
+pub mod by_name {
+    use core::fmt::{self, Formatter};
+    use core::marker::PhantomData;
+
+    use serde::de::{self, Visitor};
+    use serde::ser::{self, Serializer};
+    use serde::Deserializer;
+
+    use super::{DeserializeKeyed, KeyCodec, SerializeKeyed};
+    use crate::Enum;
+
+    struct Name;
+
+    impl KeyCodec for Name {
+        const SEQ: &'static str = "a sequence of variant names";
+        const MAP: &'static str = "a map keyed by variant names";
+
+        fn serialize_key<T: Enum, S: Serializer>(
+            value: T,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match value.name() {
+                Some(name) => serializer.serialize_str(name),
+                None => Err(ser::Error::custom("enum does not provide variant names")),
+            }
+        }
+
+        fn deserialize_key<'de, T: Enum, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<T, D::Error> {
+            struct NameVisitor<T> {
+                marker: PhantomData<T>,
+            }
+
+            impl<T: Enum> Visitor<'_> for NameVisitor<T> {
+                type Value = T;
+
+                fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                    formatter.write_str("a variant name")
+                }
+
+                fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                    T::from_name(v).ok_or_else(|| E::unknown_variant(v, T::NAMES))
+                }
+            }
+
+            let visitor = NameVisitor {
+                marker: PhantomData,
+            };
+            deserializer.deserialize_str(visitor)
+        }
+    }
+
+    /// Types that can be serialized by [`by_name`](self).
+    pub trait SerializeByName {
+        fn serialize_by_name<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+    }
+
+    /// Types that can be deserialized by [`by_name`](self).
+    pub trait DeserializeByName<'de>: Sized {
+        fn deserialize_by_name<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+    }
+
+    impl<T: SerializeKeyed> SerializeByName for T {
+        #[cfg_attr(feature = "inline-more", inline)]
+        fn serialize_by_name<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.serialize_keyed::<Name, S>(serializer)
+        }
+    }
+
+    impl<'de, T: DeserializeKeyed<'de>> DeserializeByName<'de> for T {
+        #[cfg_attr(feature = "inline-more", inline)]
+        fn deserialize_by_name<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            T::deserialize_keyed::<Name, D>(deserializer)
+        }
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: SerializeByName,
+        S: Serializer,
+    {
+        value.serialize_by_name(serializer)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: DeserializeByName<'de>,
+        D: Deserializer<'de>,
+    {
+        T::deserialize_by_name(deserializer)
+    }
+}
+
+/// Index-based (de)serialization for use with `#[serde(with = "enumeration::serde::by_index")]`.
+///
+/// Keys of an [`EnumMap`] and elements of an [`EnumSet`] are written as their
+/// [`stable_index`](Enum::stable_index), so the enum itself does not need to implement
+/// `Serialize` or `Deserialize`. For enums that pin their indices with
+/// `#[enumeration(index = N)]`, data written by older versions of the enum can still be read
+/// after variants are reordered or inserted.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::{Enum, EnumMap, EnumSet, enums};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Season {
+///     #[enumeration(index = 1)]
+///     Winter,
+///     #[enumeration(index = 2)]
+///     Spring,
+///     #[enumeration(index = 0)]
+///     Summer,
+///     #[enumeration(index = 3)]
+///     Fall,
+/// }
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Forecast {
+///     #[serde(with = "enumeration::serde::by_index")]
+///     rainy: EnumSet<Season>,
+///     #[serde(with = "enumeration::serde::by_index")]
+///     rainfall: EnumMap<Season, u32>,
+/// }
+///
+/// let forecast = Forecast {
+///     rainy: enums![Season::Spring, Season::Summer],
+///     rainfall: EnumMap::from([(Season::Spring, 13)]),
+/// };
+/// let json = serde_json::to_string(&forecast).unwrap();
+/// assert_eq!(json, r#"{"rainy":[2,0],"rainfall":{"2":13}}"#);
+/// assert_eq!(serde_json::from_str::<Forecast>(&json).unwrap(), forecast);
+/// ```
+pub mod by_index {
+    use serde::de::{self, Unexpected};
+    use serde::ser::{Serialize, Serializer};
+    use serde::{Deserialize, Deserializer};
+
+    use super::{DeserializeKeyed, KeyCodec, SerializeKeyed};
+    use crate::Enum;
+
+    struct Index;
+
+    impl KeyCodec for Index {
+        const SEQ: &'static str = "a sequence of variant indices";
+        const MAP: &'static str = "a map keyed by variant indices";
+
+        fn serialize_key<T: Enum, S: Serializer>(
+            value: T,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            value.stable_index().serialize(serializer)
+        }
+
+        fn deserialize_key<'de, T: Enum, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<T, D::Error> {
+            let i = u64::deserialize(deserializer)?;
+            usize::try_from(i)
+                .ok()
+                .and_then(T::from_stable_index)
+                .ok_or_else(|| {
+                    de::Error::invalid_value(Unexpected::Unsigned(i), &"a stable variant index")
+                })
+        }
+    }
+
+    /// Types that can be serialized by [`by_index`](self).
+    pub trait SerializeByIndex {
+        fn serialize_by_index<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+    }
+
+    /// Types that can be deserialized by [`by_index`](self).
+    pub trait DeserializeByIndex<'de>: Sized {
+        fn deserialize_by_index<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+    }
+
+    impl<T: SerializeKeyed> SerializeByIndex for T {
+        #[cfg_attr(feature = "inline-more", inline)]
+        fn serialize_by_index<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.serialize_keyed::<Index, S>(serializer)
+        }
+    }
+
+    impl<'de, T: DeserializeKeyed<'de>> DeserializeByIndex<'de> for T {
+        #[cfg_attr(feature = "inline-more", inline)]
+        fn deserialize_by_index<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            T::deserialize_keyed::<Index, D>(deserializer)
+        }
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: SerializeByIndex,
+        S: Serializer,
+    {
+        value.serialize_by_index(serializer)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: DeserializeByIndex<'de>,
+        D: Deserializer<'de>,
+    {
+        T::deserialize_by_index(deserializer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::enums;
@@ -130,7 +544,7 @@ mod tests {
     }
 
     #[allow(clippy::needless_pass_by_value)]
-    fn assert_roundtrip_eq<T: Eq + std::fmt::Debug + Serialize + DeserializeOwned>(value: T) {
+    fn assert_roundtrip_eq<T: Eq + core::fmt::Debug + Serialize + DeserializeOwned>(value: T) {
         let serialized = serde_json::to_value(&value).unwrap();
         let deserialized: T = serde_json::from_value(serialized).unwrap();
         assert_eq!(value, deserialized);
@@ -149,4 +563,88 @@ mod tests {
         map.insert(DemoEnum::D, "bar".to_owned());
         assert_roundtrip_eq(map);
     }
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct Unnamed;
+
+    impl Enum for Unnamed {
+        type Rep = u8;
+        const SIZE: usize = 1;
+        const MIN: Self = Unnamed;
+        const MAX: Self = Unnamed;
+        const BITMASK: Self::Rep = 1;
+
+        fn succ(self) -> Option<Self> {
+            None
+        }
+
+        fn pred(self) -> Option<Self> {
+            None
+        }
+
+        fn bit(self) -> Self::Rep {
+            1
+        }
+
+        fn index(self) -> usize {
+            0
+        }
+    }
+
+    #[test]
+    fn by_name_unknown_variant() {
+        let json = serde_json::json!(["A", "K"]);
+        let err = by_name::deserialize::<EnumSet<DemoEnum>, _>(json).unwrap_err();
+        assert!(err.to_string().starts_with("unknown variant `K`"));
+    }
+
+    #[test]
+    fn by_name_unnamed() {
+        let set = EnumSet::from([Unnamed]);
+        assert!(by_name::serialize(&set, serde_json::value::Serializer).is_err());
+    }
+
+    #[test]
+    fn lossy_preserves_unknown() {
+        let json = serde_json::json!(["K", "C", "A", "K", "Z"]);
+        let set: LossySet<DemoEnum> = serde_json::from_value(json).unwrap();
+        assert_eq!(set.known(), enums![DemoEnum::A, DemoEnum::C]);
+        assert_eq!(set.unknown(), ["K", "Z"]);
+        assert_eq!(
+            serde_json::to_value(&set).unwrap(),
+            serde_json::json!(["A", "C", "K", "Z"])
+        );
+        assert_roundtrip_eq(set);
+    }
+
+    /// Writes only; has no `Deserialize` impl.
+    #[derive(Serialize)]
+    struct WriteOnly(u32);
+
+    /// Reads only; has no `Serialize` impl.
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct ReadOnly(u32);
+
+    #[test]
+    fn keyed_map_bounds_split() {
+        let map = EnumMap::from([(DemoEnum::C, WriteOnly(3))]);
+        let json = by_name::serialize(&map, serde_json::value::Serializer).unwrap();
+        assert_eq!(json, serde_json::json!({"C": 3}));
+        let json = by_index::serialize(&map, serde_json::value::Serializer).unwrap();
+        assert_eq!(json, serde_json::json!({"2": 3}));
+
+        let map: EnumMap<DemoEnum, ReadOnly> =
+            by_name::deserialize(serde_json::json!({"C": 3})).unwrap();
+        assert_eq!(map, EnumMap::from([(DemoEnum::C, ReadOnly(3))]));
+        let map: EnumMap<DemoEnum, ReadOnly> =
+            by_index::deserialize(serde_json::json!({"2": 3})).unwrap();
+        assert_eq!(map, EnumMap::from([(DemoEnum::C, ReadOnly(3))]));
+    }
+
+    #[test]
+    fn by_index_unknown_index() {
+        let json = serde_json::json!([0, 10]);
+        let err = by_index::deserialize::<EnumSet<DemoEnum>, _>(json).unwrap_err();
+        assert!(err.to_string().starts_with("invalid value: integer `10`"));
+    }
 }
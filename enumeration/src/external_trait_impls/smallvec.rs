@@ -0,0 +1,32 @@
+use smallvec::SmallVec;
+
+use crate::{Enum, EnumSet};
+
+impl<T: Enum> EnumSet<T> {
+    /// Collects the elements of the set into a `SmallVec`, in order. The elements are stored
+    /// inline if there are at most `N` of them, and otherwise in a single heap allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
+    ///
+    /// let values = enums![TextStyle::Italic, TextStyle::Bold].to_smallvec::<2>();
+    /// assert_eq!(values.as_slice(), [TextStyle::Bold, TextStyle::Italic]);
+    /// assert!(!values.spilled());
+    ///
+    /// let values = EnumSet::<TextStyle>::all().to_smallvec::<2>();
+    /// assert_eq!(values.len(), 4);
+    /// assert_eq!(values.capacity(), 4);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "smallvec")))]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn to_smallvec<const N: usize>(&self) -> SmallVec<[T; N]> {
+        let mut values = SmallVec::with_capacity(self.len());
+        values.extend(*self);
+        values
+    }
+}
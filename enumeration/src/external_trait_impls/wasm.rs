@@ -0,0 +1,62 @@
+use js_sys::{Object, Reflect};
+use wasm_bindgen::JsValue;
+
+use crate::{Enum, EnumMap, EnumSet};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+impl<T: Enum> From<EnumSet<T>> for JsValue
+where
+    T::Rep: Into<JsValue>,
+{
+    /// Converts a set into its raw bits. Sets with up to 32 values become JS numbers, and
+    /// larger sets become `BigInt`s.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use enumeration::{Enum, enums};
+    /// use wasm_bindgen::JsValue;
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
+    ///
+    /// let bits = JsValue::from(enums![TextStyle::Bold, TextStyle::Underline]);
+    /// assert_eq!(bits.as_f64(), Some(9.0));
+    /// ```
+    #[inline]
+    fn from(set: EnumSet<T>) -> Self {
+        set.to_raw().into()
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+impl<K: Enum, V: Into<JsValue>> From<EnumMap<K, V>> for JsValue {
+    /// Converts a map into a plain JS object. Each entry becomes a property named after its
+    /// key's variant, or after its key's index if the key type has no
+    /// [names](crate::Enum::NAMES).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    /// use js_sys::Reflect;
+    /// use wasm_bindgen::JsValue;
+    ///
+    /// let map = EnumMap::from([(Ordering::Less, 1), (Ordering::Greater, 3)]);
+    /// let object = JsValue::from(map);
+    /// assert_eq!(Reflect::get(&object, &"2".into()).unwrap().as_f64(), Some(3.0));
+    /// ```
+    fn from(map: EnumMap<K, V>) -> Self {
+        let object = Object::new();
+        for (k, v) in map {
+            let key = match k.name() {
+                Some(name) => JsValue::from_str(name),
+                None => JsValue::from(k.index()),
+            };
+            // Defining a property on a plain, extensible object cannot fail.
+            let _ = Reflect::set(&object, &key, &v.into());
+        }
+        object.into()
+    }
+}
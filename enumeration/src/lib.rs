@@ -1,5 +1,12 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![allow(clippy::manual_map)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+// Derived code refers to `::enumeration`, which must also resolve inside this crate.
+extern crate self as enumeration;
+
 #[cfg(not(test))]
 #[cfg(feature = "enumeration_derive")]
 extern crate enumeration_derive;
@@ -16,14 +23,38 @@ pub use enumeration_derive::Enum;
 
 #[macro_use]
 mod enumerate;
-pub use enumerate::{Enum, Enumeration};
+pub use enumerate::{
+    Checked, Enum, EnumRange, Enumeration, OutOfRange, ParseEnumError, RangeWidth, Width,
+};
 pub mod set;
-pub use set::{EnumSet, __private};
+#[cfg(feature = "std")]
+pub use set::TimedEnumSet;
+pub use set::{
+    __private, DuplicateError, DuplicatePolicy, EnumSet, MaskedSet, ObservedEnumSet,
+    TrackedEnumSet, TriStateEnumMask,
+};
+#[cfg(feature = "alloc")]
+pub use set::{EnumMatrix, LossySet};
 
+#[cfg(feature = "alloc")]
 pub mod map;
-pub use map::{Entry, EnumMap, OccupiedEntry, VacantEntry};
+#[cfg(feature = "alloc")]
+pub use map::{Entry, EnumMap, EnumMap2, OccupiedEntry, SmallEnumMap, VacantEntry};
+
+#[cfg(feature = "alloc")]
+pub mod table;
+#[cfg(feature = "alloc")]
+pub use table::EnumTable;
+
+#[cfg(feature = "alloc")]
+pub mod testing;
 
 mod wordlike;
-pub use wordlike::Wordlike;
+pub use wordlike::{Wordlike, Words};
 
 mod external_trait_impls;
+#[cfg(feature = "serde")]
+pub mod serde {
+    //! Helpers for serde's `with` attribute.
+    pub use crate::external_trait_impls::serde::{by_index, by_name};
+}
@@ -0,0 +1,64 @@
+use alloc::sync::Arc;
+
+use super::enum_map::EnumMap;
+use crate::enumerate::Enum;
+
+/// Copy-on-write operations, for maps whose values are shared between snapshots.
+impl<K: Enum, V> EnumMap<K, Arc<V>> {
+    /// Returns a mutable reference to the value for a key, cloning it first if it is shared
+    /// with another `Arc`, as [`Arc::make_mut`] does. Other snapshots keep the old value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use std::sync::Arc;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut current = EnumMap::from([(Ordering::Less, Arc::new(vec![1, 2]))]);
+    /// let snapshot = current.shallow_clone();
+    ///
+    /// current.make_mut(Ordering::Less).unwrap().push(3);
+    /// assert_eq!(*current[Ordering::Less], [1, 2, 3]);
+    /// assert_eq!(*snapshot[Ordering::Less], [1, 2]);
+    /// assert_eq!(current.make_mut(Ordering::Greater), None);
+    /// ```
+    #[inline]
+    pub fn make_mut(&mut self, k: K) -> Option<&mut V>
+    where
+        V: Clone,
+    {
+        self.get_mut(k).map(Arc::make_mut)
+    }
+
+    /// Clones the map, sharing every value with the original. This is the same as
+    /// [`clone`](Clone::clone), spelled out to distinguish it from
+    /// [`deep_clone`](Self::deep_clone).
+    #[inline]
+    pub fn shallow_clone(&self) -> Self {
+        self.clone()
+    }
+
+    /// Clones the map along with every value, so that no value is shared with the original.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use std::sync::Arc;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(Ordering::Equal, Arc::new(String::from("blob")))]);
+    /// let copy = map.deep_clone();
+    /// assert_eq!(copy, map);
+    /// assert!(!Arc::ptr_eq(&copy[Ordering::Equal], &map[Ordering::Equal]));
+    /// ```
+    pub fn deep_clone(&self) -> Self
+    where
+        V: Clone,
+    {
+        self.iter()
+            .map(|(k, v)| (k, Arc::new(V::clone(v))))
+            .collect()
+    }
+}
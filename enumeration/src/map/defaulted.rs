@@ -0,0 +1,82 @@
+use core::fmt::{self, Debug, Formatter};
+use core::ops::Index;
+
+use super::enum_map::EnumMap;
+use crate::enumerate::Enum;
+
+/// A read-only view of an [`EnumMap`] that substitutes a default value for vacant keys.
+///
+/// This is created by [`EnumMap::with_default`].
+pub struct DefaultedView<'a, K: Enum, V> {
+    map: &'a EnumMap<K, V>,
+    default: &'a V,
+}
+
+impl<'a, K: Enum, V> DefaultedView<'a, K, V> {
+    #[inline]
+    pub(super) const fn new(map: &'a EnumMap<K, V>, default: &'a V) -> Self {
+        Self { map, default }
+    }
+
+    /// Returns the value for a key, or the default if the key is vacant.
+    #[inline]
+    pub fn get(&self, k: K) -> &'a V {
+        self.map.get(k).unwrap_or(self.default)
+    }
+
+    /// Returns the default value.
+    #[inline]
+    pub const fn default_value(&self) -> &'a V {
+        self.default
+    }
+
+    /// Returns the underlying map.
+    #[inline]
+    pub const fn map(&self) -> &'a EnumMap<K, V> {
+        self.map
+    }
+
+    /// An iterator visiting every key in order, along with its value or the default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(Ordering::Equal, 5)]);
+    /// let view = map.with_default(&0);
+    /// assert_eq!(view.iter().map(|(_, &v)| v).collect::<Vec<_>>(), [0, 5, 0]);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (K, &'a V)> + ExactSizeIterator {
+        let view = *self;
+        K::enumerate_all(..).map(move |k| (k, view.get(k)))
+    }
+}
+
+impl<K: Enum, V> Copy for DefaultedView<'_, K, V> {}
+
+#[allow(clippy::expl_impl_clone_on_copy)]
+impl<K: Enum, V> Clone for DefaultedView<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K: Enum, V> Index<K> for DefaultedView<'_, K, V> {
+    type Output = V;
+
+    /// Returns the value for a key, or the default if the key is vacant. This never panics.
+    #[inline]
+    fn index(&self, k: K) -> &V {
+        self.get(k)
+    }
+}
+
+impl<K: Enum + Debug, V: Debug> Debug for DefaultedView<'_, K, V> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -1,3 +1,4 @@
+use super::storage::Storage;
 use crate::enumerate::Enum;
 
 /// A view into a single entry in a map, which may either be vacant or occupied.
@@ -6,7 +7,7 @@ use crate::enumerate::Enum;
 ///
 /// [`EnumMap`]: crate::EnumMap
 /// [`entry`]: crate::EnumMap::entry
-pub enum Entry<'a, K, V> {
+pub enum Entry<'a, K: Enum, V> {
     /// An occupied entry.
     Occupied(OccupiedEntry<'a, K, V>),
     /// A vacant entry.
@@ -89,6 +90,53 @@ impl<'a, K: Enum, V> Entry<'a, K, V> {
         }
     }
 
+    /// Ensures a value is in the entry by inserting the default value if empty, and returns a
+    /// mutable reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut map: EnumMap<Ordering, Option<u32>> = EnumMap::new();
+    /// map.entry(Ordering::Less).or_default();
+    ///
+    /// assert_eq!(map[Ordering::Less], None);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Sets the value of the entry, and returns an `OccupiedEntry`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut map: EnumMap<Ordering, &str> = EnumMap::new();
+    /// let entry = map.entry(Ordering::Greater).insert_entry("hoho");
+    ///
+    /// assert_eq!(entry.key(), Ordering::Greater);
+    /// assert_eq!(map[Ordering::Greater], "hoho");
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn insert_entry(self, value: V) -> OccupiedEntry<'a, K, V> {
+        match self {
+            Self::Occupied(mut entry) => {
+                entry.insert(value);
+                entry
+            }
+            Self::Vacant(entry) => entry.insert_entry(value),
+        }
+    }
+
     /// Returns a reference to this entry's key.
     ///
     /// # Examples
@@ -142,14 +190,47 @@ impl<'a, K: Enum, V> Entry<'a, K, V> {
             Self::Vacant(entry) => Self::Vacant(entry),
         }
     }
+
+    /// Provides shared access to the key and owned access to the value of an occupied entry,
+    /// and allows replacing or removing it based on the value of the returned option.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    /// use enumeration::map::Entry;
+    ///
+    /// let mut map: EnumMap<Ordering, String> = EnumMap::new();
+    /// map.insert(Ordering::Less, "less".to_owned());
+    ///
+    /// let entry = map
+    ///     .entry(Ordering::Less)
+    ///     .replace_with(|_, v| Some(v.to_uppercase()));
+    /// assert!(matches!(entry, Entry::Occupied(_)));
+    /// assert_eq!(map[Ordering::Less], "LESS");
+    ///
+    /// let entry = map.entry(Ordering::Less).replace_with(|_, _| None);
+    /// assert!(matches!(entry, Entry::Vacant(_)));
+    /// assert!(!map.contains_key(Ordering::Less));
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn replace_with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(K, V) -> Option<V>,
+    {
+        match self {
+            Self::Occupied(entry) => entry.replace_with(f),
+            Self::Vacant(entry) => Self::Vacant(entry),
+        }
+    }
 }
 
 /// A view into an occupied entry in a `EnumMap`.
 /// It is part of the [`Entry`] enum.
-pub struct OccupiedEntry<'a, K, V> {
+pub struct OccupiedEntry<'a, K: Enum, V> {
     pub(super) key: K,
-    pub(super) value: &'a mut Option<V>,
-    pub(super) size: &'a mut usize,
+    pub(super) storage: &'a mut Storage<K, V>,
 }
 
 impl<'a, K: Enum, V> OccupiedEntry<'a, K, V> {
@@ -170,6 +251,33 @@ impl<'a, K: Enum, V> OccupiedEntry<'a, K, V> {
         self.key
     }
 
+    /// Returns the slot index of the entry, which is the [`Enum::index`] of its key.
+    ///
+    /// Slots are laid out in key order, so this can be used to correlate entries with external
+    /// arrays of length [`Enum::SIZE`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    /// use enumeration::map::Entry;
+    ///
+    /// let weights = [0.5, 1.0, 2.0];
+    /// let mut map: EnumMap<Ordering, f64> = EnumMap::new();
+    /// map.insert(Ordering::Greater, 3.0);
+    ///
+    /// if let Entry::Occupied(mut o) = map.entry(Ordering::Greater) {
+    ///     assert_eq!(o.index(), 2);
+    ///     *o.get_mut() *= weights[o.index()];
+    /// }
+    /// assert_eq!(map[Ordering::Greater], 6.0);
+    /// ```
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.key.index()
+    }
+
     /// Take the ownership of the key and value from the map.
     ///
     /// # Examples
@@ -212,7 +320,7 @@ impl<'a, K: Enum, V> OccupiedEntry<'a, K, V> {
     /// ```
     #[inline]
     pub fn get(&self) -> &V {
-        self.value.as_ref().unwrap()
+        self.storage.get(self.key).unwrap()
     }
 
     /// Gets a mutable reference to the value in the entry.
@@ -245,7 +353,7 @@ impl<'a, K: Enum, V> OccupiedEntry<'a, K, V> {
     /// ```
     #[inline]
     pub fn get_mut(&mut self) -> &mut V {
-        self.value.as_mut().unwrap()
+        self.storage.get_mut(self.key).unwrap()
     }
 
     /// Converts the `OccupiedEntry` into a mutable reference to the value in the entry
@@ -274,7 +382,7 @@ impl<'a, K: Enum, V> OccupiedEntry<'a, K, V> {
     /// ```
     #[inline]
     pub fn into_mut(self) -> &'a mut V {
-        self.value.as_mut().unwrap()
+        self.storage.get_mut(self.key).unwrap()
     }
 
     /// Sets the value of the entry, and returns the entry's old value.
@@ -297,7 +405,42 @@ impl<'a, K: Enum, V> OccupiedEntry<'a, K, V> {
     /// ```
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn insert(&mut self, value: V) -> V {
-        self.value.replace(value).unwrap()
+        self.storage.insert(self.key, value).1.unwrap()
+    }
+
+    /// Replaces the value of the entry with the result of `f`, or removes the entry if `f`
+    /// returns `None`. Returns the resulting entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    /// use enumeration::map::Entry;
+    ///
+    /// let mut map: EnumMap<Ordering, u32> = EnumMap::new();
+    /// map.insert(Ordering::Less, 1);
+    ///
+    /// if let Entry::Occupied(o) = map.entry(Ordering::Less) {
+    ///     let entry = o.replace_with(|_, v| v.checked_sub(1).filter(|&v| v > 0));
+    ///     assert!(matches!(entry, Entry::Vacant(_)));
+    /// }
+    /// assert!(map.is_empty());
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn replace_with<F>(self, f: F) -> Entry<'a, K, V>
+    where
+        F: FnOnce(K, V) -> Option<V>,
+    {
+        let key = self.key;
+        if self.storage.replace_with(key, f) {
+            Entry::Occupied(self)
+        } else {
+            Entry::Vacant(VacantEntry {
+                key,
+                storage: self.storage,
+            })
+        }
     }
 
     /// Takes the value out of the entry, and returns it.
@@ -320,15 +463,13 @@ impl<'a, K: Enum, V> OccupiedEntry<'a, K, V> {
     /// ```
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn remove(self) -> V {
-        *self.size -= 1;
-        self.value.take().unwrap()
+        self.storage.remove(self.key).unwrap()
     }
 }
 
-pub struct VacantEntry<'a, K, V> {
+pub struct VacantEntry<'a, K: Enum, V> {
     pub(super) key: K,
-    pub(super) value: &'a mut Option<V>,
-    pub(super) size: &'a mut usize,
+    pub(super) storage: &'a mut Storage<K, V>,
 }
 
 impl<'a, K: Enum, V> VacantEntry<'a, K, V> {
@@ -368,8 +509,33 @@ impl<'a, K: Enum, V> VacantEntry<'a, K, V> {
     /// ```
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn insert(self, value: V) -> &'a mut V {
-        *self.size += 1;
-        self.value.replace(value);
-        self.value.as_mut().unwrap()
+        self.storage.insert(self.key, value).0
+    }
+
+    /// Sets the value of the entry with the `VacantEntry`'s key, and returns an
+    /// `OccupiedEntry`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    /// use enumeration::map::Entry;
+    ///
+    /// let mut map: EnumMap<Ordering, u32> = EnumMap::new();
+    ///
+    /// if let Entry::Vacant(v) = map.entry(Ordering::Less) {
+    ///     let o = v.insert_entry(37);
+    ///     assert_eq!(o.get(), &37);
+    /// }
+    /// assert_eq!(map[Ordering::Less], 37);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn insert_entry(self, value: V) -> OccupiedEntry<'a, K, V> {
+        self.storage.insert(self.key, value);
+        OccupiedEntry {
+            key: self.key,
+            storage: self.storage,
+        }
     }
 }
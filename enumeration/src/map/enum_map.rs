@@ -1,12 +1,22 @@
-use std::hash::Hash;
-use std::iter::Iterator;
-use std::marker::PhantomData;
-use std::ops::{Index, IndexMut};
-use std::{slice, vec};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::iter::{FusedIterator, Iterator};
+use core::mem;
+use core::ops::{ControlFlow, Index, IndexMut};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
 
+use super::defaulted::DefaultedView;
 use super::entry::{Entry, OccupiedEntry, VacantEntry};
-use super::iter::{ExtractIf, Iter};
+use super::error::NotAllocated;
+use super::iter::{Chunks, ExtractIf, Iter, Keys, Values};
+use super::storage::{SlotsDrain, SlotsMut, SlotsOwned, SlotsRef, Storage};
+use super::update::Update;
 use crate::enumerate::Enum;
+use crate::set::{self, EnumSet};
 
 /// A lookup map using enumerated types as keys.
 ///
@@ -21,7 +31,17 @@ use crate::enumerate::Enum;
 ///
 /// Violating this property is a logic error.
 ///
-/// The backing store is a `Vec<Option<V>>` of size equal to [`K::SIZE`].
+/// The backing store holds one slot per key, [`K::SIZE`] in total, along with a bitmask of which
+/// slots are occupied. By default each slot is an `Option<V>`. With the `uninit-storage`
+/// feature, slots are left uninitialized while vacant instead, which does not spend a
+/// discriminant on every slot. That matters for values without a niche such as `u64`.
+///
+/// Iteration and [`retain`](Self::retain) scan the occupancy bitmask a word at a time, jumping
+/// over vacant slots and walking each run of occupied ones in a tight loop. Keys are never
+/// looked up by index. Each one is reached by stepping from the previous key with
+/// [`Enum::succ`] (or [`Enum::pred`] from the back), starting at [`Enum::MIN`] (or
+/// [`Enum::MAX`]), so a full walk costs at most `K::SIZE` steps however `Enum` is implemented.
+/// For enums that derive `Enum`, the compiler turns those steps into plain arithmetic.
 ///
 /// [`Enum`]: crate::Enum
 /// [`K::SIZE`]: crate::Enum::SIZE
@@ -117,11 +137,109 @@ use crate::enumerate::Enum;
 /// // modify an entry before an insert with in-place mutation
 /// player_stats.entry(Stat::Mana).and_modify(|mana| *mana += 200).or_insert(100);
 /// ```
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct EnumMap<K, V> {
-    inner: Vec<Option<V>>,
-    size: usize,
-    marker: PhantomData<K>,
+///
+/// Since [`new`](Self::new) is a `const fn`, an empty `EnumMap` can be stored in a `static`.
+/// Tables that need values can be built on first use with [`OnceLock`]:
+///
+/// ```
+/// use std::sync::OnceLock;
+/// use enumeration::{Enum, EnumMap};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Opcode { Nop, Load, Store, Jump }
+///
+/// static UNUSED: EnumMap<Opcode, &str> = EnumMap::new();
+///
+/// fn operand_counts() -> &'static EnumMap<Opcode, u8> {
+///     static TABLE: OnceLock<EnumMap<Opcode, u8>> = OnceLock::new();
+///     TABLE.get_or_init(|| {
+///         EnumMap::from_fn(|op| match op {
+///             Opcode::Nop => 0,
+///             Opcode::Load | Opcode::Store => 2,
+///             Opcode::Jump => 1,
+///         })
+///     })
+/// }
+///
+/// assert!(UNUSED.is_empty());
+/// assert_eq!(operand_counts()[Opcode::Store], 2);
+/// ```
+///
+/// # Drop check
+///
+/// With the `uninit-storage` feature, `EnumMap` drops its values itself, so the compiler assumes
+/// that dropping a map may use the values' borrows. A map of references must then be dropped
+/// before whatever it borrows from, which usually means declaring the borrowed values first:
+///
+/// ```
+/// use enumeration::{Enum, EnumMap};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Side { Left, Right }
+///
+/// let left = String::from("port");
+/// let mut labels = EnumMap::new();
+/// labels.insert(Side::Left, &left);
+/// assert_eq!(labels[Side::Left], "port");
+/// ```
+///
+/// By default, `EnumMap` follows the same rules as `Vec`, and the borrowed values may also be
+/// declared after the map.
+///
+/// [`OnceLock`]: std::sync::OnceLock
+pub struct EnumMap<K: Enum, V> {
+    storage: Storage<K, V>,
+}
+
+impl<K: Enum, V: Clone> Clone for EnumMap<K, V> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        Self {
+            storage: self.storage.clone(),
+        }
+    }
+}
+
+impl<K: Enum, V: PartialEq> PartialEq for EnumMap<K, V> {
+    /// Two maps are equal if they contain the same keys, each with equal values.
+    ///
+    /// Keys are compared first, in O(1) time, so maps with different keys are unequal without
+    /// any value being compared. Whether either map has allocated does not matter.
+    fn eq(&self, other: &Self) -> bool {
+        self.same_keys(other) && self.values().eq(other.values())
+    }
+}
+
+impl<K: Enum, V: Eq> Eq for EnumMap<K, V> {}
+
+impl<K: Enum, V: PartialOrd> PartialOrd for EnumMap<K, V> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<K: Enum, V: Ord> Ord for EnumMap<K, V> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<K: Enum, V: Hash> Hash for EnumMap<K, V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for (k, v) in self {
+            state.write_usize(k.index());
+            v.hash(state);
+        }
+    }
+}
+
+impl<K: Enum + Debug, V: Debug> Debug for EnumMap<K, V> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self).finish()
+    }
 }
 
 impl<K: Enum, V> Default for EnumMap<K, V> {
@@ -147,10 +265,58 @@ impl<K: Enum, V> EnumMap<K, V> {
     #[inline]
     pub const fn new() -> Self {
         Self {
-            inner: Vec::new(),
-            size: 0,
-            marker: PhantomData,
+            storage: Storage::new(),
+        }
+    }
+
+    /// Creates a map with a value for every key, computed by calling `f` on each key in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from_fn(|k: Ordering| k as i8);
+    /// assert_eq!(map.len(), 3);
+    /// assert_eq!(map[Ordering::Less], -1);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn from_fn<F>(mut f: F) -> Self
+    where
+        F: FnMut(K) -> V,
+    {
+        let mut storage = Storage::new();
+        for k in K::enumerate_all(..) {
+            storage.insert(k, f(k));
         }
+        Self { storage }
+    }
+
+    /// Creates a map from key-value pairs, without allocating if there are none.
+    ///
+    /// This is the same as collecting the pairs, and is meant for code paths that build many
+    /// maps, most of them empty. Later values replace earlier values with the same key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let empty: EnumMap<Ordering, u8> = EnumMap::collect_sparse([]);
+    /// assert!(!empty.is_allocated());
+    ///
+    /// let map = EnumMap::collect_sparse([(Ordering::Less, 1), (Ordering::Less, 2)]);
+    /// assert_eq!(map.len(), 1);
+    /// assert_eq!(map[Ordering::Less], 2);
+    /// ```
+    #[inline]
+    pub fn collect_sparse<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        iter.into_iter().collect()
     }
 
     /// Returns the number of elements the map can hold.
@@ -162,6 +328,79 @@ impl<K: Enum, V> EnumMap<K, V> {
         K::SIZE
     }
 
+    /// Returns `true` if the map has allocated its backing store.
+    ///
+    /// A map allocates on its first insertion and keeps the allocation for reuse afterwards,
+    /// so removing or clearing entries never deallocates. Read-only operations behave the same
+    /// whether or not the map is allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut map = EnumMap::new();
+    /// assert!(!map.is_allocated());
+    /// assert_eq!(map.keys().next(), None);
+    ///
+    /// map.insert(Ordering::Less, "a");
+    /// assert!(map.is_allocated());
+    ///
+    /// map.clear();
+    /// assert!(map.is_allocated());
+    /// ```
+    #[inline]
+    pub fn is_allocated(&self) -> bool {
+        self.storage.is_allocated()
+    }
+
+    /// Allocates the backing store if the map has not done so yet.
+    ///
+    /// Afterwards, no method of the map allocates. Code that must not allocate after startup,
+    /// such as an audio callback, can call this up front and then use
+    /// [`try_insert_no_alloc`](Self::try_insert_no_alloc) and
+    /// [`try_entry_no_alloc`](Self::try_entry_no_alloc) to check that it did.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut map: EnumMap<Ordering, f32> = EnumMap::new();
+    /// map.allocate();
+    /// assert!(map.is_allocated());
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    pub fn allocate(&mut self) {
+        self.storage.allocate();
+    }
+
+    /// Returns the approximate number of bytes used by the map, including its heap allocation.
+    /// Memory owned by the values themselves is not included.
+    ///
+    /// For maps over large enums that only ever hold a few entries, consider
+    /// [`SmallEnumMap`](crate::SmallEnumMap), which avoids allocating until it outgrows its
+    /// inline capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut map: EnumMap<Ordering, u64> = EnumMap::new();
+    /// let unallocated = map.memory_usage();
+    /// map.insert(Ordering::Less, 1);
+    /// assert!(map.memory_usage() >= unallocated + 3 * std::mem::size_of::<u64>());
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn memory_usage(&self) -> usize {
+        mem::size_of::<Self>() + self.storage.heap_size()
+    }
+
     /// An iterator visiting all keys.
     /// The iterator element type is `K`.
     ///
@@ -184,15 +423,35 @@ impl<K: Enum, V> EnumMap<K, V> {
     /// # Performance
     ///
     /// In the current implementation, iterating over keys takes O(capacity) time
-    /// instead of O(len) because it internally visits empty buckets too.
+    /// instead of O(len) because it internally visits empty buckets too. Iteration stops as
+    /// soon as the last entry has been visited, so an empty or unallocated map costs O(1).
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn keys(&self) -> impl '_ + Iterator<Item = K> {
-        K::enumerate(..)
-            .zip(&self.inner)
-            .filter_map(|(k, v)| match v {
-                Some(_) => Some(k),
-                None => None,
-            })
+    pub fn keys(&self) -> Keys<K, &V, SlotsRef<'_, V>> {
+        Keys::new(self.iter())
+    }
+
+    /// An iterator visiting all keys that have no value, in order.
+    /// The iterator element type is `K`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(Ordering::Equal, 1)]);
+    ///
+    /// let missing: Vec<_> = map.vacant_keys().collect();
+    /// assert_eq!(missing, [Ordering::Less, Ordering::Greater]);
+    /// ```
+    ///
+    /// # Performance
+    ///
+    /// In the current implementation, iterating takes O(capacity) time
+    /// instead of O(capacity - len) because it internally visits occupied keys too.
+    #[inline]
+    pub fn vacant_keys(&self) -> set::Iter<K> {
+        self.storage.keys().iter_absent()
     }
 
     /// An iterator visiting all values.
@@ -219,8 +478,8 @@ impl<K: Enum, V> EnumMap<K, V> {
     /// In the current implementation, iterating over values takes O(capacity) time
     /// instead of O(len) because it internally visits empty buckets too.
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn values(&self) -> impl Iterator<Item = &V> {
-        self.inner.iter().filter_map(Option::as_ref)
+    pub fn values(&self) -> Values<K, &V, SlotsRef<'_, V>> {
+        Values::new(self.iter())
     }
 
     /// An iterator visiting all values mutably.
@@ -251,8 +510,36 @@ impl<K: Enum, V> EnumMap<K, V> {
     /// In the current implementation, iterating over values takes O(capacity) time
     /// instead of O(len) because it internally visits empty buckets too.
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
-        self.inner.iter_mut().filter_map(Option::as_mut)
+    pub fn values_mut(&mut self) -> Values<K, &mut V, SlotsMut<'_, V>> {
+        Values::new(self.iter_mut())
+    }
+
+    /// Returns all values as a contiguous slice in key order, or `None` unless every key is
+    /// occupied.
+    ///
+    /// Values are stored in place, one slot per key, so a full map can be handed to
+    /// slice-based or SIMD numeric code without copying. This needs the `uninit-storage`
+    /// feature, since the default `Option` slots do not hold bare values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut map = EnumMap::from([(Ordering::Less, 1.5), (Ordering::Greater, 4.0)]);
+    /// assert_eq!(map.as_full_slice(), None);
+    ///
+    /// map.insert(Ordering::Equal, 2.5);
+    /// let values = map.as_full_slice().unwrap();
+    /// assert_eq!(values, [1.5, 2.5, 4.0]);
+    /// assert_eq!(values.iter().sum::<f64>(), 8.0);
+    /// ```
+    #[cfg(feature = "uninit-storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "uninit-storage")))]
+    #[inline]
+    pub fn as_full_slice(&self) -> Option<&[V]> {
+        self.storage.as_full_slice()
     }
 
     /// Creates a consuming iterator visiting all the values.
@@ -282,8 +569,26 @@ impl<K: Enum, V> EnumMap<K, V> {
     /// In the current implementation, iterating over values takes O(capacity) time
     /// instead of O(len) because it internally visits empty buckets too.
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn into_values(self) -> impl Iterator<Item = V> {
-        self.inner.into_iter().flatten()
+    pub fn into_values(self) -> Values<K, V, SlotsOwned<V>> {
+        Values::new(self.into_iter())
+    }
+
+    /// Creates a consuming iterator visiting all keys, in order.
+    /// The map cannot be used after calling this. The iterator element type is `K`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(Ordering::Greater, "b"), (Ordering::Less, "a")]);
+    /// let keys: Vec<_> = map.into_keys().collect();
+    /// assert_eq!(keys, [Ordering::Less, Ordering::Greater]);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn into_keys(self) -> Keys<K, V, SlotsOwned<V>> {
+        Keys::new(self.into_iter())
     }
 
     /// An iterator visiting all key-value pairs.
@@ -303,6 +608,10 @@ impl<K: Enum, V> EnumMap<K, V> {
     /// for (key, val) in map.iter() {
     ///     println!("key: {key:?} val: {val}");
     /// }
+    ///
+    /// // The iterator can be narrowed to keys or values after the fact.
+    /// let iter = map.iter();
+    /// assert_eq!(iter.clone().keys().count(), iter.values().count());
     /// ```
     ///
     /// # Performance
@@ -310,10 +619,33 @@ impl<K: Enum, V> EnumMap<K, V> {
     /// In the current implementation, iterating over map takes O(capacity) time
     /// instead of O(len) because it internally visits empty buckets too.
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn iter(&self) -> Iter<K, &V, slice::Iter<Option<V>>> {
+    pub fn iter(&self) -> Iter<K, &V, SlotsRef<'_, V>> {
         self.into_iter()
     }
 
+    /// An iterator visiting all key-value pairs in order, with copies of the values.
+    /// The iterator element type is `(K, V)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(Ordering::Equal, 1), (Ordering::Less, 5)]);
+    /// let pairs: Vec<_> = map.iter_copied().collect();
+    /// assert_eq!(pairs, [(Ordering::Less, 5), (Ordering::Equal, 1)]);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn iter_copied(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = (K, V)> + ExactSizeIterator + FusedIterator + '_
+    where
+        V: Copy,
+    {
+        self.iter().map(|(k, &v)| (k, v))
+    }
+
     /// An iterator visiting all key-value pairs.
     /// with mutable references to the values.
     /// The iterator element type is `(K, &'a mut V)`.
@@ -344,10 +676,71 @@ impl<K: Enum, V> EnumMap<K, V> {
     /// In the current implementation, iterating over map takes O(capacity) time
     /// instead of O(len) because it internally visits empty buckets too.
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn iter_mut(&mut self) -> Iter<K, &mut V, slice::IterMut<Option<V>>> {
+    pub fn iter_mut(&mut self) -> Iter<K, &mut V, SlotsMut<'_, V>> {
         self.into_iter()
     }
 
+    /// Calls `f` on each key-value pair in order, stopping as soon as `f` returns
+    /// [`ControlFlow::Break`]. Returns the value it broke with, if any.
+    ///
+    /// This visits the occupied slots directly, without the layers of an iterator adaptor
+    /// chain, and never allocates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use std::ops::ControlFlow;
+    /// use enumeration::EnumMap;
+    ///
+    /// let limits = EnumMap::from([(Ordering::Less, 10), (Ordering::Greater, -1)]);
+    /// let invalid = limits.try_for_each(|key, &limit| {
+    ///     if limit < 0 {
+    ///         ControlFlow::Break(key)
+    ///     } else {
+    ///         ControlFlow::Continue(())
+    ///     }
+    /// });
+    /// assert_eq!(invalid, Some(Ordering::Greater));
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_for_each<B, F>(&self, f: F) -> Option<B>
+    where
+        F: FnMut(K, &V) -> ControlFlow<B>,
+    {
+        self.storage.try_for_each(f).break_value()
+    }
+
+    /// Calls `f` on each key and mutable value in order, stopping as soon as `f` returns
+    /// [`ControlFlow::Break`]. Returns the value it broke with, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use std::ops::ControlFlow;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut budgets = EnumMap::from([(Ordering::Less, 5u32), (Ordering::Equal, 0), (Ordering::Greater, 3)]);
+    /// let exhausted = budgets.try_for_each_mut(|key, budget| match budget.checked_sub(1) {
+    ///     Some(rest) => {
+    ///         *budget = rest;
+    ///         ControlFlow::Continue(())
+    ///     }
+    ///     None => ControlFlow::Break(key),
+    /// });
+    /// assert_eq!(exhausted, Some(Ordering::Equal));
+    /// assert_eq!(budgets[Ordering::Less], 4);
+    /// assert_eq!(budgets[Ordering::Greater], 3);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_for_each_mut<B, F>(&mut self, f: F) -> Option<B>
+    where
+        F: FnMut(K, &mut V) -> ControlFlow<B>,
+    {
+        self.storage.try_for_each_mut(f).break_value()
+    }
+
     /// Returns the number of elements in the map.
     ///
     /// # Examples
@@ -363,7 +756,7 @@ impl<K: Enum, V> EnumMap<K, V> {
     /// ```
     #[inline]
     pub const fn len(&self) -> usize {
-        self.size
+        self.storage.len()
     }
 
     /// Returns `true` if the map contains no elements.
@@ -380,7 +773,7 @@ impl<K: Enum, V> EnumMap<K, V> {
     /// assert!(!a.is_empty());
     #[inline]
     pub const fn is_empty(&self) -> bool {
-        self.size == 0
+        self.storage.len() == 0
     }
 
     /// Clears the map, returning all key-value pairs as an iterator. Keeps the
@@ -408,10 +801,128 @@ impl<K: Enum, V> EnumMap<K, V> {
     /// assert!(a.is_empty());
     /// ```
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn drain(&mut self) -> Iter<K, V, vec::Drain<Option<V>>> {
-        let size = self.size;
-        self.size = 0;
-        Iter::new(self.inner.drain(..), size, std::convert::identity)
+    pub fn drain(&mut self) -> Iter<K, V, SlotsDrain<'_, V>> {
+        let size = self.len();
+        Iter::new(self.storage.drain(), size)
+    }
+
+    /// Clears the map, returning its keys in order as an iterator. The values are dropped.
+    ///
+    /// As with [`drain`](Self::drain), the map is empty once the iterator is dropped, even if
+    /// it was not fully consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut map = EnumMap::from([(Ordering::Greater, "b"), (Ordering::Less, "a")]);
+    /// let keys: Vec<_> = map.drain_keys().collect();
+    /// assert_eq!(keys, [Ordering::Less, Ordering::Greater]);
+    /// assert!(map.is_empty());
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn drain_keys(&mut self) -> Keys<K, V, SlotsDrain<'_, V>> {
+        Keys::new(self.drain())
+    }
+
+    /// Removes at most `max_items` key-value pairs in key order, starting at `cursor`, and
+    /// returns them as an iterator.
+    ///
+    /// `cursor` remembers progress between calls, which lets cleanup be spread across frames or
+    /// scheduler ticks. `None` starts at the first key. Afterward, the cursor holds the key
+    /// following the last one visited, so each call only walks the keys it covers. Once a pass
+    /// goes past the last key, the cursor is reset to `None`, and the next call starts a new pass
+    /// that picks up keys inserted behind it.
+    ///
+    /// Pairs are removed as the iterator is advanced; any the iterator does not reach are
+    /// retained.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut map = EnumMap::from([
+    ///     (Ordering::Less, "a"),
+    ///     (Ordering::Equal, "b"),
+    ///     (Ordering::Greater, "c"),
+    /// ]);
+    /// let mut cursor = None;
+    ///
+    /// let first: Vec<_> = map.drain_budgeted(&mut cursor, 2).collect();
+    /// assert_eq!(first, [(Ordering::Less, "a"), (Ordering::Equal, "b")]);
+    /// assert_eq!(cursor, Some(Ordering::Greater));
+    ///
+    /// map.insert(Ordering::Less, "d");
+    /// let second: Vec<_> = map.drain_budgeted(&mut cursor, 2).collect();
+    /// assert_eq!(second, [(Ordering::Greater, "c")]);
+    /// assert_eq!(cursor, None);
+    ///
+    /// let third: Vec<_> = map.drain_budgeted(&mut cursor, 2).collect();
+    /// assert_eq!(third, [(Ordering::Less, "d")]);
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn drain_budgeted<'a>(
+        &'a mut self,
+        cursor: &'a mut Option<K>,
+        max_items: usize,
+    ) -> impl Iterator<Item = (K, V)> + 'a {
+        let mut budget = max_items;
+        core::iter::from_fn(move || {
+            while budget != 0 && !self.is_empty() {
+                let k = cursor.unwrap_or(K::MIN);
+                *cursor = k.succ();
+                let removed = self.remove(k);
+                if cursor.is_none() {
+                    // The pass is over; a new one waits for the next call.
+                    budget = 0;
+                } else if removed.is_some() {
+                    budget -= 1;
+                }
+                if let Some(v) = removed {
+                    return Some((k, v));
+                }
+            }
+            None
+        })
+    }
+
+    /// An iterator over windows of `size` consecutive keys, in order. Each window is paired with
+    /// an iterator over the values at its keys, which yields `None` for vacant keys.
+    ///
+    /// The last window is shorter if `size` does not divide `K::SIZE`. Windows cover vacant keys
+    /// as well as occupied ones, so this is best suited to dense maps over large enums, where
+    /// processing a cache-sized window at a time is cheaper than visiting each key separately.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(Ordering::Less, 1), (Ordering::Greater, 3)]);
+    /// let mut chunks = map.chunks(2);
+    ///
+    /// let (keys, values) = chunks.next().unwrap();
+    /// assert_eq!(keys.collect::<Vec<_>>(), [Ordering::Less, Ordering::Equal]);
+    /// assert_eq!(values.collect::<Vec<_>>(), [Some(&1), None]);
+    ///
+    /// let (keys, values) = chunks.next().unwrap();
+    /// assert_eq!(keys.collect::<Vec<_>>(), [Ordering::Greater]);
+    /// assert_eq!(values.collect::<Vec<_>>(), [Some(&3)]);
+    ///
+    /// assert!(chunks.next().is_none());
+    /// ```
+    #[inline]
+    pub fn chunks(&self, size: usize) -> Chunks<'_, K, V> {
+        Chunks::new(self.storage.slots(), size)
     }
 
     /// Creates an iterator which uses a closure to determine if an element should be removed.
@@ -457,7 +968,7 @@ impl<K: Enum, V> EnumMap<K, V> {
     where
         F: 'a + FnMut(K, &mut V) -> bool,
     {
-        ExtractIf::new(self.inner.iter_mut(), &mut self.size, pred)
+        ExtractIf::new(&mut self.storage, pred)
     }
 
     /// Retains only the elements specified by the predicate.
@@ -483,24 +994,18 @@ impl<K: Enum, V> EnumMap<K, V> {
     ///
     /// In the current implementation, this operation takes O(capacity) time
     /// instead of O(len) because it internally visits empty buckets too.
-    pub fn retain<F>(&mut self, mut f: F)
+    pub fn retain<F>(&mut self, f: F)
     where
         F: FnMut(K, &mut V) -> bool,
     {
-        for (k, m_v) in K::enumerate(..).zip(&mut self.inner) {
-            let erase = match m_v {
-                Some(v) => !f(k, v),
-                None => false,
-            };
-            if erase {
-                self.size -= 1;
-                m_v.take();
-            }
-        }
+        self.storage.retain(f);
     }
 
-    /// Clears the map, removing all key-value pairs. Keeps the allocated memory
-    /// for reuse.
+    /// Splits the map in two by a predicate.
+    ///
+    /// The first map contains every pair for which `f` returns `true`, and the second map
+    /// contains every pair for which it returns `false`. The first map reuses the allocation of
+    /// `self`.
     ///
     /// # Examples
     ///
@@ -508,25 +1013,775 @@ impl<K: Enum, V> EnumMap<K, V> {
     /// use std::cmp::Ordering;
     /// use enumeration::EnumMap;
     ///
-    /// let mut a = EnumMap::new();
-    /// a.insert(Ordering::Less, "a");
+    /// let map: EnumMap::<Ordering, i32> = EnumMap::from([
+    ///     (Ordering::Less, -5),
+    ///     (Ordering::Equal, 1),
+    ///     (Ordering::Greater, 10),
+    /// ]);
+    /// let (positives, negatives) = map.partition(|_key, val| *val >= 0);
+    /// assert_eq!(positives, EnumMap::from([(Ordering::Equal, 1), (Ordering::Greater, 10)]));
+    /// assert_eq!(negatives, EnumMap::from([(Ordering::Less, -5)]));
+    /// ```
+    ///
+    /// # Performance
+    ///
+    /// In the current implementation, this operation takes O(capacity) time
+    /// instead of O(len) because it internally visits empty buckets too.
+    pub fn partition<F>(mut self, mut f: F) -> (Self, Self)
+    where
+        F: FnMut(K, &V) -> bool,
+    {
+        let rest = self.extract_if(|k, v| !f(k, v)).collect();
+        (self, rest)
+    }
+
+    /// Combines two maps into one holding every key of either. Where both maps have a value
+    /// for a key, the values are merged with `f`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let a = EnumMap::from([(Ordering::Less, 1), (Ordering::Equal, 2)]);
+    /// let b = EnumMap::from([(Ordering::Equal, 10), (Ordering::Greater, 20)]);
+    /// let union = a.union_with(b, |_, x, y| x + y);
+    /// assert_eq!(
+    ///     union,
+    ///     EnumMap::from([(Ordering::Less, 1), (Ordering::Equal, 12), (Ordering::Greater, 20)])
+    /// );
+    /// ```
+    pub fn union_with<F>(self, mut other: Self, mut f: F) -> Self
+    where
+        F: FnMut(K, V, V) -> V,
+    {
+        let mut union = Self::new();
+        for (k, a) in self {
+            let v = match other.remove(k) {
+                Some(b) => f(k, a, b),
+                None => a,
+            };
+            union.storage.insert(k, v);
+        }
+        for (k, b) in other {
+            union.storage.insert(k, b);
+        }
+        union
+    }
+
+    /// Combines two maps into one holding only the keys they have in common, merging their
+    /// values with `f`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let prices = EnumMap::from([(Ordering::Less, 3), (Ordering::Equal, 5)]);
+    /// let counts = EnumMap::from([(Ordering::Equal, 2), (Ordering::Greater, 7)]);
+    /// let totals = prices.intersect_with(counts, |_, price, count| price * count);
+    /// assert_eq!(totals, EnumMap::from([(Ordering::Equal, 10)]));
+    /// ```
+    pub fn intersect_with<W, U, F>(self, mut other: EnumMap<K, W>, mut f: F) -> EnumMap<K, U>
+    where
+        F: FnMut(K, V, W) -> U,
+    {
+        self.into_iter()
+            .filter_map(|(k, a)| Some((k, f(k, a, other.remove(k)?))))
+            .collect()
+    }
+
+    /// Removes every key that has a value in `other`, returning the rest of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let a = EnumMap::from([(Ordering::Less, 'a'), (Ordering::Equal, 'b')]);
+    /// let b = EnumMap::from([(Ordering::Equal, ()), (Ordering::Greater, ())]);
+    /// assert_eq!(a.difference(&b), EnumMap::from([(Ordering::Less, 'a')]));
+    /// ```
+    pub fn difference<W>(mut self, other: &EnumMap<K, W>) -> Self {
+        self.retain(|k, _| !other.contains_key(k));
+        self
+    }
+
+    /// Converts the map to a different key type, such as a newer version of the key enum.
+    ///
+    /// If several keys convert to the same new key, the value of the last of them is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumMap, map_enum};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum OldStatus { Active, Suspended }
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Status { Pending, Active, Suspended }
+    ///
+    /// map_enum! { OldStatus => Status { Active, Suspended } }
+    ///
+    /// let counts = EnumMap::from([(OldStatus::Active, 12), (OldStatus::Suspended, 3)]);
+    /// let counts: EnumMap<Status, u32> = counts.migrate_keys();
+    /// assert_eq!(counts[Status::Suspended], 3);
+    /// assert_eq!(counts.get(Status::Pending), None);
+    /// ```
+    pub fn migrate_keys<N>(self) -> EnumMap<N, V>
+    where
+        N: Enum,
+        K: Into<N>,
+    {
+        self.into_iter().map(|(k, v)| (k.into(), v)).collect()
+    }
+
+    /// Returns the key-value pairs of the map sorted with a comparator on values.
+    ///
+    /// The sort is stable, so pairs with equal values remain in key order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([
+    ///     (Ordering::Less, 30),
+    ///     (Ordering::Equal, 10),
+    ///     (Ordering::Greater, 20),
+    /// ]);
+    /// let sorted = map.sorted_by(|a, b| b.cmp(a));
+    /// assert_eq!(
+    ///     sorted,
+    ///     [(Ordering::Less, &30), (Ordering::Greater, &20), (Ordering::Equal, &10)]
+    /// );
+    /// ```
+    pub fn sorted_by<F>(&self, mut cmp: F) -> Vec<(K, &V)>
+    where
+        F: FnMut(&V, &V) -> Ordering,
+    {
+        let mut pairs: Vec<_> = self.iter().collect();
+        pairs.sort_by(|(_, a), (_, b)| cmp(a, b));
+        pairs
+    }
+
+    /// Ranks every key of the map by a sort key derived from its entry, in ascending order.
+    ///
+    /// Ranks start at 0. Keys whose sort keys are equal share a rank, and the next rank skips
+    /// ahead by the number of tied keys, as in a leaderboard.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumMap};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Team { Red, Green, Blue, Gold }
+    ///
+    /// let scores = EnumMap::from([
+    ///     (Team::Red, 12),
+    ///     (Team::Green, 40),
+    ///     (Team::Blue, 12),
+    ///     (Team::Gold, 7),
+    /// ]);
+    /// let ranks = scores.rank_by_key(|_, score| std::cmp::Reverse(*score));
+    /// assert_eq!(ranks[Team::Green], 0);
+    /// assert_eq!(ranks[Team::Red], 1);
+    /// assert_eq!(ranks[Team::Blue], 1);
+    /// assert_eq!(ranks[Team::Gold], 3);
+    /// ```
+    pub fn rank_by_key<R, F>(&self, mut f: F) -> EnumMap<K, usize>
+    where
+        R: Ord,
+        F: FnMut(K, &V) -> R,
+    {
+        let mut keyed: Vec<_> = self.iter().map(|(k, v)| (f(k, v), k)).collect();
+        keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let mut ranks = EnumMap::new();
+        let mut rank = 0;
+        for (i, (sort_key, k)) in keyed.iter().enumerate() {
+            if i != 0 && keyed[i - 1].0 != *sort_key {
+                rank = i;
+            }
+            ranks.insert(*k, rank);
+        }
+        ranks
+    }
+
+    /// Returns the distinct values of the map, in the order of the first key holding each.
+    ///
+    /// Maps have at most [`Enum::SIZE`] entries, so this compares values pairwise rather than
+    /// requiring `V: Hash`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([
+    ///     (Ordering::Less, "off"),
+    ///     (Ordering::Equal, "on"),
+    ///     (Ordering::Greater, "off"),
+    /// ]);
+    /// assert_eq!(map.unique_values(), [&"off", &"on"]);
+    /// ```
+    pub fn unique_values(&self) -> Vec<&V>
+    where
+        V: PartialEq,
+    {
+        let mut unique: Vec<&V> = Vec::new();
+        for v in self.values() {
+            if !unique.contains(&v) {
+                unique.push(v);
+            }
+        }
+        unique
+    }
+
+    /// Counts how many keys hold each distinct value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([
+    ///     (Ordering::Less, "off"),
+    ///     (Ordering::Equal, "on"),
+    ///     (Ordering::Greater, "off"),
+    /// ]);
+    /// let counts = map.value_counts();
+    /// assert_eq!(counts[&"off"], 2);
+    /// assert_eq!(counts[&"on"], 1);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn value_counts(&self) -> HashMap<&V, usize>
+    where
+        V: Eq + Hash,
+    {
+        let mut counts = HashMap::with_capacity(self.len());
+        for v in self.values() {
+            *counts.entry(v).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Copies the entries of the map into a `Vec`, in ascending order of key index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(Ordering::Greater, 'c'), (Ordering::Less, 'a')]);
+    /// assert_eq!(map.to_vec(), [(Ordering::Less, 'a'), (Ordering::Greater, 'c')]);
+    /// ```
+    pub fn to_vec(&self) -> Vec<(K, V)>
+    where
+        V: Clone,
+    {
+        self.iter().map(|(k, v)| (k, v.clone())).collect()
+    }
+
+    /// Copies the entries of the map into a [`BTreeMap`]. Since [`Enum`] orders keys by index,
+    /// the `BTreeMap` iterates in the same order as the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(Ordering::Greater, 'c'), (Ordering::Less, 'a')]);
+    /// let btree = map.to_btree_map();
+    /// assert!(btree.into_iter().eq(map));
+    /// ```
+    pub fn to_btree_map(&self) -> BTreeMap<K, V>
+    where
+        V: Clone,
+    {
+        self.iter().map(|(k, v)| (k, v.clone())).collect()
+    }
+
+    /// Copies the entries of the map into a [`HashMap`], which has no defined order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(Ordering::Greater, 'c'), (Ordering::Less, 'a')]);
+    /// let hash_map = map.to_hash_map();
+    /// assert_eq!(hash_map.len(), 2);
+    /// assert_eq!(hash_map[&Ordering::Less], 'a');
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn to_hash_map(&self) -> HashMap<K, V>
+    where
+        K: Hash,
+        V: Clone,
+    {
+        self.iter().map(|(k, v)| (k, v.clone())).collect()
+    }
+
+    /// Clears the map, removing all key-value pairs. Keeps the allocated memory
+    /// for reuse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut a = EnumMap::new();
+    /// a.insert(Ordering::Less, "a");
     /// a.clear();
     /// assert!(a.is_empty());
     /// ```
-    #[cfg_attr(feature = "inline-more", inline)]
-    pub fn clear(&mut self) {
-        self.size = 0;
-        self.inner.fill_with(Default::default);
-    }
-
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn clear(&mut self) {
+        self.storage.clear();
+    }
+
+    /// Gets the given key's corresponding entry in the map for in-place manipulation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut ords = EnumMap::new();
+    ///
+    /// for ord in [Ordering::Less, Ordering::Equal, Ordering::Less, Ordering::Less] {
+    ///     ords.entry(ord).and_modify(|counter| *counter += 1).or_insert(1);
+    /// }
+    ///
+    /// assert_eq!(ords[Ordering::Less], 3);
+    /// assert_eq!(ords[Ordering::Equal], 1);
+    /// assert_eq!(ords.get(Ordering::Greater), None);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        self.storage.allocate();
+        let storage = &mut self.storage;
+        if storage.contains(key) {
+            Entry::Occupied(OccupiedEntry { key, storage })
+        } else {
+            Entry::Vacant(VacantEntry { key, storage })
+        }
+    }
+
+    /// Gets the given key's corresponding entry in the map without allocating.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotAllocated`] if the map has not allocated its backing store. See
+    /// [`allocate`](Self::allocate).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut counts: EnumMap<Ordering, u32> = EnumMap::new();
+    /// assert!(counts.try_entry_no_alloc(Ordering::Less).is_err());
+    ///
+    /// counts.allocate();
+    /// if let Ok(entry) = counts.try_entry_no_alloc(Ordering::Less) {
+    ///     *entry.or_insert(0) += 1;
+    /// }
+    /// assert_eq!(counts[Ordering::Less], 1);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_entry_no_alloc(&mut self, key: K) -> Result<Entry<'_, K, V>, NotAllocated> {
+        if !self.storage.is_allocated() {
+            return Err(NotAllocated);
+        }
+        Ok(self.entry(key))
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but
+    /// [`Hash`] and [`Eq`] on the borrowed form *must* match those for
+    /// the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut map = EnumMap::new();
+    /// map.insert(Ordering::Less, "a");
+    /// assert_eq!(map.get(Ordering::Less), Some(&"a"));
+    /// assert_eq!(map.get(Ordering::Equal), None);
+    /// ```
+    #[inline]
+    pub fn get(&self, k: K) -> Option<&V> {
+        self.storage.get(k)
+    }
+
+    /// Returns a read-only view of the map in which vacant keys hold `default`. Its `get`
+    /// always returns a value, and indexing it never panics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(Ordering::Less, 3)]);
+    /// let view = map.with_default(&0);
+    /// assert_eq!(view[Ordering::Less], 3);
+    /// assert_eq!(view[Ordering::Greater], 0);
+    /// assert_eq!(*view.get(Ordering::Equal), 0);
+    /// ```
+    #[inline]
+    pub fn with_default<'a>(&'a self, default: &'a V) -> DefaultedView<'a, K, V> {
+        DefaultedView::new(self, default)
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(Ordering::Less, "a")]);
+    /// assert_eq!(map.get_key_value(Ordering::Less), Some((Ordering::Less, &"a")));
+    /// assert_eq!(map.get_key_value(Ordering::Equal), None);
+    /// ```
+    #[inline]
+    pub fn get_key_value(&self, k: K) -> Option<(K, &V)> {
+        self.storage.get(k).map(|v| (k, v))
+    }
+
+    /// Returns the key at position `i` of [`K::enumerate(..)`](Enum::enumerate) and its value.
+    ///
+    /// Positions count every key of `K`, occupied or not, so they are stable as entries are
+    /// inserted and removed. Returns `None` if `i` is out of range or the key is vacant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(Ordering::Equal, "b")]);
+    /// assert_eq!(map.get_index(0), None);
+    /// assert_eq!(map.get_index(1), Some((Ordering::Equal, &"b")));
+    /// assert_eq!(map.get_index(3), None);
+    /// ```
+    #[inline]
+    pub fn get_index(&self, i: usize) -> Option<(K, &V)> {
+        self.get_key_value(K::from_index(i)?)
+    }
+
+    /// Returns the key at position `i` of [`K::enumerate(..)`](Enum::enumerate) and a mutable
+    /// reference to its value.
+    ///
+    /// Returns `None` if `i` is out of range or the key is vacant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut map = EnumMap::from([(Ordering::Greater, 1)]);
+    /// if let Some((_, v)) = map.get_index_mut(2) {
+    ///     *v += 10;
+    /// }
+    /// assert_eq!(map[Ordering::Greater], 11);
+    /// ```
+    #[inline]
+    pub fn get_index_mut(&mut self, i: usize) -> Option<(K, &mut V)> {
+        let k = K::from_index(i)?;
+        self.storage.get_mut(k).map(|v| (k, v))
+    }
+
+    /// Returns a reference to the value corresponding to a borrowed key.
+    ///
+    /// This is equivalent to [`get`](Self::get), for generic code that only has a `&K`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(Ordering::Less, "a")]);
+    /// let key = &Ordering::Less;
+    /// assert_eq!(map.get_ref(key), Some(&"a"));
+    /// assert_eq!(map[key], "a");
+    /// ```
+    #[inline]
+    pub fn get_ref(&self, k: &K) -> Option<&V> {
+        self.get(*k)
+    }
+
+    /// Returns a mutable reference to the value corresponding to a borrowed key.
+    ///
+    /// This is equivalent to [`get_mut`](Self::get_mut), for generic code that only has a `&K`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut map = EnumMap::from([(Ordering::Less, 1)]);
+    /// if let Some(v) = map.get_mut_ref(&Ordering::Less) {
+    ///     *v += 1;
+    /// }
+    /// map[&Ordering::Less] *= 10;
+    /// assert_eq!(map[Ordering::Less], 20);
+    /// ```
+    #[inline]
+    pub fn get_mut_ref(&mut self, k: &K) -> Option<&mut V> {
+        self.get_mut(*k)
+    }
+
+    /// Returns `true` if the map contains a value for a borrowed key.
+    ///
+    /// This is equivalent to [`contains_key`](Self::contains_key), for generic code that only
+    /// has a `&K`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(Ordering::Less, "a")]);
+    /// assert!(map.contains_key_ref(&Ordering::Less));
+    /// assert!(!map.contains_key_ref(&Ordering::Equal));
+    /// ```
+    #[inline]
+    pub fn contains_key_ref(&self, k: &K) -> bool {
+        self.contains_key(*k)
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but
+    /// [`Hash`] and [`Eq`] on the borrowed form *must* match those for
+    /// the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut map = EnumMap::new();
+    /// map.insert(Ordering::Less, "a");
+    /// assert_eq!(map.contains_key(Ordering::Less), true);
+    /// assert_eq!(map.contains_key(Ordering::Equal), false);
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains_key(&self, k: K) -> bool {
+        self.storage.contains(k)
+    }
+
+    /// Returns `true` if both maps contain values for exactly the same keys, regardless of
+    /// what those values are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let a = EnumMap::from([(Ordering::Less, 1), (Ordering::Greater, 2)]);
+    /// let b = EnumMap::from([(Ordering::Greater, "x"), (Ordering::Less, "y")]);
+    /// let c = EnumMap::from([(Ordering::Less, "y")]);
+    /// assert!(a.same_keys(&b));
+    /// assert!(!a.same_keys(&c));
+    /// ```
+    ///
+    /// # Performance
+    ///
+    /// This operation takes O(1) time.
+    #[inline]
+    pub fn same_keys<W>(&self, other: &EnumMap<K, W>) -> bool {
+        self.len() == other.len() && self.storage.keys() == other.storage.keys()
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but
+    /// [`Hash`] and [`Eq`] on the borrowed form *must* match those for
+    /// the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut map = EnumMap::new();
+    /// map.insert(Ordering::Less, "a");
+    /// if let Some(x) = map.get_mut(Ordering::Less) {
+    ///     *x = "b";
+    /// }
+    /// assert_eq!(map[Ordering::Less], "b");
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self, k: K) -> Option<&mut V> {
+        self.storage.get_mut(k)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key, first inserting a
+    /// clone of `template` if the key is vacant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let defaults = vec!["verbose"];
+    /// let mut overrides = EnumMap::new();
+    /// overrides.insert(Ordering::Less, vec!["quiet"]);
+    ///
+    /// overrides.get_mut_or_insert_from(Ordering::Less, &defaults).push("color");
+    /// overrides.get_mut_or_insert_from(Ordering::Greater, &defaults).push("color");
+    /// assert_eq!(overrides[Ordering::Less], ["quiet", "color"]);
+    /// assert_eq!(overrides[Ordering::Greater], ["verbose", "color"]);
+    /// assert_eq!(overrides.get(Ordering::Equal), None);
+    /// ```
+    #[inline]
+    pub fn get_mut_or_insert_from(&mut self, k: K, template: &V) -> &mut V
+    where
+        V: Clone,
+    {
+        self.entry(k).or_insert_with(|| template.clone())
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have this key present, [`None`] is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old
+    /// value is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut map = EnumMap::new();
+    /// assert_eq!(map.insert(Ordering::Less, "a"), None);
+    /// assert_eq!(map.is_empty(), false);
+    ///
+    /// map.insert(Ordering::Less, "b");
+    /// assert_eq!(map.insert(Ordering::Less, "c"), Some("b"));
+    /// assert_eq!(map[Ordering::Less], "c");
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
+        self.storage.insert(k, v).1
+    }
+
+    /// Inserts a key-value pair into the map without allocating, returning the replaced value,
+    /// if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotAllocated`] and drops `v` if the map has not allocated its backing store.
+    /// See [`allocate`](Self::allocate).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    /// use enumeration::map::NotAllocated;
+    ///
+    /// let mut map = EnumMap::new();
+    /// assert_eq!(map.try_insert_no_alloc(Ordering::Less, "a"), Err(NotAllocated));
+    ///
+    /// map.allocate();
+    /// assert_eq!(map.try_insert_no_alloc(Ordering::Less, "a"), Ok(None));
+    /// assert_eq!(map.try_insert_no_alloc(Ordering::Less, "b"), Ok(Some("a")));
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_insert_no_alloc(&mut self, k: K, v: V) -> Result<Option<V>, NotAllocated> {
+        if !self.storage.is_allocated() {
+            return Err(NotAllocated);
+        }
+        Ok(self.storage.insert(k, v).1)
+    }
+
+    /// Inserts the value computed by `f` from the key's previous value, if any, and returns a
+    /// mutable reference to the new value.
+    ///
+    /// This covers "update or initialize" in a single slot access.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut map: EnumMap<Ordering, Vec<&str>> = EnumMap::new();
+    /// map.insert_with(Ordering::Less, |old| old.unwrap_or_default());
+    /// map.insert_with(Ordering::Less, |old| {
+    ///     let mut list = old.unwrap_or_default();
+    ///     list.push("a");
+    ///     list
+    /// });
+    /// assert_eq!(map[Ordering::Less], ["a"]);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn insert_with<F>(&mut self, k: K, f: F) -> &mut V
+    where
+        F: FnOnce(Option<V>) -> V,
+    {
+        let new_val = f(self.storage.remove(k));
+        self.storage.insert(k, new_val).0
+    }
+
+    /// Inserts a key-value pair into the map, returning the replaced value, if any.
+    ///
+    /// This is equivalent to [`insert`](Self::insert), and mirrors [`HashSet::replace`].
+    ///
+    /// [`HashSet::replace`]: std::collections::HashSet::replace
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut map = EnumMap::new();
+    /// assert_eq!(map.replace(Ordering::Less, "a"), None);
+    /// assert_eq!(map.replace(Ordering::Less, "b"), Some("a"));
+    /// assert_eq!(map[Ordering::Less], "b");
+    /// ```
     #[inline]
-    fn allocate(&mut self) {
-        if self.inner.is_empty() {
-            self.inner.resize_with(K::SIZE, Default::default);
-        }
+    pub fn replace(&mut self, k: K, v: V) -> Option<V> {
+        self.insert(k, v)
     }
 
-    /// Gets the given key's corresponding entry in the map for in-place manipulation.
+    /// Inserts a key-value pair into the map, dropping the replaced value, if any.
     ///
     /// # Examples
     ///
@@ -534,40 +1789,21 @@ impl<K: Enum, V> EnumMap<K, V> {
     /// use std::cmp::Ordering;
     /// use enumeration::EnumMap;
     ///
-    /// let mut ords = EnumMap::new();
-    ///
-    /// for ord in [Ordering::Less, Ordering::Equal, Ordering::Less, Ordering::Less] {
-    ///     ords.entry(ord).and_modify(|counter| *counter += 1).or_insert(1);
-    /// }
-    ///
-    /// assert_eq!(ords[Ordering::Less], 3);
-    /// assert_eq!(ords[Ordering::Equal], 1);
-    /// assert_eq!(ords.get(Ordering::Greater), None);
+    /// let mut map = EnumMap::new();
+    /// map.put(Ordering::Less, "a");
+    /// map.put(Ordering::Less, "b");
+    /// assert_eq!(map[Ordering::Less], "b");
     /// ```
-    #[cfg_attr(feature = "inline-more", inline)]
-    pub fn entry(&mut self, key: K) -> Entry<K, V> {
-        self.allocate();
-        let entry = &mut self.inner[key.index()];
-        if entry.is_some() {
-            Entry::Occupied(OccupiedEntry {
-                key,
-                value: entry,
-                size: &mut self.size,
-            })
-        } else {
-            Entry::Vacant(VacantEntry {
-                key,
-                value: entry,
-                size: &mut self.size,
-            })
-        }
+    #[inline]
+    pub fn put(&mut self, k: K, v: V) {
+        self.storage.insert(k, v);
     }
 
-    /// Returns a reference to the value corresponding to the key.
+    /// Swaps `value` with the value at a key, if the key is present, and returns `true`.
+    /// Otherwise, leaves both the map and `value` unchanged and returns `false`.
     ///
-    /// The key may be any borrowed form of the map's key type, but
-    /// [`Hash`] and [`Eq`] on the borrowed form *must* match those for
-    /// the key type.
+    /// Unlike [`insert`](Self::insert), this never moves a value into or out of the map, which
+    /// lets buffers be reused without reallocating.
     ///
     /// # Examples
     ///
@@ -575,21 +1811,28 @@ impl<K: Enum, V> EnumMap<K, V> {
     /// use std::cmp::Ordering;
     /// use enumeration::EnumMap;
     ///
-    /// let mut map = EnumMap::new();
-    /// map.insert(Ordering::Less, "a");
-    /// assert_eq!(map.get(Ordering::Less), Some(&"a"));
-    /// assert_eq!(map.get(Ordering::Equal), None);
+    /// let mut map = EnumMap::from([(Ordering::Less, vec![1, 2])]);
+    /// let mut buf = Vec::with_capacity(16);
+    /// assert!(map.swap_value(Ordering::Less, &mut buf));
+    /// assert_eq!(buf, [1, 2]);
+    /// assert!(map[Ordering::Less].is_empty());
+    ///
+    /// assert!(!map.swap_value(Ordering::Equal, &mut buf));
+    /// assert_eq!(buf, [1, 2]);
     /// ```
     #[inline]
-    pub fn get(&self, k: K) -> Option<&V> {
-        self.inner.get(k.index()).and_then(Option::as_ref)
+    pub fn swap_value(&mut self, k: K, value: &mut V) -> bool {
+        match self.storage.get_mut(k) {
+            Some(slot) => {
+                mem::swap(slot, value);
+                true
+            }
+            None => false,
+        }
     }
 
-    /// Returns `true` if the map contains a value for the specified key.
-    ///
-    /// The key may be any borrowed form of the map's key type, but
-    /// [`Hash`] and [`Eq`] on the borrowed form *must* match those for
-    /// the key type.
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
     ///
     /// # Examples
     ///
@@ -599,18 +1842,17 @@ impl<K: Enum, V> EnumMap<K, V> {
     ///
     /// let mut map = EnumMap::new();
     /// map.insert(Ordering::Less, "a");
-    /// assert_eq!(map.contains_key(Ordering::Less), true);
-    /// assert_eq!(map.contains_key(Ordering::Equal), false);
+    /// assert_eq!(map.remove(Ordering::Less), Some("a"));
+    /// assert_eq!(map.remove(Ordering::Less), None);
+    /// ```
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn contains_key(&self, k: K) -> bool {
-        matches!(self.inner.get(k.index()), Some(Some(_)))
+    pub fn remove(&mut self, k: K) -> Option<V> {
+        self.storage.remove(k)
     }
 
-    /// Returns a mutable reference to the value corresponding to the key.
+    /// Removes a key from the map, returning its value if it was present.
     ///
-    /// The key may be any borrowed form of the map's key type, but
-    /// [`Hash`] and [`Eq`] on the borrowed form *must* match those for
-    /// the key type.
+    /// This is equivalent to [`remove`](Self::remove), and mirrors [`Option::take`].
     ///
     /// # Examples
     ///
@@ -618,70 +1860,125 @@ impl<K: Enum, V> EnumMap<K, V> {
     /// use std::cmp::Ordering;
     /// use enumeration::EnumMap;
     ///
-    /// let mut map = EnumMap::new();
-    /// map.insert(Ordering::Less, "a");
-    /// if let Some(x) = map.get_mut(Ordering::Less) {
-    ///     *x = "b";
-    /// }
-    /// assert_eq!(map[Ordering::Less], "b");
+    /// let mut map = EnumMap::from([(Ordering::Greater, 'x')]);
+    /// assert_eq!(map.take(Ordering::Greater), Some('x'));
+    /// assert_eq!(map.take(Ordering::Greater), None);
     /// ```
     #[inline]
-    pub fn get_mut(&mut self, k: K) -> Option<&mut V> {
-        self.inner.get_mut(k.index()).and_then(Option::as_mut)
+    pub fn take(&mut self, k: K) -> Option<V> {
+        self.remove(k)
     }
 
-    /// Inserts a key-value pair into the map.
+    /// Applies a batch of updates in order.
     ///
-    /// If the map did not have this key present, [`None`] is returned.
-    ///
-    /// If the map did have this key present, the value is updated, and the old
-    /// value is returned.
+    /// Later updates to the same key see the effect of earlier ones. The map allocates at most
+    /// once, on the first value it stores.
     ///
     /// # Examples
     ///
     /// ```
     /// use std::cmp::Ordering;
     /// use enumeration::EnumMap;
+    /// use enumeration::map::Update;
+    ///
+    /// let mut map = EnumMap::from([(Ordering::Less, 1)]);
+    /// map.apply([
+    ///     (Ordering::Less, Update::Remove),
+    ///     (Ordering::Equal, Update::Set(2)),
+    ///     (Ordering::Equal, Update::Modify(|v| *v *= 3)),
+    ///     (Ordering::Greater, Update::Modify(|v| *v *= 3)),
+    /// ]);
+    /// assert_eq!(map.len(), 1);
+    /// assert_eq!(map[Ordering::Equal], 6);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn apply<I>(&mut self, changes: I)
+    where
+        I: IntoIterator<Item = (K, Update<V>)>,
+    {
+        for (k, change) in changes {
+            match change {
+                Update::Set(v) => {
+                    self.storage.insert(k, v);
+                }
+                Update::Remove => {
+                    self.storage.remove(k);
+                }
+                Update::Modify(f) => {
+                    if let Some(v) = self.storage.get_mut(k) {
+                        f(v);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<V> EnumMap<bool, V> {
+    /// Creates a map with a value for both `false` and `true`.
     ///
-    /// let mut map = EnumMap::new();
-    /// assert_eq!(map.insert(Ordering::Less, "a"), None);
-    /// assert_eq!(map.is_empty(), false);
+    /// # Examples
     ///
-    /// map.insert(Ordering::Less, "b");
-    /// assert_eq!(map.insert(Ordering::Less, "c"), Some("b"));
-    /// assert_eq!(map[Ordering::Less], "c");
+    /// ```
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from_pair("off", "on");
+    /// assert_eq!(map[false], "off");
+    /// assert_eq!(map[true], "on");
     /// ```
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
-        self.allocate();
-        let old_val = self.inner[k.index()].replace(v);
-        if old_val.is_none() {
-            self.size += 1;
-        }
-        old_val
+    pub fn from_pair(false_val: V, true_val: V) -> Self {
+        let mut map = Self::new();
+        map.insert(false, false_val);
+        map.insert(true, true_val);
+        map
     }
 
-    /// Removes a key from the map, returning the value at the key if the key
-    /// was previously in the map.
+    /// Returns a reference to the value corresponding to `false`.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::cmp::Ordering;
     /// use enumeration::EnumMap;
     ///
-    /// let mut map = EnumMap::new();
-    /// map.insert(Ordering::Less, "a");
-    /// assert_eq!(map.remove(Ordering::Less), Some("a"));
-    /// assert_eq!(map.remove(Ordering::Less), None);
+    /// let map = EnumMap::from([(false, "off")]);
+    /// assert_eq!(map.get_false(), Some(&"off"));
+    /// assert_eq!(map.get_true(), None);
+    /// ```
+    #[inline]
+    pub fn get_false(&self) -> Option<&V> {
+        self.get(false)
+    }
+
+    /// Returns a reference to the value corresponding to `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(true, "on")]);
+    /// assert_eq!(map.get_true(), Some(&"on"));
+    /// assert_eq!(map.get_false(), None);
+    /// ```
+    #[inline]
+    pub fn get_true(&self) -> Option<&V> {
+        self.get(true)
+    }
+
+    /// Converts the map into the values for `false` and `true`, in that order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(true, 1)]);
+    /// assert_eq!(map.into_pair(), (None, Some(1)));
     /// ```
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn remove(&mut self, k: K) -> Option<V> {
-        let old_val = self.inner.get_mut(k.index())?.take();
-        if old_val.is_some() {
-            self.size -= 1;
-        }
-        old_val
+    pub fn into_pair(mut self) -> (Option<V>, Option<V>) {
+        (self.remove(false), self.remove(true))
     }
 }
 
@@ -711,50 +2008,73 @@ impl<K: Enum, V> IndexMut<K> for EnumMap<K, V> {
     }
 }
 
+impl<K: Enum, V> Index<&K> for EnumMap<K, V> {
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the `EnumMap`.
+    #[inline]
+    fn index(&self, key: &K) -> &Self::Output {
+        &self[*key]
+    }
+}
+
+impl<K: Enum, V> IndexMut<&K> for EnumMap<K, V> {
+    /// Returns a mutable reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the `EnumMap`.
+    #[inline]
+    fn index_mut(&mut self, key: &K) -> &mut Self::Output {
+        &mut self[*key]
+    }
+}
+
 impl<K: Enum, V> IntoIterator for EnumMap<K, V> {
     type Item = (K, V);
-    type IntoIter = Iter<K, V, vec::IntoIter<Option<V>>>;
+    type IntoIter = Iter<K, V, SlotsOwned<V>>;
 
     #[cfg_attr(feature = "inline-more", inline)]
     fn into_iter(self) -> Self::IntoIter {
-        Iter::new(self.inner, self.size, std::convert::identity)
+        let size = self.len();
+        Iter::new(self.storage.into_slots(), size)
     }
 }
 
 impl<'a, K: Enum, V> IntoIterator for &'a EnumMap<K, V> {
     type Item = (K, &'a V);
-    type IntoIter = Iter<K, &'a V, slice::Iter<'a, Option<V>>>;
+    type IntoIter = Iter<K, &'a V, SlotsRef<'a, V>>;
 
     #[cfg_attr(feature = "inline-more", inline)]
     fn into_iter(self) -> Self::IntoIter {
-        Iter::new(&self.inner, self.size, Option::as_ref)
+        Iter::new(self.storage.slots(), self.len())
     }
 }
 
 impl<'a, K: Enum, V> IntoIterator for &'a mut EnumMap<K, V> {
     type Item = (K, &'a mut V);
-    type IntoIter = Iter<K, &'a mut V, slice::IterMut<'a, Option<V>>>;
+    type IntoIter = Iter<K, &'a mut V, SlotsMut<'a, V>>;
 
     #[cfg_attr(feature = "inline-more", inline)]
     fn into_iter(self) -> Self::IntoIter {
-        Iter::new(&mut self.inner, self.size, Option::as_mut)
+        let size = self.len();
+        Iter::new(self.storage.slots_mut(), size)
     }
 }
 
 impl<K: Enum, V> FromIterator<(K, V)> for EnumMap<K, V> {
+    /// Collects key-value pairs into a map. Later values replace earlier values with the same
+    /// key. The map only allocates once the first pair is inserted.
     fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
-        let mut inner: Vec<Option<V>> = Vec::with_capacity(K::SIZE);
-        inner.resize_with(K::SIZE, Default::default);
-        let mut size = 0;
+        let mut storage = Storage::new();
         for (key, val) in iter {
-            size += 1;
-            inner[key.index()] = Some(val);
-        }
-        Self {
-            inner,
-            size,
-            marker: PhantomData,
+            storage.insert(key, val);
         }
+        Self { storage }
     }
 }
 
@@ -763,3 +2083,505 @@ impl<K: Enum, V, const N: usize> From<[(K, V); N]> for EnumMap<K, V> {
         Self::from_iter(value)
     }
 }
+
+impl<V> From<(Option<V>, Option<V>)> for EnumMap<bool, V> {
+    fn from((false_val, true_val): (Option<V>, Option<V>)) -> Self {
+        let mut map = Self::new();
+        if let Some(val) = false_val {
+            map.insert(false, val);
+        }
+        if let Some(val) = true_val {
+            map.insert(true, val);
+        }
+        map
+    }
+}
+
+impl<V> From<EnumMap<bool, V>> for (Option<V>, Option<V>) {
+    #[inline]
+    fn from(value: EnumMap<bool, V>) -> Self {
+        value.into_pair()
+    }
+}
+
+/// Returns the set of occupied keys.
+///
+/// # Examples
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use enumeration::{EnumMap, EnumSet, enums};
+///
+/// let map = EnumMap::from([(Ordering::Less, "a"), (Ordering::Greater, "c")]);
+/// assert_eq!(EnumSet::from(&map), enums![Ordering::Less, Ordering::Greater]);
+/// ```
+impl<K: Enum, V> From<&EnumMap<K, V>> for EnumSet<K> {
+    #[inline]
+    fn from(value: &EnumMap<K, V>) -> Self {
+        value.storage.keys()
+    }
+}
+
+/// Creates a map with a `()` value for each element of the set.
+///
+/// # Examples
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use enumeration::{EnumMap, enums};
+///
+/// let map = EnumMap::from(enums![Ordering::Less, Ordering::Equal]);
+/// assert_eq!(map, EnumMap::from([(Ordering::Less, ()), (Ordering::Equal, ())]));
+/// ```
+impl<K: Enum> From<EnumSet<K>> for EnumMap<K, ()> {
+    fn from(value: EnumSet<K>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+/// Returns the set of keys of a map with no values. This is the inverse of
+/// `From<EnumSet<K>> for EnumMap<K, ()>`.
+///
+/// # Examples
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use enumeration::{EnumMap, EnumSet, enums};
+///
+/// let set = enums![Ordering::Less, Ordering::Greater];
+/// assert_eq!(EnumSet::from(EnumMap::from(set)), set);
+/// ```
+impl<K: Enum> From<EnumMap<K, ()>> for EnumSet<K> {
+    #[inline]
+    fn from(value: EnumMap<K, ()>) -> Self {
+        value.storage.keys()
+    }
+}
+
+impl<K: Enum> FromIterator<K> for EnumMap<K, ()> {
+    /// Collects keys into a map with no values, so that generic code written against maps can
+    /// be used with presence data.
+    fn from_iter<T: IntoIterator<Item = K>>(iter: T) -> Self {
+        iter.into_iter().map(|k| (k, ())).collect()
+    }
+}
+
+impl<K: Enum> PartialEq<EnumSet<K>> for EnumMap<K, ()> {
+    /// Returns `true` if the map's keys are exactly the elements of the set.
+    #[inline]
+    fn eq(&self, other: &EnumSet<K>) -> bool {
+        self.storage.keys() == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::hash_map::DefaultHasher;
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[rustfmt::skip] #[allow(dead_code)]
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Enum)]
+    enum DemoEnum { A, B, C, D, E, F, G, H, I, J }
+
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Counts calls to `from_index`, which has to search the enumeration for this type.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct Searched(DemoEnum);
+
+    std::thread_local! {
+        static SEARCHES: core::cell::Cell<usize> = const { core::cell::Cell::new(0) };
+    }
+
+    impl Enum for Searched {
+        type Rep = <DemoEnum as Enum>::Rep;
+        const SIZE: usize = DemoEnum::SIZE;
+        const MIN: Self = Self(DemoEnum::MIN);
+        const MAX: Self = Self(DemoEnum::MAX);
+        const BITMASK: Self::Rep = DemoEnum::BITMASK;
+
+        fn succ(self) -> Option<Self> {
+            self.0.succ().map(Self)
+        }
+
+        fn pred(self) -> Option<Self> {
+            self.0.pred().map(Self)
+        }
+
+        fn bit(self) -> Self::Rep {
+            self.0.bit()
+        }
+
+        fn index(self) -> usize {
+            self.0.index()
+        }
+
+        fn from_index(i: usize) -> Option<Self> {
+            SEARCHES.set(SEARCHES.get() + 1);
+            Self::enumerate(..).find(|x| x.index() == i)
+        }
+    }
+
+    #[test]
+    fn test_walks_step_between_keys() {
+        let searches = |f: &mut dyn FnMut()| {
+            SEARCHES.set(0);
+            f();
+            SEARCHES.get()
+        };
+        let mut map: EnumMap<Searched, usize> = Searched::enumerate(..)
+            .map(|k| (k, k.index()))
+            .filter(|&(_, i)| i % 3 != 1)
+            .collect();
+        let expected: Vec<_> = map.iter().map(|(k, &v)| (k, v)).collect();
+
+        assert_eq!(searches(&mut || assert_eq!(map.iter().count(), 7)), 0);
+        assert_eq!(
+            searches(&mut || {
+                let mut iter = map.iter();
+                let mut both = Vec::new();
+                while let (Some((a, _)), Some((b, _))) = (iter.next(), iter.next_back()) {
+                    both.extend([a.index(), b.index()]);
+                }
+                assert_eq!(both, [0, 9, 2, 8, 3, 6]);
+            }),
+            0
+        );
+        assert_eq!(
+            searches(&mut || {
+                let pairs: Vec<_> = map.iter().map(|(k, &v)| (k, v)).collect();
+                assert_eq!(pairs, expected);
+            }),
+            0
+        );
+        assert_eq!(searches(&mut || map.retain(|_, v| *v % 2 == 0)), 0);
+        assert_eq!(
+            searches(&mut || assert_eq!(map.extract_if(|_, _| true).rev().count(), 4)),
+            0
+        );
+    }
+
+    #[test]
+    fn test_drain_budgeted_resumes() {
+        let mut map: EnumMap<DemoEnum, usize> = DemoEnum::enumerate_all(..)
+            .map(|k| (k, k.index()))
+            .collect();
+        map.remove(DemoEnum::B);
+        let mut cursor = None;
+        let first: Vec<_> = map.drain_budgeted(&mut cursor, 2).map(|(k, _)| k).collect();
+        assert_eq!(first, [DemoEnum::A, DemoEnum::C]);
+        assert_eq!(cursor, Some(DemoEnum::D));
+
+        map.insert(DemoEnum::A, 0);
+        let mut rest = map.drain_budgeted(&mut cursor, usize::MAX);
+        assert_eq!(rest.next(), Some((DemoEnum::D, 3)));
+        assert_eq!(
+            rest.by_ref().last(),
+            Some((DemoEnum::MAX, DemoEnum::SIZE - 1))
+        );
+        assert_eq!(rest.next(), None);
+        drop(rest);
+        assert_eq!(cursor, None);
+        assert_eq!(map.keys().collect::<Vec<_>>(), [DemoEnum::A]);
+
+        let mut iter = map.drain_budgeted(&mut cursor, 0);
+        assert_eq!(iter.next(), None);
+        drop(iter);
+        assert_eq!(cursor, None);
+        assert_eq!(map.drain_budgeted(&mut cursor, 1).count(), 1);
+        assert_eq!(cursor, Some(DemoEnum::B));
+    }
+
+    #[test]
+    fn test_unit_map_set_roundtrip() {
+        let set = EnumSet::from([DemoEnum::B, DemoEnum::E, DemoEnum::J]);
+        let map = EnumMap::from(set);
+        assert_eq!(map, set);
+        assert_eq!(map.len(), set.len());
+        assert_eq!(set.into_iter().collect::<EnumMap<_, ()>>(), map);
+        assert_eq!(EnumSet::from(map), set);
+    }
+
+    #[test]
+    fn test_unallocated_eq() {
+        let mut map: EnumMap<DemoEnum, u64> = EnumMap::new();
+        map.insert(DemoEnum::C, 3);
+        map.remove(DemoEnum::C);
+        assert!(map.is_allocated());
+        assert_eq!(map, EnumMap::new());
+        assert_eq!(map.cmp(&EnumMap::new()), Ordering::Equal);
+        assert_eq!(hash_of(&map), hash_of(&EnumMap::<DemoEnum, u64>::new()));
+    }
+
+    #[test]
+    fn test_eq_compares_keys_and_values() {
+        let a = EnumMap::from([(DemoEnum::A, 1), (DemoEnum::J, 2)]);
+        let b = EnumMap::from([(DemoEnum::A, 1), (DemoEnum::I, 2)]);
+        let c = EnumMap::from([(DemoEnum::A, 1), (DemoEnum::J, 3)]);
+        assert_ne!(a, b);
+        assert!(a.same_keys(&c));
+        assert_ne!(a, c);
+        assert!(a < c);
+    }
+
+    #[test]
+    fn test_apply_len() {
+        let mut map = EnumMap::from([(DemoEnum::A, 1), (DemoEnum::B, 2)]);
+        map.apply([
+            (DemoEnum::A, Update::Remove),
+            (DemoEnum::A, Update::Remove),
+            (DemoEnum::B, Update::Set(3)),
+            (DemoEnum::C, Update::Modify(|v| *v += 1)),
+            (DemoEnum::J, Update::Set(4)),
+        ]);
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.keys().collect::<Vec<_>>(), [DemoEnum::B, DemoEnum::J]);
+        assert_eq!(map.values().sum::<i32>(), 7);
+    }
+
+    #[test]
+    fn test_chunks() {
+        let map = EnumMap::from([(DemoEnum::A, 0), (DemoEnum::E, 4), (DemoEnum::J, 9)]);
+        let chunks = map.chunks(3);
+        assert_eq!(chunks.len(), 4);
+        let windows: Vec<_> = chunks
+            .rev()
+            .map(|(keys, values)| (keys.collect::<Vec<_>>(), values.collect::<Vec<_>>()))
+            .collect();
+        assert_eq!(
+            windows,
+            [
+                (vec![DemoEnum::J], vec![Some(&9)]),
+                (
+                    vec![DemoEnum::G, DemoEnum::H, DemoEnum::I],
+                    vec![None, None, None]
+                ),
+                (
+                    vec![DemoEnum::D, DemoEnum::E, DemoEnum::F],
+                    vec![None, Some(&4), None]
+                ),
+                (
+                    vec![DemoEnum::A, DemoEnum::B, DemoEnum::C],
+                    vec![Some(&0), None, None]
+                ),
+            ]
+        );
+        let unallocated: EnumMap<DemoEnum, u8> = EnumMap::new();
+        assert!(unallocated
+            .chunks(4)
+            .all(|(_, mut values)| values.all(|v| v.is_none())));
+    }
+
+    #[test]
+    fn test_from_iter_duplicates() {
+        let map = EnumMap::from([(DemoEnum::B, 1), (DemoEnum::B, 2)]);
+        assert_eq!(map.len(), 1);
+        assert_eq!(map[DemoEnum::B], 2);
+    }
+
+    fn full_map(value: &Rc<()>) -> EnumMap<DemoEnum, Rc<()>> {
+        DemoEnum::enumerate(..)
+            .step_by(2)
+            .map(|k| (k, Rc::clone(value)))
+            .collect()
+    }
+
+    #[test]
+    fn test_drops_values() {
+        let value = Rc::new(());
+
+        let mut map = full_map(&value);
+        map.insert(DemoEnum::A, Rc::clone(&value));
+        map.remove(DemoEnum::C);
+        let clone = map.clone();
+        map.clear();
+        assert_eq!(Rc::strong_count(&value), 1 + clone.len());
+        drop(clone);
+        assert_eq!(Rc::strong_count(&value), 1);
+
+        let mut map = full_map(&value);
+        assert_eq!(map.drain().take(2).count(), 2);
+        assert!(map.is_empty());
+        assert_eq!(Rc::strong_count(&value), 1);
+        map.insert(DemoEnum::J, Rc::clone(&value));
+        assert_eq!(map.len(), 1);
+        drop(map);
+
+        let mut iter = full_map(&value).into_iter();
+        iter.next_back();
+        iter.next();
+        drop(iter);
+        assert_eq!(Rc::strong_count(&value), 1);
+
+        let mut map = full_map(&value);
+        let extracted: Vec<_> = map.extract_if(|k, _| k > DemoEnum::D).collect();
+        assert_eq!(map.len() + extracted.len(), 5);
+        drop(extracted);
+        map.retain(|k, _| k == DemoEnum::A);
+        assert_eq!(Rc::strong_count(&value), 2);
+        drop(map);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[cfg(feature = "uninit-storage")]
+    #[test]
+    fn test_as_full_slice() {
+        let mut map: EnumMap<DemoEnum, usize> = EnumMap::new();
+        assert_eq!(map.as_full_slice(), None);
+        for k in DemoEnum::enumerate(..) {
+            map.insert(k, k.index());
+        }
+        assert_eq!(
+            map.as_full_slice(),
+            Some(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9][..])
+        );
+        map.remove(DemoEnum::J);
+        assert_eq!(map.as_full_slice(), None);
+        map.clear();
+        assert_eq!(map.as_full_slice(), None);
+    }
+
+    #[test]
+    fn test_replace_with_panic() {
+        let value = Rc::new(());
+        let mut map = full_map(&value);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            map.entry(DemoEnum::A).replace_with(|_, _| panic!());
+        }));
+        assert!(result.is_err());
+        assert!(!map.contains_key(DemoEnum::A));
+        assert_eq!(map.len(), 4);
+        assert_eq!(map.iter().count(), 4);
+        drop(map);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[cfg(not(feature = "uninit-storage"))]
+    #[test]
+    fn test_borrowed_values_may_be_declared_later() {
+        let mut map = EnumMap::new();
+        let value = String::from("value");
+        map.insert(DemoEnum::A, &value);
+        assert_eq!(map[DemoEnum::A], "value");
+    }
+
+    #[test]
+    fn test_leaked_drain() {
+        let value = Rc::new(());
+        let mut map = full_map(&value);
+        core::mem::forget(map.drain());
+        assert!(map.is_empty());
+        map.insert(DemoEnum::B, Rc::clone(&value));
+        assert_eq!(map.iter().count(), 1);
+        drop(map);
+        // `MaybeUninit` slots leak the drained values. `Option` slots still own them, so the map
+        // drops them along with the rest.
+        let leaked = if cfg!(feature = "uninit-storage") {
+            5
+        } else {
+            0
+        };
+        assert_eq!(Rc::strong_count(&value), 1 + leaked);
+    }
+
+    #[test]
+    fn test_try_for_each_matches_iter() {
+        let mut map = EnumMap::from([(DemoEnum::B, 1), (DemoEnum::F, 5), (DemoEnum::J, 9)]);
+        let mut visited = Vec::new();
+        let broke = map.try_for_each(|k, &v| {
+            visited.push((k, v));
+            ControlFlow::<()>::Continue(())
+        });
+        assert_eq!(broke, None);
+        assert_eq!(
+            visited,
+            map.iter().map(|(k, &v)| (k, v)).collect::<Vec<_>>()
+        );
+
+        let broke = map.try_for_each_mut(|k, v| {
+            *v += 1;
+            if k == DemoEnum::F {
+                ControlFlow::Break(*v)
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(broke, Some(6));
+        assert_eq!(
+            map,
+            EnumMap::from([(DemoEnum::B, 2), (DemoEnum::F, 6), (DemoEnum::J, 9)])
+        );
+    }
+
+    #[test]
+    fn test_iter_both_ends() {
+        let mut map = EnumMap::from([(DemoEnum::A, 0), (DemoEnum::E, 4), (DemoEnum::J, 9)]);
+        for (k, v) in map.iter_mut().rev() {
+            *v += k.index();
+        }
+        let mut iter = map.iter();
+        assert_eq!(iter.next(), Some((DemoEnum::A, &0)));
+        assert_eq!(iter.next_back(), Some((DemoEnum::J, &18)));
+        assert_eq!(iter.next(), Some((DemoEnum::E, &8)));
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_walks_runs_across_words() {
+        let keys: Vec<u8> = (0..3)
+            .chain(62..67)
+            .chain([100])
+            .chain(127..=128)
+            .chain(190..=255)
+            .collect();
+        let map: EnumMap<u8, u8> = keys.iter().map(|&k| (k, k)).collect();
+        let rev: Vec<u8> = keys.iter().rev().copied().collect();
+
+        assert_eq!(map.keys().collect::<Vec<_>>(), keys);
+        assert_eq!(map.keys().rev().collect::<Vec<_>>(), rev);
+        assert_eq!(map.values().copied().collect::<Vec<_>>(), keys);
+        assert_eq!(map.clone().into_values().rev().collect::<Vec<_>>(), rev);
+
+        let mut iter = map.iter();
+        assert_eq!(iter.next(), Some((0, &0)));
+        assert_eq!(iter.next_back(), Some((255, &255)));
+        assert_eq!(iter.nth(3), Some((63, &63)));
+        let middle: Vec<u8> = iter.rev().map(|(k, _)| k).collect();
+        assert_eq!(middle, rev[1..rev.len() - 5]);
+
+        let mut visited = Vec::new();
+        map.try_for_each(|k, &v| {
+            visited.push((k, v));
+            ControlFlow::<()>::Continue(())
+        });
+        assert_eq!(
+            visited,
+            map.iter().map(|(k, &v)| (k, v)).collect::<Vec<_>>()
+        );
+
+        let mut evens = map.clone();
+        evens.retain(|k, _| k % 2 == 0);
+        let expected: Vec<u8> = keys.iter().copied().filter(|k| k % 2 == 0).collect();
+        assert_eq!(evens.keys().collect::<Vec<_>>(), expected);
+        assert_eq!(evens.len(), expected.len());
+
+        let mut odds = map;
+        let extracted: Vec<u8> = odds
+            .extract_if(|k, _| k % 2 == 0)
+            .rev()
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(
+            extracted,
+            expected.iter().rev().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(odds.len(), keys.len() - expected.len());
+    }
+}
@@ -0,0 +1,34 @@
+use core::error::Error;
+use core::fmt::{self, Debug, Display, Formatter};
+
+/// The error returned by the non-allocating methods of [`EnumMap`] when the map has not
+/// allocated its backing store yet.
+///
+/// [`EnumMap`]: crate::EnumMap
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NotAllocated;
+
+impl Display for NotAllocated {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("map has not allocated its backing store")
+    }
+}
+
+impl Error for NotAllocated {}
+
+/// The error returned by [`EnumMap::topological_order`] when the graph has a cycle.
+///
+/// [`EnumMap::topological_order`]: crate::EnumMap::topological_order
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CycleError<K> {
+    /// A value that can reach itself.
+    pub value: K,
+}
+
+impl<K: Debug> Display for CycleError<K> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "cycle through {:?}", self.value)
+    }
+}
+
+impl<K: Debug> Error for CycleError<K> {}
@@ -0,0 +1,137 @@
+use alloc::vec::Vec;
+
+use super::enum_map::EnumMap;
+use super::error::CycleError;
+use crate::enumerate::Enum;
+use crate::set::EnumSet;
+
+/// Graph operations, treating the map as an adjacency list from each key to the values it has
+/// edges to. A missing key has no outgoing edges.
+impl<K: Enum> EnumMap<K, EnumSet<K>> {
+    /// Returns every value reachable from `k` by following one or more edges. The result only
+    /// contains `k` itself if `k` is on a cycle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumMap, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Stage { Fetch, Parse, Check, Emit }
+    ///
+    /// let deps = EnumMap::from([
+    ///     (Stage::Emit, enums![Stage::Check]),
+    ///     (Stage::Check, enums![Stage::Parse]),
+    ///     (Stage::Parse, enums![Stage::Fetch]),
+    /// ]);
+    /// assert_eq!(
+    ///     deps.reachable_from(Stage::Check),
+    ///     enums![Stage::Parse, Stage::Fetch]
+    /// );
+    /// assert!(deps.reachable_from(Stage::Fetch).is_empty());
+    /// ```
+    pub fn reachable_from(&self, k: K) -> EnumSet<K> {
+        let mut reached = EnumSet::new();
+        let mut frontier = self.get(k).copied().unwrap_or_default();
+        while !frontier.is_empty() {
+            reached = reached.union(&frontier);
+            let mut next = EnumSet::new();
+            for x in frontier {
+                if let Some(targets) = self.get(x) {
+                    next = next.union(targets);
+                }
+            }
+            frontier = next.difference(&reached);
+        }
+        reached
+    }
+
+    /// Returns the transitive closure of the graph: a map from each key to every value
+    /// reachable from it. Keys with nothing reachable are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumMap, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Stage { Fetch, Parse, Check, Emit }
+    ///
+    /// let deps = EnumMap::from([
+    ///     (Stage::Emit, enums![Stage::Check]),
+    ///     (Stage::Check, enums![Stage::Parse]),
+    ///     (Stage::Fetch, enums![]),
+    /// ]);
+    /// let closure = deps.transitive_closure();
+    /// assert_eq!(closure[Stage::Emit], enums![Stage::Parse, Stage::Check]);
+    /// assert_eq!(closure[Stage::Check], enums![Stage::Parse]);
+    /// assert_eq!(closure.len(), 2);
+    /// ```
+    pub fn transitive_closure(&self) -> Self {
+        self.keys()
+            .filter_map(|k| {
+                let reached = self.reachable_from(k);
+                (!reached.is_empty()).then_some((k, reached))
+            })
+            .collect()
+    }
+
+    /// Orders the values in the graph so that every value comes before the values it has edges
+    /// to. Values that appear neither as a key nor in a set are left out. Ties are broken in
+    /// index order, so the result is deterministic.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CycleError`] if the graph has a cycle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumMap, enums};
+    /// use enumeration::map::CycleError;
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Stage { Fetch, Parse, Check, Emit }
+    ///
+    /// // Each stage points to the stages that must run after it.
+    /// let mut next = EnumMap::from([
+    ///     (Stage::Check, enums![Stage::Emit]),
+    ///     (Stage::Fetch, enums![Stage::Parse]),
+    ///     (Stage::Parse, enums![Stage::Check, Stage::Emit]),
+    /// ]);
+    /// assert_eq!(
+    ///     next.topological_order(),
+    ///     Ok(vec![Stage::Fetch, Stage::Parse, Stage::Check, Stage::Emit])
+    /// );
+    ///
+    /// next.insert(Stage::Emit, enums![Stage::Parse]);
+    /// assert!(matches!(next.topological_order(), Err(CycleError { .. })));
+    /// ```
+    pub fn topological_order(&self) -> Result<Vec<K>, CycleError<K>> {
+        let mut remaining = self.iter().fold(EnumSet::new(), |nodes, (k, targets)| {
+            let mut nodes = nodes.union(targets);
+            nodes.insert(k);
+            nodes
+        });
+        let mut order = Vec::with_capacity(remaining.len());
+        while !remaining.is_empty() {
+            let mut targeted = EnumSet::new();
+            for k in remaining {
+                if let Some(targets) = self.get(k) {
+                    targeted = targeted.union(targets);
+                }
+            }
+            let ready = remaining.difference(&targeted);
+            if ready.is_empty() {
+                let value = remaining
+                    .into_iter()
+                    .find(|&k| self.reachable_from(k).contains(k))
+                    .expect("a graph with no sources has a cycle");
+                return Err(CycleError { value });
+            }
+            order.extend(ready);
+            remaining = remaining.difference(&ready);
+        }
+        Ok(order)
+    }
+}
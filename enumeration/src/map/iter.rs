@@ -1,51 +1,133 @@
-use std::iter::{FusedIterator, Iterator, Zip};
-use std::slice;
+use core::convert::Infallible;
+use core::iter::{FusedIterator, Iterator};
+use core::marker::PhantomData;
+use core::ops::ControlFlow;
 
+use super::storage::{KeyCursor, Slots, SlotsRef, Storage, WORD_BITS};
 use crate::enumerate::{Enum, Enumeration};
 
-fn map_fold<B, K, From, To>(
-    mut f: impl FnMut(From) -> Option<To>,
-    mut fold: impl FnMut(B, (K, To)) -> B,
-) -> impl FnMut(B, (K, From)) -> B {
-    move |acc, (k, item)| match f(item) {
-        Some(x) => fold(acc, (k, x)),
-        None => acc,
-    }
-}
-
 #[must_use = "iterators are lazy and do nothing unless consumed"]
-pub struct Iter<K, V, I: Iterator> {
-    inner: Zip<Enumeration<K>, I>,
-    f: fn(I::Item) -> Option<V>,
+pub struct Iter<K: Enum, V, I> {
+    slots: I,
+    /// Position one past the last slot visited from the front.
+    front: usize,
+    /// Position of the last slot visited from the back.
+    back: usize,
+    /// Word `front_word` of the occupancy bitvector, less the slots already visited from the
+    /// front.
+    front_bits: usize,
+    front_word: usize,
+    /// Word `back_word` of the occupancy bitvector, less the slots already visited from the
+    /// back.
+    back_bits: usize,
+    back_word: usize,
+    front_keys: KeyCursor<K>,
+    back_keys: KeyCursor<K>,
     remaining: usize,
+    marker: PhantomData<fn() -> V>,
 }
 
-impl<K: Enum, V, I: Iterator> Iter<K, V, I> {
+impl<K: Enum, V, I: Slots<V>> Iter<K, V, I> {
     #[inline]
-    pub(super) fn new<It>(iter: It, size: usize, f: fn(I::Item) -> Option<V>) -> Self
-    where
-        It: IntoIterator<IntoIter = I>,
-    {
+    pub(super) fn new(slots: I, size: usize) -> Self {
+        let back_word = K::SIZE.saturating_sub(1) / WORD_BITS;
         Self {
-            inner: K::enumerate(..).zip(iter.into_iter()),
-            f,
+            front: 0,
+            back: K::SIZE,
+            front_bits: slots.marks(0, 0, K::SIZE),
+            front_word: 0,
+            back_bits: slots.marks(back_word, 0, K::SIZE),
+            back_word,
+            slots,
+            front_keys: KeyCursor::front(),
+            back_keys: KeyCursor::back(),
             remaining: size,
+            marker: PhantomData,
         }
     }
+
+    /// Converts the iterator into an iterator over only the keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(Ordering::Equal, 1), (Ordering::Less, 5)]);
+    ///
+    /// let keys: Vec<_> = map.iter().keys().collect();
+    /// assert_eq!(keys, [Ordering::Less, Ordering::Equal]);
+    /// ```
+    #[inline]
+    pub fn keys(self) -> Keys<K, V, I> {
+        Keys::new(self)
+    }
+
+    /// Converts the iterator into an iterator over only the values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(Ordering::Equal, 1), (Ordering::Less, 5)]);
+    ///
+    /// let values: Vec<_> = map.iter().values().collect();
+    /// assert_eq!(values, [&5, &1]);
+    /// ```
+    #[inline]
+    pub fn values(self) -> Values<K, V, I> {
+        Values::new(self)
+    }
 }
 
-impl<K: Enum, V, I: Iterator> Iterator for Iter<K, V, I> {
+impl<K: Enum, V, I: Slots<V> + Clone> Clone for Iter<K, V, I> {
+    fn clone(&self) -> Self {
+        Self {
+            slots: self.slots.clone(),
+            front: self.front,
+            back: self.back,
+            front_bits: self.front_bits,
+            front_word: self.front_word,
+            back_bits: self.back_bits,
+            back_word: self.back_word,
+            front_keys: self.front_keys,
+            back_keys: self.back_keys,
+            remaining: self.remaining,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<K: Enum, V, I: Slots<V>> Iterator for Iter<K, V, I> {
     type Item = (K, V);
 
     #[cfg_attr(feature = "inline-more", inline)]
     fn next(&mut self) -> Option<Self::Item> {
-        for (k, v) in &mut self.inner {
-            if let Some(item) = (self.f)(v) {
-                self.remaining -= 1;
-                return Some((k, item));
+        if self.remaining == 0 {
+            return None;
+        }
+        while self.front_bits == 0 {
+            self.front_word += 1;
+            if self.front_word * WORD_BITS >= self.back {
+                return None;
             }
+            self.front_bits = self.slots.marks(self.front_word, self.front, self.back);
         }
-        None
+        let i = self.front_word * WORD_BITS + self.front_bits.trailing_zeros() as usize;
+        // The back may have moved into this word since it was read.
+        if i >= self.back {
+            return None;
+        }
+        self.front_bits &= self.front_bits - 1;
+        self.front = i + 1;
+        self.remaining -= 1;
+        let key = self.front_keys.key_at(i);
+        // SAFETY: `i` is marked, and positions between `front` and `back` are visited at most
+        // once.
+        Some((key, unsafe { self.slots.take(i) }))
     }
 
     #[inline]
@@ -59,157 +141,512 @@ impl<K: Enum, V, I: Iterator> Iterator for Iter<K, V, I> {
     }
 
     #[inline]
-    fn fold<B, F>(self, init: B, fold: F) -> B
+    fn fold<B, F>(mut self, init: B, mut f: F) -> B
     where
         F: FnMut(B, Self::Item) -> B,
     {
-        self.inner.fold(init, map_fold(self.f, fold))
+        let mut acc = init;
+        let (front, back) = (self.front, self.back);
+        if front >= back {
+            return acc;
+        }
+        for w in front / WORD_BITS..=(back - 1) / WORD_BITS {
+            let bits = self.slots.marks(w, front, back);
+            let flow: ControlFlow<Infallible, B> =
+                self.front_keys.try_fold_word(w, bits, acc, |acc, i, k| {
+                    // SAFETY: `i` is marked, and each position between `front` and `back` is
+                    // visited once.
+                    ControlFlow::Continue(f(acc, (k, unsafe { self.slots.take(i) })))
+                });
+            acc = match flow {
+                ControlFlow::Continue(acc) => acc,
+                ControlFlow::Break(never) => match never {},
+            };
+        }
+        acc
     }
 }
 
-impl<K: Enum, V, I: Iterator> ExactSizeIterator for Iter<K, V, I> {
+impl<K: Enum, V, I: Slots<V>> ExactSizeIterator for Iter<K, V, I> {
     #[inline]
     fn len(&self) -> usize {
         self.remaining
     }
 }
 
-impl<K: Enum, V, I: DoubleEndedIterator + ExactSizeIterator> DoubleEndedIterator for Iter<K, V, I> {
+impl<K: Enum, V, I: Slots<V>> DoubleEndedIterator for Iter<K, V, I> {
     #[cfg_attr(feature = "inline-more", inline)]
     fn next_back(&mut self) -> Option<Self::Item> {
-        while let Some((k, v)) = self.inner.next_back() {
-            if let Some(item) = (self.f)(v) {
-                self.remaining -= 1;
-                return Some((k, item));
+        if self.remaining == 0 {
+            return None;
+        }
+        while self.back_bits == 0 {
+            if self.back_word * WORD_BITS <= self.front {
+                return None;
             }
+            self.back_word -= 1;
+            self.back_bits = self.slots.marks(self.back_word, self.front, self.back);
         }
-        None
+        let bit = WORD_BITS - 1 - self.back_bits.leading_zeros() as usize;
+        let i = self.back_word * WORD_BITS + bit;
+        // The front may have moved into this word since it was read.
+        if i < self.front {
+            return None;
+        }
+        self.back_bits &= !(1 << bit);
+        self.back = i;
+        self.remaining -= 1;
+        let key = self.back_keys.key_at(i);
+        // SAFETY: `i` is marked, and positions between `front` and `back` are visited at most
+        // once.
+        Some((key, unsafe { self.slots.take(i) }))
     }
 
+    #[inline]
+    fn rfold<B, F>(mut self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut acc = init;
+        let (front, back) = (self.front, self.back);
+        if front >= back {
+            return acc;
+        }
+        for w in (front / WORD_BITS..=(back - 1) / WORD_BITS).rev() {
+            let bits = self.slots.marks(w, front, back);
+            let flow: ControlFlow<Infallible, B> =
+                self.back_keys.try_rfold_word(w, bits, acc, |acc, i, k| {
+                    // SAFETY: `i` is marked, and each position between `front` and `back` is
+                    // visited once.
+                    ControlFlow::Continue(f(acc, (k, unsafe { self.slots.take(i) })))
+                });
+            acc = match flow {
+                ControlFlow::Continue(acc) => acc,
+                ControlFlow::Break(never) => match never {},
+            };
+        }
+        acc
+    }
+}
+
+impl<K: Enum, V, I: Slots<V>> FusedIterator for Iter<K, V, I> {}
+
+/// An iterator over the keys of an `EnumMap`.
+///
+/// This `struct` is created by the [`keys`] method on [`EnumMap`].
+/// See its documentation for more.
+///
+/// [`EnumMap`]: crate::EnumMap
+/// [`keys`]: crate::EnumMap::keys
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Keys<K: Enum, V, I> {
+    inner: Iter<K, V, I>,
+}
+
+impl<K: Enum, V, I: Slots<V>> Keys<K, V, I> {
+    #[inline]
+    pub(super) fn new(inner: Iter<K, V, I>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<K: Enum, V, I: Slots<V> + Clone> Clone for Keys<K, V, I> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K: Enum, V, I: Slots<V>> Iterator for Keys<K, V, I> {
+    type Item = K;
+
     #[cfg_attr(feature = "inline-more", inline)]
-    fn rfold<B, F>(self, init: B, fold: F) -> B
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, _)| k)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.inner.count()
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, mut fold: F) -> B
     where
         F: FnMut(B, Self::Item) -> B,
     {
-        self.inner.rfold(init, map_fold(self.f, fold))
-    }
-}
-
-#[inline]
-fn matches_mut<K: Copy, V, P>(key: K, val: &mut Option<V>, pred: &mut P) -> bool
-where
-    P: FnMut(K, &mut V) -> bool,
-{
-    let Some(val) = val.as_mut() else {
-        return false;
-    };
-    pred(key, val)
-}
-
-fn drain_fold<'a, B, K: Copy, V: 'a>(
-    mut pred: impl FnMut(K, &mut V) -> bool,
-    mut fold: impl FnMut(B, (K, V)) -> B,
-    size: &'a mut usize,
-) -> impl FnMut(B, (K, &'a mut Option<V>)) -> B {
-    move |acc, (k, item)| {
-        if matches_mut(k, item, &mut pred) {
-            *size -= 1;
-            fold(acc, (k, item.take().unwrap()))
-        } else {
-            acc
+        self.inner.fold(init, |acc, (k, _)| fold(acc, k))
+    }
+}
+
+impl<K: Enum, V, I: Slots<V>> ExactSizeIterator for Keys<K, V, I> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<K: Enum, V, I: Slots<V>> DoubleEndedIterator for Keys<K, V, I> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(k, _)| k)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn rfold<B, F>(self, init: B, mut fold: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.inner.rfold(init, |acc, (k, _)| fold(acc, k))
+    }
+}
+
+impl<K: Enum, V, I: Slots<V>> FusedIterator for Keys<K, V, I> {}
+
+/// An iterator over the values of an `EnumMap`.
+///
+/// This `struct` is created by the [`values`], [`values_mut`], and [`into_values`] methods on
+/// [`EnumMap`]. See their documentation for more.
+///
+/// [`EnumMap`]: crate::EnumMap
+/// [`values`]: crate::EnumMap::values
+/// [`values_mut`]: crate::EnumMap::values_mut
+/// [`into_values`]: crate::EnumMap::into_values
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Values<K: Enum, V, I> {
+    inner: Iter<K, V, I>,
+}
+
+impl<K: Enum, V, I: Slots<V>> Values<K, V, I> {
+    #[inline]
+    pub(super) fn new(inner: Iter<K, V, I>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<K: Enum, V, I: Slots<V> + Clone> Clone for Values<K, V, I> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
         }
     }
 }
 
-impl<K: Enum, V, I: FusedIterator> FusedIterator for Iter<K, V, I> {}
+impl<K: Enum, V, I: Slots<V>> Iterator for Values<K, V, I> {
+    type Item = V;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, v)| v)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.inner.count()
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, mut fold: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.inner.fold(init, |acc, (_, v)| fold(acc, v))
+    }
+}
+
+impl<K: Enum, V, I: Slots<V>> ExactSizeIterator for Values<K, V, I> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<K: Enum, V, I: Slots<V>> DoubleEndedIterator for Values<K, V, I> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(_, v)| v)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn rfold<B, F>(self, init: B, mut fold: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.inner.rfold(init, |acc, (_, v)| fold(acc, v))
+    }
+}
+
+impl<K: Enum, V, I: Slots<V>> FusedIterator for Values<K, V, I> {}
 
 #[must_use = "iterators are lazy and do nothing unless consumed"]
-pub struct ExtractIf<'a, K, V, P> {
-    inner: Zip<Enumeration<K>, slice::IterMut<'a, Option<V>>>,
+pub struct ExtractIf<'a, K: Enum, V, P> {
+    /// Position of the next slot from the front.
+    front: usize,
+    /// Position one past the next slot from the back.
+    back: usize,
+    front_keys: KeyCursor<K>,
+    back_keys: KeyCursor<K>,
     pred: P,
-    size: &'a mut usize,
+    storage: &'a mut Storage<K, V>,
 }
 
 impl<'a, K: Enum, V, P: FnMut(K, &mut V) -> bool> ExtractIf<'a, K, V, P> {
     #[inline]
-    pub(super) fn new(iter: slice::IterMut<'a, Option<V>>, size: &'a mut usize, pred: P) -> Self {
+    pub(super) fn new(storage: &'a mut Storage<K, V>, pred: P) -> Self {
         Self {
-            inner: K::enumerate(..).zip(iter),
+            front: 0,
+            back: K::SIZE,
+            front_keys: KeyCursor::front(),
+            back_keys: KeyCursor::back(),
             pred,
-            size,
+            storage,
+        }
+    }
+
+    #[inline]
+    fn extract(
+        storage: &mut Storage<K, V>,
+        pred: &mut P,
+        keys: &mut KeyCursor<K>,
+        i: usize,
+    ) -> Option<(K, V)> {
+        let k = keys.key_at(i);
+        let val = storage.get_mut(k)?;
+        if pred(k, val) {
+            storage.remove(k).map(|val| (k, val))
+        } else {
+            None
         }
     }
 }
 
-impl<'a, K: Enum, V, P: FnMut(K, &mut V) -> bool> Iterator for ExtractIf<'a, K, V, P> {
+impl<K: Enum, V, P: FnMut(K, &mut V) -> bool> Iterator for ExtractIf<'_, K, V, P> {
     type Item = (K, V);
 
     #[cfg_attr(feature = "inline-more", inline)]
     fn next(&mut self) -> Option<Self::Item> {
-        for (k, v) in &mut self.inner {
-            if matches_mut(k, v, &mut self.pred) {
-                *self.size -= 1;
-                return Some((k, v.take().unwrap()));
+        while let Some(i) = self.storage.next_occupied(self.front, self.back) {
+            self.front = i + 1;
+            if let Some(item) = Self::extract(self.storage, &mut self.pred, &mut self.front_keys, i)
+            {
+                return Some(item);
             }
         }
+        self.front = self.back;
         None
     }
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (0, Some(*self.size))
+        (0, Some(self.storage.len()))
     }
+}
 
+impl<K: Enum, V, P: FnMut(K, &mut V) -> bool> ExactSizeIterator for ExtractIf<'_, K, V, P> {
     #[inline]
-    fn count(mut self) -> usize {
-        let mut count = 0;
-        for (k, v) in &mut self.inner {
-            if matches_mut(k, v, &mut self.pred) {
-                *v = None;
-                *self.size -= 1;
-                count += 1;
+    fn len(&self) -> usize {
+        self.storage.len()
+    }
+}
+
+impl<K: Enum, V, P: FnMut(K, &mut V) -> bool> DoubleEndedIterator for ExtractIf<'_, K, V, P> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some(i) = self.storage.prev_occupied(self.front, self.back) {
+            self.back = i;
+            if let Some(item) = Self::extract(self.storage, &mut self.pred, &mut self.back_keys, i)
+            {
+                return Some(item);
             }
         }
-        count
+        self.back = self.front;
+        None
     }
+}
+
+impl<K: Enum, V, P: FnMut(K, &mut V) -> bool> FusedIterator for ExtractIf<'_, K, V, P> {}
+
+/// An iterator over fixed-size windows of an `EnumMap`'s keys, paired with the values at those
+/// keys.
+///
+/// This `struct` is created by the [`chunks`] method on [`EnumMap`].
+/// See its documentation for more.
+///
+/// [`EnumMap`]: crate::EnumMap
+/// [`chunks`]: crate::EnumMap::chunks
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Chunks<'a, K: Enum, V> {
+    slots: SlotsRef<'a, V>,
+    size: usize,
+    /// Index of the next chunk from the front.
+    front: usize,
+    /// Index one past the next chunk from the back.
+    back: usize,
+    marker: PhantomData<fn() -> K>,
+}
 
+impl<'a, K: Enum, V> Chunks<'a, K, V> {
     #[inline]
-    fn fold<B, F>(self, init: B, fold: F) -> B
-    where
-        F: FnMut(B, Self::Item) -> B,
-    {
-        self.inner
-            .fold(init, drain_fold(self.pred, fold, self.size))
+    pub(super) fn new(slots: SlotsRef<'a, V>, size: usize) -> Self {
+        assert!(size != 0, "chunk size must be non-zero");
+        Self {
+            slots,
+            size,
+            front: 0,
+            back: K::SIZE.div_ceil(size),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the `i`th chunk of the map, regardless of how far the iterator has advanced.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub(crate) fn chunk(&self, i: usize) -> (Enumeration<K>, Chunk<'a, V>) {
+        let start = i * self.size;
+        let end = K::SIZE.min(start + self.size);
+        let key = |i| {
+            K::from_index(i).expect("got None from calling Enum::from_index() where < Enum::SIZE")
+        };
+        let keys = K::enumerate_all(key(start)..=key(end - 1));
+        let chunk = Chunk {
+            slots: self.slots,
+            front: start,
+            back: end,
+        };
+        (keys, chunk)
+    }
+}
+
+impl<K: Enum, V> Clone for Chunks<'_, K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            slots: self.slots,
+            size: self.size,
+            front: self.front,
+            back: self.back,
+            marker: PhantomData,
+        }
     }
 }
 
-impl<'a, K: Enum, V, P: FnMut(K, &mut V) -> bool> ExactSizeIterator for ExtractIf<'a, K, V, P> {
+impl<'a, K: Enum, V> Iterator for Chunks<'a, K, V> {
+    type Item = (Enumeration<K>, Chunk<'a, V>);
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        let chunk = self.chunk(self.front);
+        self.front += 1;
+        Some(chunk)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+impl<K: Enum, V> ExactSizeIterator for Chunks<'_, K, V> {
     #[inline]
     fn len(&self) -> usize {
-        *self.size
+        self.back - self.front
     }
 }
 
-impl<'a, K: Enum, V, P: FnMut(K, &mut V) -> bool> DoubleEndedIterator for ExtractIf<'a, K, V, P> {
+impl<K: Enum, V> DoubleEndedIterator for Chunks<'_, K, V> {
     #[cfg_attr(feature = "inline-more", inline)]
     fn next_back(&mut self) -> Option<Self::Item> {
-        while let Some((k, v)) = self.inner.next_back() {
-            if matches_mut(k, v, &mut self.pred) {
-                *self.size -= 1;
-                return Some((k, v.take().unwrap()));
-            }
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.chunk(self.back))
+    }
+}
+
+impl<K: Enum, V> FusedIterator for Chunks<'_, K, V> {}
+
+/// An iterator over a window of an `EnumMap`'s slots, yielding `None` for vacant keys.
+///
+/// This `struct` is yielded by [`Chunks`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Chunk<'a, V> {
+    slots: SlotsRef<'a, V>,
+    /// Position of the next slot from the front.
+    front: usize,
+    /// Position one past the next slot from the back.
+    back: usize,
+}
+
+impl<V> Clone for Chunk<'_, V> {
+    fn clone(&self) -> Self {
+        Self {
+            slots: self.slots,
+            front: self.front,
+            back: self.back,
         }
-        None
     }
+}
+
+impl<'a, V> Iterator for Chunk<'a, V> {
+    type Item = Option<&'a V>;
 
     #[cfg_attr(feature = "inline-more", inline)]
-    fn rfold<B, F>(mut self, init: B, fold: F) -> B
-    where
-        F: FnMut(B, Self::Item) -> B,
-    {
-        self.inner
-            .rfold(init, drain_fold(&mut self.pred, fold, self.size))
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        let val = self.slots.get(self.front);
+        self.front += 1;
+        Some(val)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+impl<V> ExactSizeIterator for Chunk<'_, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.back - self.front
+    }
+}
+
+impl<V> DoubleEndedIterator for Chunk<'_, V> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.slots.get(self.back))
     }
 }
 
-impl<'a, K: Enum, V, P: FnMut(K, &mut V) -> bool> FusedIterator for ExtractIf<'a, K, V, P> {}
+impl<V> FusedIterator for Chunk<'_, V> {}
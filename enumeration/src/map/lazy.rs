@@ -0,0 +1,148 @@
+use core::fmt::{self, Debug, Formatter};
+use core::future::Future;
+use core::marker::PhantomData;
+
+use tokio::sync::OnceCell;
+
+use super::enum_map::EnumMap;
+use crate::enumerate::Enum;
+
+/// A map whose values are created asynchronously on first use, at most once per key.
+///
+/// Each key has its own cell, so values for different keys can be initialized concurrently,
+/// while concurrent requests for the same key wait for a single initializer to finish. This
+/// suits services that lazily open one client per backend, where the backends are variants of
+/// an enum.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::Enum;
+/// use enumeration::map::LazyEnumMap;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Backend { Primary, Replica }
+///
+/// async fn connect(backend: Backend) -> String {
+///     format!("connection to {backend:?}")
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let clients = LazyEnumMap::new();
+/// let (primary, replica) = tokio::join!(
+///     clients.get_or_init_async(Backend::Primary, connect),
+///     clients.get_or_init_async(Backend::Replica, connect),
+/// );
+/// assert_eq!(primary, "connection to Primary");
+/// assert_eq!(replica, "connection to Replica");
+/// assert_eq!(clients.len(), 2);
+/// # });
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub struct LazyEnumMap<K: Enum, V> {
+    cells: Box<[OnceCell<V>]>,
+    marker: PhantomData<fn() -> K>,
+}
+
+impl<K: Enum, V> LazyEnumMap<K, V> {
+    /// Creates a map with every key uninitialized.
+    pub fn new() -> Self {
+        Self {
+            cells: (0..K::SIZE).map(|_| OnceCell::new()).collect(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of initialized keys.
+    pub fn len(&self) -> usize {
+        self.cells.iter().filter(|cell| cell.initialized()).count()
+    }
+
+    /// Returns `true` if no key has been initialized.
+    pub fn is_empty(&self) -> bool {
+        !self.cells.iter().any(OnceCell::initialized)
+    }
+
+    /// Returns the value for a key if it has been initialized.
+    #[inline]
+    pub fn get(&self, k: K) -> Option<&V> {
+        self.cells[k.index()].get()
+    }
+
+    /// Returns the value for a key, initializing it with the future returned by `f` if it has
+    /// not been initialized yet.
+    ///
+    /// If several tasks call this for the same key at once, only one of them runs its future;
+    /// the others wait for it and then return the same value. If the running future is
+    /// cancelled, another waiting task takes over.
+    pub async fn get_or_init_async<F, Fut>(&self, k: K, f: F) -> &V
+    where
+        F: FnOnce(K) -> Fut,
+        Fut: Future<Output = V>,
+    {
+        self.cells[k.index()].get_or_init(|| f(k)).await
+    }
+
+    /// Returns the value for a key, initializing it with the future returned by `f` if it has
+    /// not been initialized yet.
+    ///
+    /// # Errors
+    ///
+    /// If the future fails, its error is returned and the key is left uninitialized, so a later
+    /// call can try again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::map::LazyEnumMap;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let map = LazyEnumMap::new();
+    /// let failed = map.get_or_try_init_async(Ordering::Less, |_| async { Err("offline") });
+    /// assert_eq!(failed.await, Err("offline"));
+    /// assert!(map.get(Ordering::Less).is_none());
+    ///
+    /// let ok = map.get_or_try_init_async(Ordering::Less, |_| async { Ok::<_, &str>(1) });
+    /// assert_eq!(ok.await, Ok(&1));
+    /// # });
+    /// ```
+    pub async fn get_or_try_init_async<E, F, Fut>(&self, k: K, f: F) -> Result<&V, E>
+    where
+        F: FnOnce(K) -> Fut,
+        Fut: Future<Output = Result<V, E>>,
+    {
+        self.cells[k.index()].get_or_try_init(|| f(k)).await
+    }
+
+    /// An iterator visiting the initialized keys and their values, in order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (K, &V)> + '_ {
+        K::enumerate_all(..)
+            .zip(self.cells.iter())
+            .filter_map(|(k, cell)| Some((k, cell.get()?)))
+    }
+
+    /// Converts into an [`EnumMap`] of the initialized values.
+    pub fn into_map(self) -> EnumMap<K, V> {
+        let mut map = EnumMap::new();
+        for (k, cell) in K::enumerate_all(..).zip(self.cells.into_vec()) {
+            if let Some(value) = cell.into_inner() {
+                map.insert(k, value);
+            }
+        }
+        map
+    }
+}
+
+impl<K: Enum, V> Default for LazyEnumMap<K, V> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Enum + Debug, V: Debug> Debug for LazyEnumMap<K, V> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
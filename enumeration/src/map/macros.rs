@@ -0,0 +1,102 @@
+/// Declares a struct with one named field per key of an [`EnumMap`](crate::EnumMap), along with
+/// conversions to and from the map.
+///
+/// Each field holds an `Option` of the map's value type, and is `None` when the map has no
+/// entry for the key. Attributes on the struct and its fields are passed through, so the struct
+/// can derive `Serialize` and `Deserialize` (with `#[serde(flatten)]` or
+/// `#[serde(skip_serializing_if = "Option::is_none")]` as needed) while the rest of the code
+/// works with the dense map. Every variant of the key type must be given a field.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::{Enum, EnumMap, enum_struct};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Season { Winter, Spring, Summer, Fall }
+///
+/// enum_struct! {
+///     #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+///     pub struct Rainfall: EnumMap<Season, u32> {
+///         pub winter: Winter,
+///         pub spring: Spring,
+///         #[serde(skip_serializing_if = "Option::is_none")]
+///         pub summer: Summer,
+///         pub fall: Fall,
+///     }
+/// }
+///
+/// let map = EnumMap::from([(Season::Spring, 13), (Season::Fall, 9)]);
+/// let rainfall = Rainfall::from(map.clone());
+/// assert_eq!(rainfall.spring, Some(13));
+/// assert_eq!(
+///     serde_json::to_string(&rainfall).unwrap(),
+///     r#"{"winter":null,"spring":13,"fall":9}"#
+/// );
+/// assert_eq!(EnumMap::from(rainfall), map);
+/// ```
+///
+/// Leaving out a variant is a compile error:
+///
+/// ```compile_fail
+/// use enumeration::{Enum, EnumMap, enum_struct};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Season { Winter, Spring, Summer, Fall }
+///
+/// enum_struct! {
+///     pub struct Rainfall: EnumMap<Season, u32> {
+///         pub winter: Winter,
+///         pub spring: Spring,
+///         pub summer: Summer,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! enum_struct {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident: EnumMap<$k:ty, $v:ty> {
+            $(
+                $(#[$field_meta:meta])*
+                $field_vis:vis $field:ident: $variant:ident
+            ),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $(
+                $(#[$field_meta])*
+                $field_vis $field: ::core::option::Option<$v>,
+            )+
+        }
+
+        // Fails to compile if a variant has no field.
+        const _: fn($k) = |key| match key {
+            $(<$k>::$variant => {})+
+        };
+
+        impl ::core::convert::From<$crate::EnumMap<$k, $v>> for $name {
+            #[inline]
+            fn from(mut map: $crate::EnumMap<$k, $v>) -> Self {
+                Self {
+                    $($field: map.remove(<$k>::$variant),)+
+                }
+            }
+        }
+
+        impl ::core::convert::From<$name> for $crate::EnumMap<$k, $v> {
+            #[inline]
+            fn from(value: $name) -> Self {
+                let mut map = Self::new();
+                $(
+                    if let ::core::option::Option::Some(v) = value.$field {
+                        map.insert(<$k>::$variant, v);
+                    }
+                )+
+                map
+            }
+        }
+    };
+}
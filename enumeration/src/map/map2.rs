@@ -0,0 +1,304 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::marker::PhantomData;
+use core::ops::Index;
+
+use crate::enumerate::Enum;
+
+fn key_at<K: Enum>(i: usize) -> K {
+    K::from_index(i).expect("got None from calling Enum::from_index() where < Enum::SIZE")
+}
+
+fn pair_at<K: Enum, L: Enum>(i: usize) -> (K, L) {
+    (key_at(i / L::SIZE), key_at(i % L::SIZE))
+}
+
+/// A map keyed by pairs of enum values, stored in a single dense buffer.
+///
+/// An `EnumMap2<K, L, V>` behaves like an `EnumMap<K, EnumMap<L, V>>`, but allocates one buffer
+/// of `K::SIZE * L::SIZE` slots on its first insertion, rather than one buffer per outer key.
+/// Use [`row`](Self::row) to view the entries for a single outer key.
+///
+/// # Examples
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use enumeration::EnumMap2;
+///
+/// let mut wins = EnumMap2::new();
+/// wins.insert(Ordering::Less, false, 3);
+/// wins.insert(Ordering::Less, true, 5);
+/// wins.insert(Ordering::Greater, true, 1);
+///
+/// assert_eq!(wins.get(Ordering::Less, true), Some(&5));
+/// assert_eq!(wins.get(Ordering::Equal, true), None);
+/// assert_eq!(wins.row(Ordering::Less).values().sum::<i32>(), 8);
+/// assert_eq!(wins.len(), 3);
+/// ```
+pub struct EnumMap2<K: Enum, L: Enum, V> {
+    slots: Vec<Option<V>>,
+    len: usize,
+    marker: PhantomData<fn() -> (K, L)>,
+}
+
+impl<K: Enum, L: Enum, V> EnumMap2<K, L, V> {
+    /// Creates an empty map. It does not allocate until it is first inserted into.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            len: 0,
+            marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn position(k: K, l: L) -> usize {
+        k.index() * L::SIZE + l.index()
+    }
+
+    /// Returns the number of elements in the map.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the map contains no elements.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns a reference to the value for a pair of keys.
+    #[inline]
+    pub fn get(&self, k: K, l: L) -> Option<&V> {
+        self.slots.get(Self::position(k, l))?.as_ref()
+    }
+
+    /// Returns a mutable reference to the value for a pair of keys.
+    #[inline]
+    pub fn get_mut(&mut self, k: K, l: L) -> Option<&mut V> {
+        self.slots.get_mut(Self::position(k, l))?.as_mut()
+    }
+
+    /// Returns `true` if the map contains a value for a pair of keys.
+    #[inline]
+    pub fn contains_key(&self, k: K, l: L) -> bool {
+        self.get(k, l).is_some()
+    }
+
+    /// Inserts a value for a pair of keys, returning the previous value if there was one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap2;
+    ///
+    /// let mut map = EnumMap2::new();
+    /// assert_eq!(map.insert(Ordering::Equal, false, "a"), None);
+    /// assert_eq!(map.insert(Ordering::Equal, false, "b"), Some("a"));
+    /// assert_eq!(map.get(Ordering::Equal, false), Some(&"b"));
+    /// ```
+    pub fn insert(&mut self, k: K, l: L, v: V) -> Option<V> {
+        if self.slots.is_empty() {
+            self.slots.resize_with(K::SIZE * L::SIZE, || None);
+        }
+        let old = self.slots[Self::position(k, l)].replace(v);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    /// Removes the value for a pair of keys, returning it if there was one.
+    #[inline]
+    pub fn remove(&mut self, k: K, l: L) -> Option<V> {
+        let old = self.slots.get_mut(Self::position(k, l))?.take();
+        if old.is_some() {
+            self.len -= 1;
+        }
+        old
+    }
+
+    /// Clears the map, removing all values. Keeps the allocated memory for reuse.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.slots.iter_mut().for_each(|slot| *slot = None);
+        self.len = 0;
+    }
+
+    /// Returns a view of the entries whose outer key is `k`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap2;
+    ///
+    /// let mut map = EnumMap2::new();
+    /// map.insert(Ordering::Less, true, 'x');
+    /// map.insert(Ordering::Greater, false, 'y');
+    ///
+    /// let row = map.row(Ordering::Less);
+    /// assert_eq!(row.get(true), Some(&'x'));
+    /// assert_eq!(row.iter().collect::<Vec<_>>(), [(true, &'x')]);
+    /// assert!(map.row(Ordering::Equal).is_empty());
+    /// ```
+    #[inline]
+    pub fn row(&self, k: K) -> Row<'_, L, V> {
+        let start = k.index() * L::SIZE;
+        Row {
+            slots: self.slots.get(start..start + L::SIZE).unwrap_or_default(),
+            marker: PhantomData,
+        }
+    }
+
+    /// An iterator visiting all key pairs and their values, ordered by outer key and then by
+    /// inner key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap2;
+    ///
+    /// let mut map = EnumMap2::new();
+    /// map.insert(Ordering::Greater, false, 1);
+    /// map.insert(Ordering::Less, true, 2);
+    /// assert_eq!(
+    ///     map.iter().collect::<Vec<_>>(),
+    ///     [((Ordering::Less, true), &2), ((Ordering::Greater, false), &1)]
+    /// );
+    /// ```
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = ((K, L), &V)> + '_ {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(i, slot)| Some((pair_at(i), slot.as_ref()?)))
+    }
+
+    /// An iterator visiting all key pairs and mutable references to their values, ordered by
+    /// outer key and then by inner key.
+    pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = ((K, L), &mut V)> + '_ {
+        self.slots
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(i, slot)| Some((pair_at(i), slot.as_mut()?)))
+    }
+}
+
+impl<K: Enum, L: Enum, V: Clone> Clone for EnumMap2<K, L, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            slots: self.slots.clone(),
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<K: Enum, L: Enum, V> Default for EnumMap2<K, L, V> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Enum, L: Enum, V: PartialEq> PartialEq for EnumMap2<K, L, V> {
+    /// Two maps are equal if they contain the same key pairs, each with equal values. Whether
+    /// either map has allocated does not matter.
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: Enum, L: Enum, V: Eq> Eq for EnumMap2<K, L, V> {}
+
+impl<K: Enum + Debug, L: Enum + Debug, V: Debug> Debug for EnumMap2<K, L, V> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Enum, L: Enum, V> Index<(K, L)> for EnumMap2<K, L, V> {
+    type Output = V;
+
+    /// Returns a reference to the value for a pair of keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pair is not present in the map.
+    #[inline]
+    fn index(&self, (k, l): (K, L)) -> &V {
+        self.get(k, l).expect("key not found")
+    }
+}
+
+impl<K: Enum, L: Enum, V> FromIterator<((K, L), V)> for EnumMap2<K, L, V> {
+    fn from_iter<I: IntoIterator<Item = ((K, L), V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for ((k, l), v) in iter {
+            map.insert(k, l, v);
+        }
+        map
+    }
+}
+
+/// A view of the entries of an [`EnumMap2`] that share an outer key.
+///
+/// This is created by [`EnumMap2::row`].
+pub struct Row<'a, L, V> {
+    slots: &'a [Option<V>],
+    marker: PhantomData<fn() -> L>,
+}
+
+impl<'a, L: Enum, V> Row<'a, L, V> {
+    /// Returns a reference to the value for an inner key.
+    #[inline]
+    pub fn get(&self, l: L) -> Option<&'a V> {
+        self.slots.get(l.index())?.as_ref()
+    }
+
+    /// Returns the number of entries in the row.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.slots.iter().filter(|slot| slot.is_some()).count()
+    }
+
+    /// Returns `true` if the row has no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.slots.iter().all(Option::is_none)
+    }
+
+    /// An iterator visiting the inner keys of the row and their values, in order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (L, &'a V)> + 'a {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(i, slot)| Some((key_at(i), slot.as_ref()?)))
+    }
+
+    /// An iterator visiting the values of the row, in order of inner key.
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &'a V> + 'a {
+        self.slots.iter().filter_map(Option::as_ref)
+    }
+}
+
+impl<L, V> Copy for Row<'_, L, V> {}
+
+#[allow(clippy::expl_impl_clone_on_copy)]
+impl<L, V> Clone for Row<'_, L, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<L: Enum + Debug, V: Debug> Debug for Row<'_, L, V> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -1,7 +1,46 @@
+#[macro_use]
+mod macros;
+
+mod arc;
+
+mod defaulted;
+pub use defaulted::DefaultedView;
+
 mod entry;
 pub use entry::{Entry, OccupiedEntry, VacantEntry};
 
+mod error;
+pub use error::{CycleError, NotAllocated};
+
 mod enum_map;
 pub use enum_map::EnumMap;
 
+mod graph;
+
 mod iter;
+pub use iter::{Chunk, Chunks, ExtractIf, Iter, Keys, Values};
+
+#[cfg(feature = "async")]
+mod lazy;
+#[cfg(feature = "async")]
+pub use lazy::LazyEnumMap;
+
+mod map2;
+pub use map2::{EnumMap2, Row};
+
+mod numeric;
+pub use numeric::{MissingKeyPolicy, SaturatingAdd};
+
+mod small;
+pub use small::SmallEnumMap;
+
+mod storage;
+pub use storage::{Slots, SlotsDrain, SlotsMut, SlotsOwned, SlotsRef};
+
+mod update;
+pub use update::Update;
+
+#[cfg(feature = "arc-swap")]
+mod shared;
+#[cfg(feature = "arc-swap")]
+pub use shared::SharedEnumMap;
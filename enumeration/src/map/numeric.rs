@@ -0,0 +1,236 @@
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+use super::enum_map::EnumMap;
+use crate::enumerate::Enum;
+use crate::set::EnumSet;
+
+/// Addition that clamps at the numeric bounds instead of overflowing, as used by
+/// [`EnumMap::saturating_add`].
+///
+/// This is implemented for the primitive integer types.
+pub trait SaturatingAdd: Sized {
+    /// Returns `self + rhs`, clamped to the bounds of the type.
+    #[must_use]
+    fn saturating_add(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_saturating_add {
+    ($($t:ty),*) => {
+        $(
+            impl SaturatingAdd for $t {
+                #[inline]
+                fn saturating_add(self, rhs: Self) -> Self {
+                    <$t>::saturating_add(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_saturating_add!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// How [`EnumMap::merge_arith`] treats a key that has a value in only one of the two maps.
+///
+/// # Examples
+///
+/// The elementwise operators on maps combine the values of keys present in both maps, as with
+/// `KeepLeft`:
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use enumeration::EnumMap;
+///
+/// let mut health = EnumMap::from([(Ordering::Less, 10), (Ordering::Equal, 20)]);
+/// let damage = EnumMap::from([(Ordering::Equal, 5), (Ordering::Greater, 1)]);
+/// health -= &damage;
+/// assert_eq!(health, EnumMap::from([(Ordering::Less, 10), (Ordering::Equal, 15)]));
+/// assert_eq!((health * &EnumMap::from([(Ordering::Less, 2)]))[Ordering::Less], 20);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MissingKeyPolicy {
+    /// Leave values in the left map as they are, and ignore values only in the right map. The
+    /// arithmetic operators use this policy.
+    #[default]
+    KeepLeft,
+    /// Remove keys that are not in both maps.
+    Intersect,
+    /// Treat a missing value on either side as `V::default()`, so the result has every key of
+    /// either map.
+    FillDefault,
+}
+
+/// Accumulator operations for counters and running totals.
+impl<K: Enum, V> EnumMap<K, V> {
+    /// Adds `delta` to the value for a key, starting from `V::default()` if the key is vacant.
+    /// Returns a reference to the updated value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut counts = EnumMap::new();
+    /// for x in [3, 1, 4, 1, 5] {
+    ///     counts.add(x.cmp(&2), 1);
+    /// }
+    /// assert_eq!(counts[Ordering::Less], 2);
+    /// assert_eq!(*counts.add(Ordering::Greater, 10), 13);
+    /// assert_eq!(counts.get(Ordering::Equal), None);
+    /// ```
+    #[inline]
+    pub fn add(&mut self, k: K, delta: V) -> &mut V
+    where
+        V: AddAssign + Default,
+    {
+        let value = self.entry(k).or_default();
+        *value += delta;
+        value
+    }
+
+    /// Like [`add`](Self::add), but clamps at the numeric bounds instead of overflowing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let mut counts = EnumMap::from([(Ordering::Less, 250u8)]);
+    /// assert_eq!(*counts.saturating_add(Ordering::Less, 10), u8::MAX);
+    /// assert_eq!(*counts.saturating_add(Ordering::Greater, 10), 10);
+    /// ```
+    #[inline]
+    pub fn saturating_add(&mut self, k: K, delta: V) -> &mut V
+    where
+        V: SaturatingAdd + Copy + Default,
+    {
+        let value = self.entry(k).or_default();
+        *value = value.saturating_add(delta);
+        value
+    }
+
+    /// Returns the sum of all values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    ///
+    /// let map = EnumMap::from([(Ordering::Less, 2.5), (Ordering::Greater, 4.0)]);
+    /// assert_eq!(map.sum_values(), 6.5);
+    /// ```
+    #[inline]
+    pub fn sum_values(&self) -> V
+    where
+        V: for<'a> Sum<&'a V>,
+    {
+        self.values().sum()
+    }
+
+    /// Combines the values of `other` into this map, one key at a time, calling `f` with the
+    /// value in this map and the value in `other`. `policy` decides what happens to keys that
+    /// have a value in only one of the maps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    /// use enumeration::map::MissingKeyPolicy;
+    ///
+    /// let mut stock = EnumMap::from([(Ordering::Less, 5), (Ordering::Equal, 2)]);
+    /// let delivery = EnumMap::from([(Ordering::Equal, 3), (Ordering::Greater, 4)]);
+    ///
+    /// let mut kept = stock.clone();
+    /// kept.merge_arith(&delivery, MissingKeyPolicy::KeepLeft, |a, b| *a += b);
+    /// assert_eq!(kept, EnumMap::from([(Ordering::Less, 5), (Ordering::Equal, 5)]));
+    ///
+    /// let mut shared = stock.clone();
+    /// shared.merge_arith(&delivery, MissingKeyPolicy::Intersect, |a, b| *a += b);
+    /// assert_eq!(shared, EnumMap::from([(Ordering::Equal, 5)]));
+    ///
+    /// stock.merge_arith(&delivery, MissingKeyPolicy::FillDefault, |a, b| *a += b);
+    /// assert_eq!(
+    ///     stock,
+    ///     EnumMap::from([(Ordering::Less, 5), (Ordering::Equal, 5), (Ordering::Greater, 4)])
+    /// );
+    /// ```
+    pub fn merge_arith<F>(&mut self, other: &Self, policy: MissingKeyPolicy, mut f: F)
+    where
+        V: Default,
+        F: FnMut(&mut V, &V),
+    {
+        match policy {
+            MissingKeyPolicy::KeepLeft => self.merge_present(other, f),
+            MissingKeyPolicy::Intersect => {
+                self.retain(|k, _| other.contains_key(k));
+                self.merge_present(other, f);
+            }
+            MissingKeyPolicy::FillDefault => {
+                let fill = V::default();
+                for k in EnumSet::from(&*self) | EnumSet::from(other) {
+                    let value = self.entry(k).or_default();
+                    f(value, other.get(k).unwrap_or(&fill));
+                }
+            }
+        }
+    }
+
+    /// Calls `f` for every key that has a value in both maps.
+    fn merge_present<F: FnMut(&mut V, &V)>(&mut self, other: &Self, mut f: F) {
+        for (k, value) in self.iter_mut() {
+            if let Some(rhs) = other.get(k) {
+                f(value, rhs);
+            }
+        }
+    }
+}
+
+macro_rules! impl_elementwise_op {
+    ($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident, $doc:literal) => {
+        impl<K: Enum, V: $OpAssign + Clone> $OpAssign<&EnumMap<K, V>> for EnumMap<K, V> {
+            #[doc = $doc]
+            ///
+            /// Keys that have a value in only one of the maps are left as they are.
+            #[inline]
+            fn $op_assign(&mut self, rhs: &EnumMap<K, V>) {
+                self.merge_present(rhs, |a, b| a.$op_assign(b.clone()));
+            }
+        }
+
+        impl<K: Enum, V: $OpAssign + Clone> $Op<&EnumMap<K, V>> for EnumMap<K, V> {
+            type Output = Self;
+
+            #[inline]
+            fn $op(mut self, rhs: &EnumMap<K, V>) -> Self {
+                self.$op_assign(rhs);
+                self
+            }
+        }
+    };
+}
+
+impl_elementwise_op!(
+    Add,
+    add,
+    AddAssign,
+    add_assign,
+    "Adds each value of `rhs` to the value for the same key."
+);
+impl_elementwise_op!(
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign,
+    "Subtracts each value of `rhs` from the value for the same key."
+);
+impl_elementwise_op!(
+    Mul,
+    mul,
+    MulAssign,
+    mul_assign,
+    "Multiplies the value for each key by the value of `rhs` for the same key."
+);
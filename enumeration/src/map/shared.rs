@@ -0,0 +1,140 @@
+use core::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use arc_swap::{ArcSwap, Guard};
+
+use super::enum_map::EnumMap;
+use crate::enumerate::Enum;
+
+/// An [`EnumMap`] that can be read from many threads without locking and is replaced as a
+/// whole when it changes.
+///
+/// Readers get a snapshot of the map with [`load`](Self::load), which never blocks. Writers
+/// clone the current map, modify the clone, and publish it with [`update`](Self::update), so
+/// this suits read-mostly tables such as configuration that changes rarely.
+///
+/// # Examples
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use std::sync::Arc;
+/// use std::thread;
+/// use enumeration::EnumMap;
+/// use enumeration::map::SharedEnumMap;
+///
+/// let limits = Arc::new(SharedEnumMap::new(EnumMap::from([(Ordering::Less, 10)])));
+///
+/// let reader = Arc::clone(&limits);
+/// thread::spawn(move || {
+///     let snapshot = reader.load();
+///     assert!(snapshot.contains_key(Ordering::Less));
+/// })
+/// .join()
+/// .unwrap();
+///
+/// limits.update(|map| {
+///     map.insert(Ordering::Greater, 20);
+/// });
+/// assert_eq!(limits.load().len(), 2);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "arc-swap")))]
+pub struct SharedEnumMap<K: Enum, V> {
+    inner: ArcSwap<EnumMap<K, V>>,
+}
+
+impl<K: Enum, V> SharedEnumMap<K, V> {
+    /// Creates a shared map holding `map`.
+    #[inline]
+    pub fn new(map: EnumMap<K, V>) -> Self {
+        Self {
+            inner: ArcSwap::from_pointee(map),
+        }
+    }
+
+    /// Returns a snapshot of the current map.
+    ///
+    /// The snapshot is unaffected by later updates. It is meant to be held briefly; use
+    /// [`load_full`](Self::load_full) to keep a snapshot for longer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    /// use enumeration::map::SharedEnumMap;
+    ///
+    /// let shared = SharedEnumMap::new(EnumMap::from([(Ordering::Less, 1)]));
+    /// let before = shared.load();
+    /// shared.store(EnumMap::new());
+    /// assert_eq!(before.get(Ordering::Less), Some(&1));
+    /// assert!(shared.load().is_empty());
+    /// ```
+    #[inline]
+    pub fn load(&self) -> Guard<Arc<EnumMap<K, V>>> {
+        self.inner.load()
+    }
+
+    /// Returns a snapshot of the current map that can be kept indefinitely.
+    #[inline]
+    pub fn load_full(&self) -> Arc<EnumMap<K, V>> {
+        self.inner.load_full()
+    }
+
+    /// Replaces the map, returning the previous one.
+    #[inline]
+    pub fn store(&self, map: EnumMap<K, V>) -> Arc<EnumMap<K, V>> {
+        self.inner.swap(Arc::new(map))
+    }
+
+    /// Replaces the map with a modified copy of it.
+    ///
+    /// `f` is called on a clone of the current map. If another thread publishes a map in the
+    /// meantime, `f` is called again on a clone of that map, so it should be free of side
+    /// effects.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMap;
+    /// use enumeration::map::SharedEnumMap;
+    ///
+    /// let shared = SharedEnumMap::new(EnumMap::from([(Ordering::Less, 1)]));
+    /// shared.update(|map| {
+    ///     map.remove(Ordering::Less);
+    ///     map.insert(Ordering::Equal, 2);
+    /// });
+    /// assert_eq!(*shared.load_full(), EnumMap::from([(Ordering::Equal, 2)]));
+    /// ```
+    pub fn update<F>(&self, mut f: F)
+    where
+        V: Clone,
+        F: FnMut(&mut EnumMap<K, V>),
+    {
+        self.inner.rcu(|current| {
+            let mut map = EnumMap::clone(current);
+            f(&mut map);
+            map
+        });
+    }
+}
+
+impl<K: Enum, V> Default for SharedEnumMap<K, V> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(EnumMap::new())
+    }
+}
+
+impl<K: Enum, V> From<EnumMap<K, V>> for SharedEnumMap<K, V> {
+    #[inline]
+    fn from(value: EnumMap<K, V>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<K: Enum + Debug, V: Debug> Debug for SharedEnumMap<K, V> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.load().fmt(f)
+    }
+}
@@ -0,0 +1,449 @@
+use core::fmt::{self, Debug, Formatter};
+use core::iter::Iterator;
+use core::mem;
+use core::slice;
+
+use super::enum_map::EnumMap;
+use super::iter::Iter;
+use super::storage::SlotsRef;
+use crate::enumerate::Enum;
+
+/// A lookup map using enumerated types as keys, which stores up to `N` entries inline before
+/// switching to the dense storage of [`EnumMap`].
+///
+/// Maps over large enums allocate storage for every key as soon as they are first inserted into.
+/// When most maps only ever hold a handful of entries, `SmallEnumMap` avoids that allocation
+/// entirely: entries are kept in a small array sorted by key, and only spill into an `EnumMap`
+/// once an `N + 1`th key is inserted. A map that has spilled stays dense.
+///
+/// Lookups on an inline map take O(log N) time.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::{Enum, SmallEnumMap};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Season { Winter, Spring, Summer, Fall }
+///
+/// let mut rainfall: SmallEnumMap<Season, u32, 2> = SmallEnumMap::new();
+/// rainfall.insert(Season::Winter, 11);
+/// rainfall.insert(Season::Summer, 3);
+/// assert!(rainfall.is_inline());
+///
+/// rainfall.insert(Season::Spring, 13);
+/// assert!(!rainfall.is_inline());
+/// assert_eq!(rainfall.get(Season::Winter), Some(&11));
+/// ```
+pub struct SmallEnumMap<K: Enum, V, const N: usize> {
+    repr: Repr<K, V, N>,
+}
+
+enum Repr<K: Enum, V, const N: usize> {
+    Inline {
+        entries: [Option<(K, V)>; N],
+        len: usize,
+    },
+    Dense(EnumMap<K, V>),
+}
+
+impl<K: Enum, V, const N: usize> Default for SmallEnumMap<K, V, N> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Enum, V, const N: usize> SmallEnumMap<K, V, N> {
+    /// Creates an empty `SmallEnumMap`. It does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::SmallEnumMap;
+    /// let mut map: SmallEnumMap<Ordering, i32, 1> = SmallEnumMap::new();
+    /// ```
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            repr: Repr::Inline {
+                entries: [const { None }; N],
+                len: 0,
+            },
+        }
+    }
+
+    /// Returns `true` if the entries are still stored inline.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::SmallEnumMap;
+    ///
+    /// let mut map: SmallEnumMap<Ordering, i32, 1> = SmallEnumMap::new();
+    /// map.insert(Ordering::Less, 1);
+    /// assert!(map.is_inline());
+    /// map.insert(Ordering::Greater, 2);
+    /// assert!(!map.is_inline());
+    /// ```
+    #[inline]
+    pub const fn is_inline(&self) -> bool {
+        matches!(self.repr, Repr::Inline { .. })
+    }
+
+    /// Returns the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::SmallEnumMap;
+    ///
+    /// let mut a: SmallEnumMap<Ordering, &str, 2> = SmallEnumMap::new();
+    /// assert_eq!(a.len(), 0);
+    /// a.insert(Ordering::Less, "a");
+    /// assert_eq!(a.len(), 1);
+    /// ```
+    #[inline]
+    pub const fn len(&self) -> usize {
+        match &self.repr {
+            Repr::Inline { len, .. } => *len,
+            Repr::Dense(map) => map.len(),
+        }
+    }
+
+    /// Returns `true` if the map contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::SmallEnumMap;
+    ///
+    /// let mut a: SmallEnumMap<Ordering, &str, 2> = SmallEnumMap::new();
+    /// assert!(a.is_empty());
+    /// a.insert(Ordering::Less, "a");
+    /// assert!(!a.is_empty());
+    /// ```
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the approximate number of bytes used by the map, including its heap allocation.
+    /// Memory owned by the values themselves is not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::SmallEnumMap;
+    ///
+    /// let mut map: SmallEnumMap<Ordering, u64, 1> = SmallEnumMap::new();
+    /// map.insert(Ordering::Less, 1);
+    /// assert_eq!(map.memory_usage(), std::mem::size_of_val(&map));
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn memory_usage(&self) -> usize {
+        match &self.repr {
+            Repr::Inline { .. } => mem::size_of::<Self>(),
+            Repr::Dense(map) => {
+                mem::size_of::<Self>() - mem::size_of::<EnumMap<K, V>>() + map.memory_usage()
+            }
+        }
+    }
+
+    #[inline]
+    fn position(entries: &[Option<(K, V)>], k: K) -> Result<usize, usize> {
+        entries.binary_search_by_key(&k.index(), |entry| match entry {
+            Some((key, _)) => key.index(),
+            None => unreachable!("vacant inline entry"),
+        })
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::SmallEnumMap;
+    ///
+    /// let mut map: SmallEnumMap<Ordering, &str, 2> = SmallEnumMap::new();
+    /// map.insert(Ordering::Less, "a");
+    /// assert_eq!(map.get(Ordering::Less), Some(&"a"));
+    /// assert_eq!(map.get(Ordering::Equal), None);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get(&self, k: K) -> Option<&V> {
+        match &self.repr {
+            Repr::Inline { entries, len } => match Self::position(&entries[..*len], k) {
+                Ok(i) => entries[i].as_ref().map(|(_, v)| v),
+                Err(_) => None,
+            },
+            Repr::Dense(map) => map.get(k),
+        }
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::SmallEnumMap;
+    ///
+    /// let mut map: SmallEnumMap<Ordering, &str, 2> = SmallEnumMap::new();
+    /// map.insert(Ordering::Less, "a");
+    /// if let Some(x) = map.get_mut(Ordering::Less) {
+    ///     *x = "b";
+    /// }
+    /// assert_eq!(map.get(Ordering::Less), Some(&"b"));
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn get_mut(&mut self, k: K) -> Option<&mut V> {
+        match &mut self.repr {
+            Repr::Inline { entries, len } => match Self::position(&entries[..*len], k) {
+                Ok(i) => entries[i].as_mut().map(|(_, v)| v),
+                Err(_) => None,
+            },
+            Repr::Dense(map) => map.get_mut(k),
+        }
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::SmallEnumMap;
+    ///
+    /// let mut map: SmallEnumMap<Ordering, &str, 2> = SmallEnumMap::new();
+    /// map.insert(Ordering::Less, "a");
+    /// assert_eq!(map.contains_key(Ordering::Less), true);
+    /// assert_eq!(map.contains_key(Ordering::Equal), false);
+    /// ```
+    #[inline]
+    pub fn contains_key(&self, k: K) -> bool {
+        self.get(k).is_some()
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have this key present, [`None`] is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old
+    /// value is returned.
+    ///
+    /// If the map is inline and already holds `N` entries, inserting a new key moves all
+    /// entries into dense storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::SmallEnumMap;
+    ///
+    /// let mut map: SmallEnumMap<Ordering, &str, 2> = SmallEnumMap::new();
+    /// assert_eq!(map.insert(Ordering::Less, "a"), None);
+    /// assert_eq!(map.insert(Ordering::Less, "b"), Some("a"));
+    /// assert_eq!(map.get(Ordering::Less), Some(&"b"));
+    /// ```
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
+        match &mut self.repr {
+            Repr::Inline { entries, len } => match Self::position(&entries[..*len], k) {
+                Ok(i) => entries[i].replace((k, v)).map(|(_, old)| old),
+                Err(i) if *len < N => {
+                    entries[*len] = Some((k, v));
+                    entries[i..=*len].rotate_right(1);
+                    *len += 1;
+                    None
+                }
+                Err(_) => {
+                    let mut map: EnumMap<K, V> =
+                        entries.iter_mut().filter_map(Option::take).collect();
+                    map.insert(k, v);
+                    self.repr = Repr::Dense(map);
+                    None
+                }
+            },
+            Repr::Dense(map) => map.insert(k, v),
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::SmallEnumMap;
+    ///
+    /// let mut map: SmallEnumMap<Ordering, &str, 2> = SmallEnumMap::new();
+    /// map.insert(Ordering::Less, "a");
+    /// assert_eq!(map.remove(Ordering::Less), Some("a"));
+    /// assert_eq!(map.remove(Ordering::Less), None);
+    /// ```
+    pub fn remove(&mut self, k: K) -> Option<V> {
+        match &mut self.repr {
+            Repr::Inline { entries, len } => {
+                let i = Self::position(&entries[..*len], k).ok()?;
+                let (_, old) = entries[i].take()?;
+                entries[i..*len].rotate_left(1);
+                *len -= 1;
+                Some(old)
+            }
+            Repr::Dense(map) => map.remove(k),
+        }
+    }
+
+    /// An iterator visiting all key-value pairs in key order.
+    /// The iterator element type is `(K, &'a V)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::SmallEnumMap;
+    ///
+    /// let mut map: SmallEnumMap<Ordering, i32, 2> = SmallEnumMap::new();
+    /// map.insert(Ordering::Greater, 5);
+    /// map.insert(Ordering::Less, 1);
+    ///
+    /// let pairs: Vec<_> = map.iter().collect();
+    /// assert_eq!(pairs, [(Ordering::Less, &1), (Ordering::Greater, &5)]);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn iter(&self) -> impl '_ + Iterator<Item = (K, &V)> {
+        match &self.repr {
+            Repr::Inline { entries, len } => SmallIter::Inline(entries[..*len].iter()),
+            Repr::Dense(map) => SmallIter::Dense(map.iter()),
+        }
+    }
+}
+
+enum SmallIter<'a, K: Enum, V> {
+    Inline(slice::Iter<'a, Option<(K, V)>>),
+    Dense(Iter<K, &'a V, SlotsRef<'a, V>>),
+}
+
+impl<'a, K: Enum, V> Iterator for SmallIter<'a, K, V> {
+    type Item = (K, &'a V);
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Inline(iter) => iter.next().and_then(|entry| match entry {
+                Some((k, v)) => Some((*k, v)),
+                None => None,
+            }),
+            Self::Dense(iter) => iter.next(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Inline(iter) => iter.size_hint(),
+            Self::Dense(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl<K: Enum, V, const N: usize> Clone for SmallEnumMap<K, V, N>
+where
+    K: Clone,
+    V: Clone,
+{
+    fn clone(&self) -> Self {
+        let repr = match &self.repr {
+            Repr::Inline { entries, len } => Repr::Inline {
+                entries: entries.clone(),
+                len: *len,
+            },
+            Repr::Dense(map) => Repr::Dense(map.clone()),
+        };
+        Self { repr }
+    }
+}
+
+impl<K: Enum, V: PartialEq, const N: usize> PartialEq for SmallEnumMap<K, V, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: Enum, V: Eq, const N: usize> Eq for SmallEnumMap<K, V, N> {}
+
+impl<K: Enum + Debug, V: Debug, const N: usize> Debug for SmallEnumMap<K, V, N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Enum, V, const N: usize> From<SmallEnumMap<K, V, N>> for EnumMap<K, V> {
+    fn from(value: SmallEnumMap<K, V, N>) -> Self {
+        match value.repr {
+            Repr::Inline { entries, .. } => entries.into_iter().flatten().collect(),
+            Repr::Dense(map) => map,
+        }
+    }
+}
+
+impl<K: Enum, V, const N: usize> FromIterator<(K, V)> for SmallEnumMap<K, V, N> {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut map = Self::new();
+        for (k, v) in iter {
+            map.insert(k, v);
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rustfmt::skip] #[allow(dead_code)]
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Enum)]
+    enum DemoEnum { A, B, C, D, E, F, G, H, I, J }
+
+    #[test]
+    fn test_matches_dense() {
+        let mut small: SmallEnumMap<DemoEnum, usize, 3> = SmallEnumMap::new();
+        let mut dense = EnumMap::new();
+        for (i, k) in [
+            DemoEnum::H,
+            DemoEnum::B,
+            DemoEnum::E,
+            DemoEnum::B,
+            DemoEnum::A,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            assert_eq!(small.insert(k, i), dense.insert(k, i));
+            assert!(small.iter().eq(dense.iter()));
+        }
+        assert!(!small.is_inline());
+        assert_eq!(EnumMap::from(small), dense);
+    }
+
+    #[test]
+    fn test_remove_inline() {
+        let mut small: SmallEnumMap<DemoEnum, usize, 3> =
+            [(DemoEnum::C, 0), (DemoEnum::A, 1), (DemoEnum::J, 2)]
+                .into_iter()
+                .collect();
+        assert_eq!(small.remove(DemoEnum::A), Some(1));
+        assert_eq!(small.remove(DemoEnum::A), None);
+        small.insert(DemoEnum::B, 3);
+        assert!(small.is_inline());
+        let keys: Vec<_> = small.iter().map(|(k, _)| k).collect();
+        assert_eq!(keys, [DemoEnum::B, DemoEnum::C, DemoEnum::J]);
+    }
+}
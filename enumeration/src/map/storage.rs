@@ -0,0 +1,844 @@
+//! Backing store for [`EnumMap`](super::EnumMap).
+//!
+//! Values live in a buffer with one [`Slot`] per key. By default a slot is an `Option<V>`. With
+//! the `uninit-storage` feature it is a `MaybeUninit<V>` instead, so a map pays no discriminant
+//! per slot, at the cost of a manual `Drop` impl and the stricter drop check that comes with it.
+//!
+//! Either way, which slots are occupied is tracked by a bitvector indexed by slot position.
+//! That bitvector is the only thing unsafe code in this module trusts: [`Enum`] is a safe trait,
+//! so a faulty implementation may cause logic errors but never undefined behavior.
+
+use alloc::vec::Vec;
+use core::convert::Infallible;
+use core::marker::PhantomData;
+use core::mem;
+#[cfg(feature = "uninit-storage")]
+use core::mem::MaybeUninit;
+use core::ops::ControlFlow;
+#[cfg(feature = "uninit-storage")]
+use core::slice;
+
+use crate::enumerate::Enum;
+use crate::set::EnumSet;
+
+pub(super) const WORD_BITS: usize = usize::BITS as usize;
+
+#[inline]
+fn word_count(slots: usize) -> usize {
+    slots.div_ceil(WORD_BITS)
+}
+
+#[inline]
+fn test_bit(words: &[usize], i: usize) -> bool {
+    words
+        .get(i / WORD_BITS)
+        .is_some_and(|word| word & (1 << (i % WORD_BITS)) != 0)
+}
+
+/// Returns word `w` of `words`, keeping only the marks for positions in `from..to`.
+#[inline]
+fn marks_within(words: &[usize], w: usize, from: usize, to: usize) -> usize {
+    let start = w * WORD_BITS;
+    match words.get(w) {
+        Some(&word) if from < start + WORD_BITS && to > start => {
+            let low = !0 << from.saturating_sub(start);
+            let high = if to - start < WORD_BITS {
+                !(!0 << (to - start))
+            } else {
+                !0
+            };
+            word & low & high
+        }
+        _ => 0,
+    }
+}
+
+/// Returns the first position in `from..to` that is marked in `words`.
+#[inline]
+fn next_marked(words: &[usize], from: usize, to: usize) -> Option<usize> {
+    if from >= to {
+        return None;
+    }
+    let mut w = from / WORD_BITS;
+    let mut bits = *words.get(w)? & (!0 << (from % WORD_BITS));
+    while bits == 0 {
+        w += 1;
+        if w * WORD_BITS >= to {
+            return None;
+        }
+        bits = *words.get(w)?;
+    }
+    let i = w * WORD_BITS + bits.trailing_zeros() as usize;
+    (i < to).then_some(i)
+}
+
+/// Returns the last position in `from..to` that is marked in `words`.
+#[inline]
+fn prev_marked(words: &[usize], from: usize, to: usize) -> Option<usize> {
+    if from >= to || words.is_empty() {
+        return None;
+    }
+    let last = to - 1;
+    let (mut w, mut bits) = match words.get(last / WORD_BITS) {
+        Some(&word) => (
+            last / WORD_BITS,
+            word & (!0 >> (WORD_BITS - 1 - last % WORD_BITS)),
+        ),
+        None => (words.len() - 1, words[words.len() - 1]),
+    };
+    while bits == 0 {
+        if w == 0 || w * WORD_BITS <= from {
+            return None;
+        }
+        w -= 1;
+        bits = words[w];
+    }
+    let i = w * WORD_BITS + (WORD_BITS - 1 - bits.leading_zeros() as usize);
+    (i >= from).then_some(i)
+}
+
+#[inline]
+fn set_bit(words: &mut [usize], i: usize) {
+    words[i / WORD_BITS] |= 1 << (i % WORD_BITS);
+}
+
+#[inline]
+fn clear_bit(words: &mut [usize], i: usize) {
+    if let Some(word) = words.get_mut(i / WORD_BITS) {
+        *word &= !(1 << (i % WORD_BITS));
+    }
+}
+
+/// A slot of the value buffer.
+#[cfg(not(feature = "uninit-storage"))]
+type Slot<V> = Option<V>;
+/// A slot of the value buffer.
+#[cfg(feature = "uninit-storage")]
+type Slot<V> = MaybeUninit<V>;
+
+/// Operations on a [`Slot`]. Whether a slot holds a value is decided by the `init` bitvector,
+/// never by the slot itself.
+trait SlotExt<V> {
+    fn vacant() -> Self;
+
+    /// Stores a value in a vacant slot, returning a reference to it.
+    fn occupy(&mut self, v: V) -> &mut V;
+
+    /// # Safety
+    ///
+    /// The slot must be occupied.
+    unsafe fn assume_ref(&self) -> &V;
+
+    /// # Safety
+    ///
+    /// The slot must be occupied.
+    unsafe fn assume_mut(&mut self) -> &mut V;
+
+    /// Moves the value out. The slot must be marked as vacant afterward.
+    ///
+    /// # Safety
+    ///
+    /// The slot must be occupied.
+    unsafe fn assume_read(&mut self) -> V;
+
+    /// Drops the value in place. The slot must be marked as vacant afterward.
+    ///
+    /// # Safety
+    ///
+    /// The slot must be occupied.
+    unsafe fn assume_drop(&mut self);
+}
+
+#[cfg(not(feature = "uninit-storage"))]
+impl<V> SlotExt<V> for Option<V> {
+    #[inline]
+    fn vacant() -> Self {
+        None
+    }
+
+    #[inline]
+    fn occupy(&mut self, v: V) -> &mut V {
+        self.insert(v)
+    }
+
+    #[inline]
+    unsafe fn assume_ref(&self) -> &V {
+        // SAFETY: an occupied slot is `Some`.
+        unsafe { self.as_ref().unwrap_unchecked() }
+    }
+
+    #[inline]
+    unsafe fn assume_mut(&mut self) -> &mut V {
+        // SAFETY: an occupied slot is `Some`.
+        unsafe { self.as_mut().unwrap_unchecked() }
+    }
+
+    #[inline]
+    unsafe fn assume_read(&mut self) -> V {
+        // SAFETY: an occupied slot is `Some`.
+        unsafe { self.take().unwrap_unchecked() }
+    }
+
+    #[inline]
+    unsafe fn assume_drop(&mut self) {
+        *self = None;
+    }
+}
+
+#[cfg(feature = "uninit-storage")]
+impl<V> SlotExt<V> for MaybeUninit<V> {
+    #[inline]
+    fn vacant() -> Self {
+        MaybeUninit::uninit()
+    }
+
+    #[inline]
+    fn occupy(&mut self, v: V) -> &mut V {
+        MaybeUninit::write(self, v)
+    }
+
+    #[inline]
+    unsafe fn assume_ref(&self) -> &V {
+        // SAFETY: an occupied slot is initialized.
+        unsafe { self.assume_init_ref() }
+    }
+
+    #[inline]
+    unsafe fn assume_mut(&mut self) -> &mut V {
+        // SAFETY: an occupied slot is initialized.
+        unsafe { self.assume_init_mut() }
+    }
+
+    #[inline]
+    unsafe fn assume_read(&mut self) -> V {
+        // SAFETY: an occupied slot is initialized, and the caller marks it as vacant.
+        unsafe { self.assume_init_read() }
+    }
+
+    #[inline]
+    unsafe fn assume_drop(&mut self) {
+        // SAFETY: an occupied slot is initialized, and the caller marks it as vacant.
+        unsafe { self.assume_init_drop() }
+    }
+}
+
+/// Drops every value of `values` marked in `init`, clearing the marks as it goes.
+///
+/// # Safety
+///
+/// `values` must be valid for `len` slots, and every slot marked in `init` must be occupied.
+unsafe fn drop_marked<V>(values: *mut Slot<V>, len: usize, init: &mut [usize]) {
+    if !mem::needs_drop::<V>() {
+        init.fill(0);
+        return;
+    }
+    for i in 0..len {
+        if test_bit(init, i) {
+            clear_bit(init, i);
+            // SAFETY: the slot is in bounds and marked as occupied.
+            unsafe { (*values.add(i)).assume_drop() };
+        }
+    }
+}
+
+const MISSING_STEP: &str = "got None from calling Enum::succ() or Enum::pred() within Enum::SIZE";
+
+/// Finds the keys of slots visited in order, from either end.
+///
+/// No key is looked up by position. A cursor starts at [`Enum::MIN`] or [`Enum::MAX`] and
+/// reaches every later key by stepping from the previous one with [`Enum::succ`] or
+/// [`Enum::pred`]. The default `from_index` searches the whole enumeration, so looking up keys
+/// by position would make a walk over the slots quadratic for implementations that do not
+/// override it. Stepping keeps a full walk to at most `K::SIZE` steps, however `Enum` is
+/// implemented, and for derived implementations the compiler folds a run of steps into a
+/// single addition.
+#[derive(Clone, Copy)]
+pub(super) struct KeyCursor<K> {
+    /// The last slot visited and its key.
+    last: (usize, K),
+}
+
+impl<K: Enum> KeyCursor<K> {
+    /// Returns a cursor for walking up from the first slot.
+    #[inline]
+    pub(super) const fn front() -> Self {
+        Self { last: (0, K::MIN) }
+    }
+
+    /// Returns a cursor for walking down from the last slot.
+    #[inline]
+    pub(super) const fn back() -> Self {
+        Self {
+            last: (K::SIZE.saturating_sub(1), K::MAX),
+        }
+    }
+
+    /// Returns the key stored in slot `i`.
+    #[inline]
+    pub(super) fn key_at(&mut self, i: usize) -> K {
+        let (last, mut key) = self.last;
+        if last <= i {
+            for _ in last..i {
+                key = key.succ().expect(MISSING_STEP);
+            }
+        } else {
+            for _ in i..last {
+                key = key.pred().expect(MISSING_STEP);
+            }
+        }
+        self.last = (i, key);
+        key
+    }
+
+    /// Folds over the slots marked in `bits`, word `w` of an `init` bitvector, in ascending
+    /// order, passing `f` each position along with its key.
+    ///
+    /// The marks are consumed a run at a time: [`trailing_zeros`](usize::trailing_zeros) finds
+    /// the start of the next run and [`trailing_ones`](usize::trailing_ones) its length, so a
+    /// run of occupied slots is walked in a tight loop with no bit tests.
+    #[inline]
+    pub(super) fn try_fold_word<B, C, F>(
+        &mut self,
+        w: usize,
+        mut bits: usize,
+        mut acc: C,
+        mut f: F,
+    ) -> ControlFlow<B, C>
+    where
+        F: FnMut(C, usize, K) -> ControlFlow<B, C>,
+    {
+        while bits != 0 {
+            let lo = bits.trailing_zeros();
+            let start = w * WORD_BITS + lo as usize;
+            let mut key = self.key_at(start);
+            if bits & (2 << lo) == 0 {
+                bits &= bits - 1;
+                acc = f(acc, start, key)?;
+                continue;
+            }
+            let end = start + (bits >> lo).trailing_ones() as usize;
+            // Clears the lowest run of marks.
+            bits &= bits.wrapping_add(bits & bits.wrapping_neg());
+            let mut i = start;
+            loop {
+                acc = f(acc, i, key)?;
+                if i + 1 == end {
+                    break;
+                }
+                i += 1;
+                key = key.succ().expect(MISSING_STEP);
+            }
+            self.last = (i, key);
+        }
+        ControlFlow::Continue(acc)
+    }
+
+    /// Like [`try_fold_word`](Self::try_fold_word), in descending order.
+    #[inline]
+    pub(super) fn try_rfold_word<B, C, F>(
+        &mut self,
+        w: usize,
+        mut bits: usize,
+        mut acc: C,
+        mut f: F,
+    ) -> ControlFlow<B, C>
+    where
+        F: FnMut(C, usize, K) -> ControlFlow<B, C>,
+    {
+        while bits != 0 {
+            let lz = bits.leading_zeros();
+            let hi = WORD_BITS - 1 - lz as usize;
+            let end = w * WORD_BITS + hi;
+            let mut key = self.key_at(end);
+            if hi == 0 || bits & (1 << (hi - 1)) == 0 {
+                bits &= !(1 << hi);
+                acc = f(acc, end, key)?;
+                continue;
+            }
+            let start = end + 1 - (bits << lz).leading_ones() as usize;
+            // Clears the highest run of marks, above which `bits` is already clear.
+            bits &= (1 << (start - w * WORD_BITS)) - 1;
+            let mut i = end;
+            loop {
+                acc = f(acc, i, key)?;
+                if i == start {
+                    break;
+                }
+                i -= 1;
+                key = key.pred().expect(MISSING_STEP);
+            }
+            self.last = (i, key);
+        }
+        ControlFlow::Continue(acc)
+    }
+}
+
+pub(super) struct Storage<K: Enum, V> {
+    values: Vec<Slot<V>>,
+    /// Bit `i` is set if and only if `values[i]` is occupied.
+    init: Vec<usize>,
+    /// The occupied keys, tracked by [`Enum::bit`] for constant-time comparisons.
+    keys: EnumSet<K>,
+    len: usize,
+}
+
+impl<K: Enum, V> Storage<K, V> {
+    #[inline]
+    pub(super) const fn new() -> Self {
+        Self {
+            values: Vec::new(),
+            init: Vec::new(),
+            keys: EnumSet::new(),
+            len: 0,
+        }
+    }
+
+    #[inline]
+    pub(super) const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub(super) const fn keys(&self) -> EnumSet<K> {
+        self.keys
+    }
+
+    #[inline]
+    pub(super) fn is_allocated(&self) -> bool {
+        !self.values.is_empty()
+    }
+
+    /// Number of bytes allocated on the heap.
+    #[inline]
+    pub(super) fn heap_size(&self) -> usize {
+        self.values.capacity() * mem::size_of::<V>()
+            + self.init.capacity() * mem::size_of::<usize>()
+    }
+
+    #[inline]
+    pub(super) fn allocate(&mut self) {
+        if self.values.is_empty() {
+            self.values.resize_with(K::SIZE, Slot::vacant);
+        }
+        let words = word_count(self.values.len());
+        if self.init.len() < words {
+            self.init.resize(words, 0);
+        }
+    }
+
+    #[inline]
+    pub(super) fn contains(&self, k: K) -> bool {
+        test_bit(&self.init, k.index())
+    }
+
+    #[inline]
+    pub(super) fn get(&self, k: K) -> Option<&V> {
+        let i = k.index();
+        if test_bit(&self.init, i) {
+            // SAFETY: the slot is marked as occupied.
+            Some(unsafe { self.values[i].assume_ref() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns every value as a slice in slot order, if every slot is occupied.
+    #[cfg(feature = "uninit-storage")]
+    #[inline]
+    pub(super) fn as_full_slice(&self) -> Option<&[V]> {
+        // `len` counts the set bits of `init`, each of which marks one of the slots. Comparing
+        // against `values` keeps this sound even if `K::SIZE` is wrong.
+        if self.len != K::SIZE || self.len != self.values.len() {
+            return None;
+        }
+        // SAFETY: every slot is marked as occupied, and `MaybeUninit<V>` has the same layout
+        // as `V`.
+        Some(unsafe { slice::from_raw_parts(self.values.as_ptr().cast::<V>(), self.len) })
+    }
+
+    #[inline]
+    pub(super) fn get_mut(&mut self, k: K) -> Option<&mut V> {
+        let i = k.index();
+        if test_bit(&self.init, i) {
+            // SAFETY: the slot is marked as occupied.
+            Some(unsafe { self.values[i].assume_mut() })
+        } else {
+            None
+        }
+    }
+
+    /// Inserts a value, returning a reference to it along with the value it replaced.
+    #[inline]
+    pub(super) fn insert(&mut self, k: K, v: V) -> (&mut V, Option<V>) {
+        self.allocate();
+        let i = k.index();
+        let slot = &mut self.values[i];
+        let old_val = if test_bit(&self.init, i) {
+            // SAFETY: the slot is marked as occupied, and is overwritten below.
+            Some(unsafe { slot.assume_read() })
+        } else {
+            set_bit(&mut self.init, i);
+            self.keys.insert(k);
+            self.len += 1;
+            None
+        };
+        (slot.occupy(v), old_val)
+    }
+
+    #[inline]
+    pub(super) fn remove(&mut self, k: K) -> Option<V> {
+        let i = k.index();
+        if !test_bit(&self.init, i) {
+            return None;
+        }
+        clear_bit(&mut self.init, i);
+        self.keys.remove(k);
+        self.len -= 1;
+        // SAFETY: the slot was marked as occupied, and is now marked as vacant.
+        Some(unsafe { self.values[i].assume_read() })
+    }
+
+    /// Replaces the value of an occupied slot with the result of `f`, or vacates it if `f`
+    /// returns `None`. Returns whether the slot is occupied afterward.
+    ///
+    /// The slot is never deallocated or looked up again, so this is cheaper than a remove
+    /// followed by an insert.
+    #[inline]
+    pub(super) fn replace_with<F: FnOnce(K, V) -> Option<V>>(&mut self, k: K, f: F) -> bool {
+        let i = k.index();
+        if !test_bit(&self.init, i) {
+            return false;
+        }
+        // Vacated while `f` runs, so a panic in `f` leaves the store consistent.
+        clear_bit(&mut self.init, i);
+        self.keys.remove(k);
+        self.len -= 1;
+        // SAFETY: the slot was marked as occupied, and is now marked as vacant.
+        let old_val = unsafe { self.values[i].assume_read() };
+        let Some(new_val) = f(k, old_val) else {
+            return false;
+        };
+        self.values[i].occupy(new_val);
+        set_bit(&mut self.init, i);
+        self.keys.insert(k);
+        self.len += 1;
+        true
+    }
+
+    /// Returns the first occupied position in `from..to`.
+    #[inline]
+    pub(super) fn next_occupied(&self, from: usize, to: usize) -> Option<usize> {
+        next_marked(&self.init, from, to)
+    }
+
+    /// Returns the last occupied position in `from..to`.
+    #[inline]
+    pub(super) fn prev_occupied(&self, from: usize, to: usize) -> Option<usize> {
+        prev_marked(&self.init, from, to)
+    }
+
+    /// Calls `f` on each occupied slot in order, stopping at the first `Break`.
+    ///
+    /// This walks the set bits of `init` directly, skipping vacant slots a word at a time.
+    #[inline]
+    pub(super) fn try_for_each<B, F>(&self, mut f: F) -> ControlFlow<B>
+    where
+        F: FnMut(K, &V) -> ControlFlow<B>,
+    {
+        let mut keys = KeyCursor::front();
+        for (w, &bits) in self.init.iter().enumerate() {
+            keys.try_fold_word(w, bits, (), |(), i, k| {
+                // SAFETY: the slot is marked as occupied.
+                f(k, unsafe { self.values.get_unchecked(i).assume_ref() })
+            })?;
+        }
+        ControlFlow::Continue(())
+    }
+
+    /// Like [`try_for_each`](Self::try_for_each), with mutable access to the values.
+    #[inline]
+    pub(super) fn try_for_each_mut<B, F>(&mut self, mut f: F) -> ControlFlow<B>
+    where
+        F: FnMut(K, &mut V) -> ControlFlow<B>,
+    {
+        let mut keys = KeyCursor::front();
+        for (w, &bits) in self.init.iter().enumerate() {
+            keys.try_fold_word(w, bits, (), |(), i, k| {
+                // SAFETY: the slot is marked as occupied.
+                f(k, unsafe { self.values.get_unchecked_mut(i).assume_mut() })
+            })?;
+        }
+        ControlFlow::Continue(())
+    }
+
+    /// Removes every value for which `f` returns `false`.
+    #[inline]
+    pub(super) fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(K, &mut V) -> bool,
+    {
+        let mut keys = KeyCursor::front();
+        for w in 0..self.init.len() {
+            let bits = self.init[w];
+            let _: ControlFlow<Infallible> = keys.try_fold_word(w, bits, (), |(), i, k| {
+                // SAFETY: marks are only ever set for positions within `values`.
+                let slot = unsafe { self.values.get_unchecked_mut(i) };
+                // SAFETY: the slot is marked as occupied.
+                if !f(k, unsafe { slot.assume_mut() }) {
+                    clear_bit(&mut self.init, i);
+                    self.keys.remove(k);
+                    self.len -= 1;
+                    // SAFETY: the slot was marked as occupied, and is now marked as vacant.
+                    drop(unsafe { slot.assume_read() });
+                }
+                ControlFlow::Continue(())
+            });
+        }
+    }
+
+    #[inline]
+    pub(super) fn clear(&mut self) {
+        self.keys = EnumSet::new();
+        self.len = 0;
+        // SAFETY: the marks describe exactly the occupied slots.
+        unsafe { drop_marked(self.values.as_mut_ptr(), self.values.len(), &mut self.init) };
+    }
+
+    #[inline]
+    pub(super) fn slots(&self) -> SlotsRef<'_, V> {
+        SlotsRef {
+            values: &self.values,
+            init: &self.init,
+        }
+    }
+
+    #[inline]
+    pub(super) fn slots_mut(&mut self) -> SlotsMut<'_, V> {
+        SlotsMut {
+            values: self.values.as_mut_ptr(),
+            len: self.values.len(),
+            init: &self.init,
+            marker: PhantomData,
+        }
+    }
+
+    /// Moves every value out of the store, leaving it empty but allocated.
+    ///
+    /// The store is emptied up front, so leaking the returned slots leaks the values instead of
+    /// dropping them twice.
+    #[inline]
+    pub(super) fn drain(&mut self) -> SlotsDrain<'_, V> {
+        self.keys = EnumSet::new();
+        self.len = 0;
+        let init = mem::take(&mut self.init);
+        SlotsDrain {
+            values: self.values.as_mut_ptr(),
+            len: self.values.len(),
+            init,
+            home: &mut self.init,
+            marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub(super) fn into_slots(mut self) -> SlotsOwned<V> {
+        self.keys = EnumSet::new();
+        self.len = 0;
+        SlotsOwned {
+            values: mem::take(&mut self.values),
+            init: mem::take(&mut self.init),
+        }
+    }
+}
+
+impl<K: Enum, V: Clone> Clone for Storage<K, V> {
+    fn clone(&self) -> Self {
+        let mut clone = Self::new();
+        if self.is_allocated() {
+            clone.allocate();
+        }
+        for i in 0..self.values.len() {
+            if test_bit(&self.init, i) {
+                // SAFETY: the slot is marked as occupied.
+                let val = unsafe { self.values[i].assume_ref() };
+                clone.values[i].occupy(val.clone());
+                // Marked only once written, so a panicking `clone` leaves `clone` consistent.
+                set_bit(&mut clone.init, i);
+                clone.len += 1;
+            }
+        }
+        clone.keys = self.keys;
+        clone
+    }
+}
+
+// Without `#[may_dangle]`, which is unstable, this impl makes the borrow checker treat the
+// values as used when the map is dropped; see "Drop check" on `EnumMap`. With `Option` slots,
+// the buffer drops its own values and no impl is needed.
+#[cfg(feature = "uninit-storage")]
+impl<K: Enum, V> Drop for Storage<K, V> {
+    fn drop(&mut self) {
+        // SAFETY: the marks describe exactly the occupied slots.
+        unsafe { drop_marked(self.values.as_mut_ptr(), self.values.len(), &mut self.init) };
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Access to the value slots of an [`EnumMap`](crate::EnumMap), used to parameterize its
+/// iterators.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait Slots<V>: private::Sealed {
+    /// Returns word `w` of the occupancy bitvector, keeping only positions in `from..to`.
+    #[doc(hidden)]
+    fn marks(&self, w: usize, from: usize, to: usize) -> usize;
+
+    /// # Safety
+    ///
+    /// `i` must be marked in `marks`, and `take(i)` must not have been called before.
+    #[doc(hidden)]
+    unsafe fn take(&mut self, i: usize) -> V;
+}
+
+/// Shared access to the value slots of an [`EnumMap`](crate::EnumMap).
+pub struct SlotsRef<'a, V> {
+    values: &'a [Slot<V>],
+    init: &'a [usize],
+}
+
+impl<V> Copy for SlotsRef<'_, V> {}
+
+#[allow(clippy::expl_impl_clone_on_copy)]
+impl<V> Clone for SlotsRef<'_, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, V> SlotsRef<'a, V> {
+    #[inline]
+    pub(super) fn get(&self, i: usize) -> Option<&'a V> {
+        if test_bit(self.init, i) {
+            // SAFETY: the slot is marked as occupied.
+            Some(unsafe { self.values[i].assume_ref() })
+        } else {
+            None
+        }
+    }
+}
+
+impl<V> private::Sealed for SlotsRef<'_, V> {}
+
+impl<'a, V> Slots<&'a V> for SlotsRef<'a, V> {
+    #[inline]
+    fn marks(&self, w: usize, from: usize, to: usize) -> usize {
+        marks_within(self.init, w, from, to)
+    }
+
+    #[inline]
+    unsafe fn take(&mut self, i: usize) -> &'a V {
+        // Marks are only ever set for positions within `values`.
+        self.values.get_unchecked(i).assume_ref()
+    }
+}
+
+/// Exclusive access to the value slots of an [`EnumMap`](crate::EnumMap).
+pub struct SlotsMut<'a, V> {
+    values: *mut Slot<V>,
+    len: usize,
+    init: &'a [usize],
+    marker: PhantomData<&'a mut [V]>,
+}
+
+// SAFETY: `SlotsMut` behaves like `&mut [V]`.
+unsafe impl<V: Send> Send for SlotsMut<'_, V> {}
+// SAFETY: `SlotsMut` behaves like `&mut [V]`.
+unsafe impl<V: Sync> Sync for SlotsMut<'_, V> {}
+
+impl<V> private::Sealed for SlotsMut<'_, V> {}
+
+impl<'a, V> Slots<&'a mut V> for SlotsMut<'a, V> {
+    #[inline]
+    fn marks(&self, w: usize, from: usize, to: usize) -> usize {
+        marks_within(self.init, w, from, to.min(self.len))
+    }
+
+    #[inline]
+    unsafe fn take(&mut self, i: usize) -> &'a mut V {
+        // Each slot is taken at most once, so the returned references never alias.
+        (*self.values.add(i)).assume_mut()
+    }
+}
+
+/// Owned value slots of an [`EnumMap`](crate::EnumMap). Values that are never taken are dropped
+/// along with the slots.
+pub struct SlotsOwned<V> {
+    values: Vec<Slot<V>>,
+    init: Vec<usize>,
+}
+
+impl<V> private::Sealed for SlotsOwned<V> {}
+
+impl<V> Slots<V> for SlotsOwned<V> {
+    #[inline]
+    fn marks(&self, w: usize, from: usize, to: usize) -> usize {
+        marks_within(&self.init, w, from, to)
+    }
+
+    #[inline]
+    unsafe fn take(&mut self, i: usize) -> V {
+        clear_bit(&mut self.init, i);
+        self.values[i].assume_read()
+    }
+}
+
+#[cfg(feature = "uninit-storage")]
+impl<V> Drop for SlotsOwned<V> {
+    fn drop(&mut self) {
+        // SAFETY: the marks describe exactly the occupied slots that have not been taken.
+        unsafe { drop_marked(self.values.as_mut_ptr(), self.values.len(), &mut self.init) };
+    }
+}
+
+/// Value slots moved out of an [`EnumMap`](crate::EnumMap) by [`drain`](crate::EnumMap::drain).
+/// Values that are never taken are dropped along with the slots.
+pub struct SlotsDrain<'a, V> {
+    values: *mut Slot<V>,
+    len: usize,
+    init: Vec<usize>,
+    home: &'a mut Vec<usize>,
+    marker: PhantomData<&'a mut [V]>,
+}
+
+// SAFETY: `SlotsDrain` behaves like `&mut [V]` that moves values out.
+unsafe impl<V: Send> Send for SlotsDrain<'_, V> {}
+// SAFETY: `SlotsDrain` behaves like `&mut [V]` that moves values out.
+unsafe impl<V: Sync> Sync for SlotsDrain<'_, V> {}
+
+impl<V> private::Sealed for SlotsDrain<'_, V> {}
+
+impl<V> Slots<V> for SlotsDrain<'_, V> {
+    #[inline]
+    fn marks(&self, w: usize, from: usize, to: usize) -> usize {
+        marks_within(&self.init, w, from, to.min(self.len))
+    }
+
+    #[inline]
+    unsafe fn take(&mut self, i: usize) -> V {
+        clear_bit(&mut self.init, i);
+        (*self.values.add(i)).assume_read()
+    }
+}
+
+impl<V> Drop for SlotsDrain<'_, V> {
+    fn drop(&mut self) {
+        // SAFETY: the marks describe exactly the occupied slots that have not been taken.
+        unsafe { drop_marked(self.values, self.len, &mut self.init) };
+        // Hand the now-cleared bitvector back so the map does not need to reallocate it.
+        *self.home = mem::take(&mut self.init);
+    }
+}
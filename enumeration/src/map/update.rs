@@ -0,0 +1,29 @@
+/// A change to a single entry of an [`EnumMap`], applied by [`EnumMap::apply`].
+///
+/// [`EnumMap`]: crate::EnumMap
+/// [`EnumMap::apply`]: crate::EnumMap::apply
+///
+/// # Examples
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use enumeration::EnumMap;
+/// use enumeration::map::Update;
+///
+/// let mut map = EnumMap::from([(Ordering::Less, 1), (Ordering::Equal, 2)]);
+/// map.apply([
+///     (Ordering::Less, Update::Modify(|v| *v += 10)),
+///     (Ordering::Equal, Update::Remove),
+///     (Ordering::Greater, Update::Set(3)),
+/// ]);
+/// assert_eq!(map, EnumMap::from([(Ordering::Less, 11), (Ordering::Greater, 3)]));
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub enum Update<V> {
+    /// Inserts the value, replacing any previous value.
+    Set(V),
+    /// Removes the entry, if present.
+    Remove,
+    /// Modifies the value in place. Does nothing if the entry is vacant.
+    Modify(fn(&mut V)),
+}
@@ -0,0 +1,44 @@
+use core::error::Error;
+use core::fmt::{self, Debug, Display, Formatter};
+
+use super::enum_set::EnumSet;
+use crate::enumerate::Enum;
+
+/// How [`EnumSet::apply_diff_with`] treats removals of values that are not in the set.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DiffPolicy {
+    /// Fail with [`Conflict::AbsentRemoval`].
+    #[default]
+    Strict,
+    /// Skip them, as `AND NOT` does.
+    IgnoreAbsentRemovals,
+}
+
+/// The error returned when a set difference cannot be applied.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Conflict<T: Enum> {
+    /// These values were both added and removed.
+    Overlap(EnumSet<T>),
+    /// These values were removed, but were not in the set.
+    AbsentRemoval(EnumSet<T>),
+}
+
+impl<T: Enum + Debug> Debug for Conflict<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Overlap(values) => f.debug_tuple("Overlap").field(values).finish(),
+            Self::AbsentRemoval(values) => f.debug_tuple("AbsentRemoval").field(values).finish(),
+        }
+    }
+}
+
+impl<T: Enum + Debug> Display for Conflict<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Overlap(values) => write!(f, "values both added and removed: {values:?}"),
+            Self::AbsentRemoval(values) => write!(f, "removed values not in the set: {values:?}"),
+        }
+    }
+}
+
+impl<T: Enum + Debug> Error for Conflict<T> {}
@@ -0,0 +1,35 @@
+use core::error::Error;
+use core::fmt::{self, Debug, Display, Formatter};
+
+/// How [`EnumSet::with_policy`](super::EnumSet::with_policy) treats a value that appears more
+/// than once.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DuplicatePolicy {
+    /// Keep one copy of the value, as `collect` does.
+    #[default]
+    IgnoreDuplicates,
+    /// Fail with a [`DuplicateError`].
+    ErrorOnDuplicate,
+}
+
+/// The error returned when a value appears more than once in input that must not contain
+/// duplicates.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DuplicateError<T> {
+    /// The repeated value.
+    pub value: T,
+    /// The position in the input of the second occurrence of `value`.
+    pub position: usize,
+}
+
+impl<T: Debug> Display for DuplicateError<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "duplicate value {:?} at position {}",
+            self.value, self.position
+        )
+    }
+}
+
+impl<T: Debug> Error for DuplicateError<T> {}
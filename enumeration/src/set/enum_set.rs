@@ -1,13 +1,32 @@
-use std::cmp::Ordering;
-use std::fmt::{self, Debug, Formatter};
-use std::hash::{Hash, Hasher};
-use std::iter::{FromIterator, Iterator};
-use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::iter::{FromIterator, Iterator, Product, Sum};
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, RangeBounds};
 
-use super::iter::Iter;
+use super::diff::{Conflict, DiffPolicy};
+use super::duplicates::{DuplicateError, DuplicatePolicy};
+use super::iter::{AllSets, Indices, Iter};
+use super::masked::MaskedSet;
+use super::quota::QuotaExceeded;
 use crate::enumerate::Enum;
 use crate::wordlike::Wordlike;
 
+/// Returns `x.bit()`, checking in debug builds that it is a single bit within `T::BITMASK`.
+///
+/// A faulty manual [`Enum`] implementation would otherwise silently corrupt sets.
+#[inline]
+fn bit_of<T: Enum>(x: T) -> T::Rep {
+    let bit = x.bit();
+    debug_assert!(
+        Wordlike::count_ones(bit) == 1 && bit & !T::BITMASK == Wordlike::ZERO,
+        "Enum::bit() must return a single bit within Enum::BITMASK",
+    );
+    bit
+}
+
 #[repr(transparent)]
 pub struct EnumSet<T: Enum> {
     raw: T::Rep,
@@ -113,6 +132,200 @@ where
         self.raw == Wordlike::ZERO
     }
 
+    /// Returns `true` if the set contains exactly one element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    ///
+    /// let mut v: EnumSet<TextStyle> = EnumSet::new();
+    /// assert!(!v.is_single());
+    /// v.insert(TextStyle::Italic);
+    /// assert!(v.is_single());
+    /// v.insert(TextStyle::Bold);
+    /// assert!(!v.is_single());
+    /// ```
+    #[inline]
+    pub fn is_single(&self) -> bool {
+        T::Rep::count_ones(self.raw) == 1
+    }
+
+    /// Returns the only element of the set, or `None` if it does not contain exactly one.
+    ///
+    /// This is cheaper than iterating, which makes singleton sets a convenient way to store
+    /// the current state of a state machine.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    ///
+    /// assert_eq!(enums![TextStyle::Italic].exactly_one(), Some(TextStyle::Italic));
+    /// assert_eq!(enums![TextStyle::Bold, TextStyle::Italic].exactly_one(), None);
+    /// assert_eq!(EnumSet::<TextStyle>::new().exactly_one(), None);
+    /// ```
+    #[inline]
+    pub fn exactly_one(&self) -> Option<T> {
+        if self.is_single() {
+            T::from_index(T::Rep::trailing_zeros(self.raw))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the first value that is not in the set, or `None` if the set is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Channel { A, B, C, D }
+    ///
+    /// let mut in_use = enums![Channel::A, Channel::C];
+    /// assert_eq!(in_use.first_absent(), Some(Channel::B));
+    ///
+    /// in_use.insert(Channel::B);
+    /// in_use.insert(Channel::D);
+    /// assert_eq!(in_use.first_absent(), None);
+    /// ```
+    #[inline]
+    pub fn first_absent(&self) -> Option<T> {
+        Self::first_in(!self.raw & T::BITMASK)
+    }
+
+    /// Returns the first value after `x` that is in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Channel { A, B, C, D }
+    ///
+    /// let set = enums![Channel::A, Channel::C];
+    /// assert_eq!(set.next_present_after(Channel::A), Some(Channel::C));
+    /// assert_eq!(set.next_present_after(Channel::C), None);
+    /// ```
+    #[inline]
+    pub fn next_present_after(&self, x: T) -> Option<T> {
+        Self::first_in(self.raw & !T::Rep::low_mask(x.index() + 1))
+    }
+
+    /// Returns the first value after `x` that is not in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Channel { A, B, C, D }
+    ///
+    /// let set = enums![Channel::B, Channel::C, Channel::D];
+    /// assert_eq!(set.next_absent_after(Channel::A), None);
+    /// assert_eq!(enums![Channel::B].next_absent_after(Channel::A), Some(Channel::C));
+    /// ```
+    #[inline]
+    pub fn next_absent_after(&self, x: T) -> Option<T> {
+        Self::first_in(!self.raw & T::BITMASK & !T::Rep::low_mask(x.index() + 1))
+    }
+
+    /// Returns the set that follows this one when every possible set is ordered by its raw
+    /// bits, or `None` if this is [`all`](Self::all). Stepping from [`new`](Self::new) visits
+    /// all 2<sup>`SIZE`</sup> sets, each in a single increment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Channel { A, B, C, D }
+    ///
+    /// assert_eq!(EnumSet::new().next_set(), Some(enums![Channel::A]));
+    /// assert_eq!(enums![Channel::A].next_set(), Some(enums![Channel::B]));
+    /// assert_eq!(enums![Channel::A, Channel::B].next_set(), Some(enums![Channel::C]));
+    /// assert_eq!(EnumSet::<Channel>::all().next_set(), None);
+    /// ```
+    #[inline]
+    pub fn next_set(&self) -> Option<Self> {
+        let raw = self.raw & T::BITMASK;
+        if raw == T::BITMASK {
+            None
+        } else {
+            Some(Self { raw: raw.incr() })
+        }
+    }
+
+    /// Returns the set that precedes this one when every possible set is ordered by its raw
+    /// bits, or `None` if this is empty. This is the inverse of
+    /// [`next_set`](Self::next_set).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Channel { A, B, C, D }
+    ///
+    /// assert_eq!(enums![Channel::C].prev_set(), Some(enums![Channel::A, Channel::B]));
+    /// assert_eq!(EnumSet::<Channel>::new().prev_set(), None);
+    /// ```
+    #[inline]
+    pub fn prev_set(&self) -> Option<Self> {
+        let raw = self.raw & T::BITMASK;
+        if raw == T::Rep::ZERO {
+            None
+        } else {
+            Some(Self { raw: raw.decr() })
+        }
+    }
+
+    /// An iterator over every possible set of `T`, from the empty set to [`all`](Self::all), in
+    /// the order of [`next_set`](Self::next_set).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Channel { A, B, C, D }
+    ///
+    /// assert_eq!(EnumSet::<Channel>::all_sets().count(), 16);
+    ///
+    /// // Brute-force the smallest set of channels that covers a requirement.
+    /// let smallest = EnumSet::all_sets()
+    ///     .filter(|set| set.contains(Channel::B) && set.len() >= 2)
+    ///     .min_by_key(EnumSet::len);
+    /// assert_eq!(smallest, Some(enums![Channel::A, Channel::B]));
+    /// ```
+    #[inline]
+    pub fn all_sets() -> AllSets<T> {
+        AllSets::new()
+    }
+
+    /// Returns the value of the lowest set bit.
+    #[inline]
+    fn first_in(raw: T::Rep) -> Option<T> {
+        if raw == T::Rep::ZERO {
+            None
+        } else {
+            T::from_index(T::Rep::trailing_zeros(raw))
+        }
+    }
+
     /// Retains only the elements specified by the predicate.
     ///
     /// In other words, remove all elements `e` for which `f(e)` returns `false`.
@@ -134,14 +347,70 @@ where
     where
         F: FnMut(T) -> bool,
     {
-        for val in T::enumerate(..) {
-            let bit = val.bit();
+        for val in T::enumerate_all(..) {
+            let bit = bit_of(val);
             if ((self.raw & bit) != Wordlike::ZERO) && !f(val) {
                 self.raw &= !bit;
             }
         }
     }
 
+    /// Returns the elements of the set that lie within a range, analogous to
+    /// [`BTreeSet::range`](std::collections::BTreeSet::range). Unlike `BTreeSet::range`, this
+    /// returns an empty set rather than panicking if the range is backwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Level { Trace, Debug, Info, Warn, Error }
+    ///
+    /// let flagged = enums![Level::Debug, Level::Warn, Level::Error];
+    /// assert_eq!(flagged.range(Level::Warn..), enums![Level::Warn, Level::Error]);
+    /// assert_eq!(flagged.range(..Level::Warn), enums![Level::Debug]);
+    /// assert_eq!(flagged.range(Level::Info..=Level::Info), EnumSet::new());
+    /// ```
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> Self {
+        let within = T::enumerate_all(range).fold(T::Rep::ZERO, |raw, x| raw | bit_of(x));
+        Self {
+            raw: self.raw & within,
+        }
+    }
+
+    /// Splits the set in two by a predicate.
+    ///
+    /// The first set contains every element for which `f` returns `true`, and the second set
+    /// contains every element for which it returns `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    ///
+    /// let set = enums![TextStyle::Blink, TextStyle::Bold, TextStyle::Underline];
+    /// let (lines, rest) = set.partition(|k| k == TextStyle::Strikeout || k == TextStyle::Underline);
+    /// assert_eq!(lines, enums![TextStyle::Underline]);
+    /// assert_eq!(rest, enums![TextStyle::Blink, TextStyle::Bold]);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn partition<F>(&self, mut f: F) -> (Self, Self)
+    where
+        F: FnMut(T) -> bool,
+    {
+        let mut matching = Self::new();
+        for val in *self {
+            if f(val) {
+                matching.insert(val);
+            }
+        }
+        (matching, self.difference(&matching))
+    }
+
     /// Clears the set, removing all values.
     ///
     /// # Examples
@@ -186,8 +455,8 @@ where
         }
     }
 
-    /// Returns a new set representing the difference,
-    /// i.e., the values that are in `self` but not in `other`.
+    /// An iterator visiting the values contained by this set, in order. The set is not
+    /// consumed, although since it is `Copy`, `into_iter` leaves it usable too.
     ///
     /// # Examples
     ///
@@ -195,25 +464,20 @@ where
     /// use enumeration::{Enum, EnumSet, enums};
     ///
     /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
-    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
-    ///
-    /// let a = enums![TextStyle::Blink, TextStyle::Bold, TextStyle::Italic];
-    /// let b = enums![TextStyle::Bold, TextStyle::Italic, TextStyle::Underline];
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
     ///
-    /// // Can be seen as `a - b`.
-    /// let diff = a.difference(&b);
-    /// assert_eq!(diff, enums![TextStyle::Blink]);
+    /// let set = enums![TextStyle::Strikeout, TextStyle::Bold];
+    /// let present: Vec<_> = set.iter().collect();
+    /// assert_eq!(present, [TextStyle::Bold, TextStyle::Strikeout]);
     /// ```
     #[inline]
-    #[must_use = "newly constructed set is unused"]
-    pub fn difference(&self, other: &Self) -> Self {
-        Self {
-            raw: (self.raw | other.raw) ^ other.raw,
-        }
+    pub fn iter(&self) -> Iter<T> {
+        Iter::new(*self)
     }
 
-    /// Returns a new set representing the symmetric difference,
-    /// i.e., the values that are in `self` or in `other` but not in both.
+    /// An iterator visiting the values not contained by this set, in order.
+    ///
+    /// This is equivalent to `self.inverse().into_iter()`.
     ///
     /// # Examples
     ///
@@ -221,24 +485,18 @@ where
     /// use enumeration::{Enum, EnumSet, enums};
     ///
     /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
-    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
-    ///
-    /// let a = enums![TextStyle::Blink, TextStyle::Bold, TextStyle::Italic];
-    /// let b = enums![TextStyle::Bold, TextStyle::Italic, TextStyle::Underline];
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
     ///
-    /// let diff = a.symmetric_difference(&b);
-    /// assert_eq!(diff, enums![TextStyle::Blink, TextStyle::Underline]);
+    /// let set = enums![TextStyle::Bold, TextStyle::Strikeout];
+    /// let absent: Vec<_> = set.iter_absent().collect();
+    /// assert_eq!(absent, [TextStyle::Italic, TextStyle::Underline]);
     /// ```
     #[inline]
-    #[must_use = "newly constructed set is unused"]
-    pub fn symmetric_difference(&self, other: &Self) -> Self {
-        Self {
-            raw: self.raw ^ other.raw,
-        }
+    pub fn iter_absent(&self) -> Iter<T> {
+        Iter::new(self.inverse())
     }
 
-    /// Returns a new set representing the intersection,
-    /// i.e., the values that are both in `self` and `other`.
+    /// An iterator visiting the [`index`](Enum::index) of every element, in order.
     ///
     /// # Examples
     ///
@@ -246,23 +504,18 @@ where
     /// use enumeration::{Enum, EnumSet, enums};
     ///
     /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
-    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
     ///
-    /// let a = enums![TextStyle::Blink, TextStyle::Bold, TextStyle::Italic];
-    /// let b = enums![TextStyle::Bold, TextStyle::Italic, TextStyle::Underline];
-    /// let intersection = a.intersection(&b);
-    /// assert_eq!(intersection, enums![TextStyle::Bold, TextStyle::Italic]);
+    /// let set = enums![TextStyle::Italic, TextStyle::Underline];
+    /// let indices: Vec<_> = set.iter_indices().collect();
+    /// assert_eq!(indices, [1, 3]);
     /// ```
     #[inline]
-    #[must_use = "newly constructed set is unused"]
-    pub fn intersection(&self, other: &Self) -> Self {
-        Self {
-            raw: (self.raw & other.raw),
-        }
+    pub fn iter_indices(&self) -> Indices<T> {
+        Indices::new(*self)
     }
 
-    /// Visits the values representing the union,
-    /// i.e., all the values in `self` or `other`, without duplicates.
+    /// Collects the elements of the set into a `Vec`, in order, allocating exactly once.
     ///
     /// # Examples
     ///
@@ -270,22 +523,95 @@ where
     /// use enumeration::{Enum, EnumSet, enums};
     ///
     /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
-    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
     ///
-    /// let a = enums![TextStyle::Blink, TextStyle::Bold];
-    /// let b = enums![TextStyle::Bold, TextStyle::Italic];
-    /// let union = a.union(&b);
-    /// assert_eq!(union, enums![TextStyle::Blink, TextStyle::Bold, TextStyle::Italic]);
+    /// let set = enums![TextStyle::Underline, TextStyle::Bold];
+    /// let values = set.to_vec();
+    /// assert_eq!(values, [TextStyle::Bold, TextStyle::Underline]);
+    /// assert_eq!(values.capacity(), 2);
     /// ```
-    #[inline]
-    #[must_use = "newly constructed set is unused"]
-    pub fn union(&self, other: &Self) -> Self {
-        Self {
-            raw: self.raw | other.raw,
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn to_vec(&self) -> Vec<T> {
+        let mut values = Vec::with_capacity(self.len());
+        values.extend(*self);
+        values
+    }
+
+    /// Collects the elements of the set into an array, in order, or returns `None` if the set
+    /// does not have exactly `N` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
+    ///
+    /// let set = enums![TextStyle::Underline, TextStyle::Bold];
+    /// assert_eq!(set.collect_array(), Some([TextStyle::Bold, TextStyle::Underline]));
+    /// assert_eq!(set.collect_array::<3>(), None);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn collect_array<const N: usize>(&self) -> Option<[T; N]> {
+        if self.len() != N {
+            return None;
         }
+        let mut iter = self.into_iter();
+        Some(core::array::from_fn(|_| {
+            iter.next()
+                .expect("got None from a set iterator before reaching its length")
+        }))
     }
 
-    /// Returns `true` if the set contains a value.
+    /// Creates a set from an iterator, handling repeated values according to `policy`.
+    ///
+    /// # Errors
+    ///
+    /// With [`DuplicatePolicy::ErrorOnDuplicate`], returns the first value that appears more
+    /// than once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{DuplicateError, DuplicatePolicy, Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
+    ///
+    /// let input = [TextStyle::Bold, TextStyle::Italic, TextStyle::Bold];
+    /// assert_eq!(
+    ///     EnumSet::with_policy(input, DuplicatePolicy::IgnoreDuplicates),
+    ///     Ok(enums![TextStyle::Bold, TextStyle::Italic])
+    /// );
+    /// assert_eq!(
+    ///     EnumSet::with_policy(input, DuplicatePolicy::ErrorOnDuplicate),
+    ///     Err(DuplicateError { value: TextStyle::Bold, position: 2 })
+    /// );
+    /// ```
+    pub fn with_policy<I: IntoIterator<Item = T>>(
+        iter: I,
+        policy: DuplicatePolicy,
+    ) -> Result<Self, DuplicateError<T>> {
+        match policy {
+            DuplicatePolicy::IgnoreDuplicates => Ok(Self::from_iter(iter)),
+            DuplicatePolicy::ErrorOnDuplicate => {
+                let mut set = Self::new();
+                for (position, value) in iter.into_iter().enumerate() {
+                    if set.contains(value) {
+                        return Err(DuplicateError { value, position });
+                    }
+                    set.insert(value);
+                }
+                Ok(set)
+            }
+        }
+    }
+
+    /// Creates a set from the [`index`](Enum::index) of each element.
+    ///
+    /// Indices that do not correspond to any value of `T` are ignored.
     ///
     /// # Examples
     ///
@@ -293,19 +619,699 @@ where
     /// use enumeration::{Enum, EnumSet, enums};
     ///
     /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
-    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
     ///
-    /// let set = enums![TextStyle::Blink, TextStyle::Bold, TextStyle::Italic];
-    /// assert_eq!(set.contains(TextStyle::Bold), true);
-    /// assert_eq!(set.contains(TextStyle::Highlight), false);
+    /// let set = EnumSet::from_indices([3, 0, 7]);
+    /// assert_eq!(set, enums![TextStyle::Bold, TextStyle::Underline]);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn from_indices<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        iter.into_iter().filter_map(T::from_index).collect()
+    }
+
+    /// Returns a view of the set restricted to `universe`, whose complement is taken relative
+    /// to `universe` instead of all values of `T`.
+    ///
+    /// Values of the set that are not in `universe` are dropped from the view.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
+    ///
+    /// let allowed = enums![TextStyle::Bold, TextStyle::Italic];
+    /// let set = enums![TextStyle::Bold].restricted_to(allowed);
+    /// assert_eq!(set.complement().to_set(), enums![TextStyle::Italic]);
     /// ```
     #[inline]
-    pub fn contains(&self, x: T) -> bool {
-        self.raw & x.bit() != Wordlike::ZERO
+    pub fn restricted_to(&self, universe: Self) -> MaskedSet<T> {
+        MaskedSet::new(*self, universe)
+    }
+
+    /// Returns a new set representing the difference,
+    /// i.e., the values that are in `self` but not in `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    ///
+    /// let a = enums![TextStyle::Blink, TextStyle::Bold, TextStyle::Italic];
+    /// let b = enums![TextStyle::Bold, TextStyle::Italic, TextStyle::Underline];
+    ///
+    /// // Can be seen as `a - b`.
+    /// let diff = a.difference(&b);
+    /// assert_eq!(diff, enums![TextStyle::Blink]);
+    /// ```
+    #[inline]
+    #[must_use = "newly constructed set is unused"]
+    pub fn difference(&self, other: &Self) -> Self {
+        Self {
+            raw: (self.raw | other.raw) ^ other.raw,
+        }
+    }
+
+    /// Returns a new set representing the symmetric difference,
+    /// i.e., the values that are in `self` or in `other` but not in both.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    ///
+    /// let a = enums![TextStyle::Blink, TextStyle::Bold, TextStyle::Italic];
+    /// let b = enums![TextStyle::Bold, TextStyle::Italic, TextStyle::Underline];
+    ///
+    /// let diff = a.symmetric_difference(&b);
+    /// assert_eq!(diff, enums![TextStyle::Blink, TextStyle::Underline]);
+    /// ```
+    #[inline]
+    #[must_use = "newly constructed set is unused"]
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        Self {
+            raw: self.raw ^ other.raw,
+        }
+    }
+
+    /// Returns a new set representing the intersection,
+    /// i.e., the values that are both in `self` and `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    ///
+    /// let a = enums![TextStyle::Blink, TextStyle::Bold, TextStyle::Italic];
+    /// let b = enums![TextStyle::Bold, TextStyle::Italic, TextStyle::Underline];
+    /// let intersection = a.intersection(&b);
+    /// assert_eq!(intersection, enums![TextStyle::Bold, TextStyle::Italic]);
+    /// ```
+    #[inline]
+    #[must_use = "newly constructed set is unused"]
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self {
+            raw: (self.raw & other.raw),
+        }
+    }
+
+    /// Visits the values representing the union,
+    /// i.e., all the values in `self` or `other`, without duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    ///
+    /// let a = enums![TextStyle::Blink, TextStyle::Bold];
+    /// let b = enums![TextStyle::Bold, TextStyle::Italic];
+    /// let union = a.union(&b);
+    /// assert_eq!(union, enums![TextStyle::Blink, TextStyle::Bold, TextStyle::Italic]);
+    /// ```
+    #[inline]
+    #[must_use = "newly constructed set is unused"]
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            raw: self.raw | other.raw,
+        }
+    }
+
+    /// Returns `true` if the set contains a value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    ///
+    /// let set = enums![TextStyle::Blink, TextStyle::Bold, TextStyle::Italic];
+    /// assert_eq!(set.contains(TextStyle::Bold), true);
+    /// assert_eq!(set.contains(TextStyle::Highlight), false);
+    /// ```
+    #[inline]
+    pub fn contains(&self, x: T) -> bool {
+        self.raw & bit_of(x) != Wordlike::ZERO
+    }
+
+    /// Returns `true` if `self` has no elements in common with `other`.
+    /// This is equivalent to checking for an empty intersection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    ///
+    /// let a = enums![TextStyle::Blink, TextStyle::Bold, TextStyle::Italic];
+    /// let mut b = EnumSet::new();
+    ///
+    /// assert_eq!(a.is_disjoint(&b), true);
+    /// b.insert(TextStyle::Highlight);
+    /// assert_eq!(a.is_disjoint(&b), true);
+    /// b.insert(TextStyle::Blink);
+    /// assert_eq!(a.is_disjoint(&b), false);
+    /// ```
+    #[inline]
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.raw & other.raw == Wordlike::ZERO
+    }
+
+    /// Returns `true` if the set is a subset of another,
+    /// i.e., `other` contains at least all the values in `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    ///
+    /// let sup = enums![TextStyle::Blink, TextStyle::Bold, TextStyle::Italic];
+    /// let mut set = EnumSet::new();
+    ///
+    /// assert_eq!(set.is_subset(&sup), true);
+    /// set.insert(TextStyle::Blink);
+    /// assert_eq!(set.is_subset(&sup), true);
+    /// set.insert(TextStyle::Highlight);
+    /// assert_eq!(set.is_subset(&sup), false);
+    /// ```
+    #[inline]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.raw | other.raw == other.raw
+    }
+
+    /// Returns `true` if the set is a superset of another,
+    /// i.e., `self` contains at least all the values in `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    ///
+    /// let sub = enums![TextStyle::Blink, TextStyle::Bold];
+    /// let mut set = EnumSet::new();
+    ///
+    /// assert_eq!(set.is_superset(&sub), false);
+    ///
+    /// set.insert(TextStyle::Blink);
+    /// set.insert(TextStyle::Highlight);
+    /// assert_eq!(set.is_superset(&sub), false);
+    ///
+    /// set.insert(TextStyle::Bold);
+    /// assert_eq!(set.is_superset(&sub), true);
+    /// ```
+    #[inline]
+    pub fn is_superset(&self, other: &Self) -> bool {
+        self.raw | other.raw == self.raw
+    }
+
+    /// Adds a value to the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    ///
+    /// let mut set = enums![TextStyle::Blink];
+    /// set.insert(TextStyle::Bold);
+    ///
+    /// assert_eq!(set, enums![TextStyle::Blink, TextStyle::Bold]);
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, x: T) {
+        self.raw |= bit_of(x);
+    }
+
+    /// Adds a value to the set unless the set already holds `max_len` values. Returns whether
+    /// the value was newly inserted.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`QuotaExceeded`], leaving the set unchanged, if the value is not in the set
+    /// and the set already holds `max_len` or more values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    /// use enumeration::set::QuotaExceeded;
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Buff { Haste, Shield, Regen }
+    ///
+    /// let mut active = enums![Buff::Haste];
+    /// assert_eq!(active.try_insert_within(Buff::Shield, 2), Ok(true));
+    /// assert_eq!(active.try_insert_within(Buff::Shield, 2), Ok(false));
+    /// assert_eq!(active.try_insert_within(Buff::Regen, 2), Err(QuotaExceeded { limit: 2 }));
+    /// assert_eq!(active, enums![Buff::Haste, Buff::Shield]);
+    /// ```
+    pub fn try_insert_within(&mut self, x: T, max_len: usize) -> Result<bool, QuotaExceeded> {
+        if self.contains(x) {
+            Ok(false)
+        } else if self.len() >= max_len {
+            Err(QuotaExceeded { limit: max_len })
+        } else {
+            self.insert(x);
+            Ok(true)
+        }
+    }
+
+    /// Removes a value from the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    ///
+    /// let mut set = EnumSet::new();
+    ///
+    /// set.insert(TextStyle::Blink);
+    /// set.insert(TextStyle::Bold);
+    /// set.remove(TextStyle::Bold);
+    /// assert_eq!(set, enums![TextStyle::Blink]);
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, x: T) {
+        self.raw &= !bit_of(x);
+    }
+
+    /// Returns the set with a value added, for building sets in expressions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    ///
+    /// let set = EnumSet::new().with(TextStyle::Bold).with(TextStyle::Italic);
+    /// assert_eq!(set, enums![TextStyle::Bold, TextStyle::Italic]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with(mut self, x: T) -> Self {
+        self.insert(x);
+        self
+    }
+
+    /// Returns the set with a value added if `condition` is `true`, and unchanged otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    ///
+    /// let emphasized = false;
+    /// let set = enums![TextStyle::Underline].with_if(emphasized, TextStyle::Bold);
+    /// assert_eq!(set, enums![TextStyle::Underline]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_if(self, condition: bool, x: T) -> Self {
+        if condition {
+            self.with(x)
+        } else {
+            self
+        }
+    }
+
+    /// Returns the set with a value removed, for building sets in expressions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    ///
+    /// let set = EnumSet::all().without(TextStyle::Blink).without(TextStyle::Highlight);
+    /// assert_eq!(set.len(), 4);
+    /// assert!(!set.contains(TextStyle::Blink));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn without(mut self, x: T) -> Self {
+        self.remove(x);
+        self
+    }
+
+    /// Starts building a set by method chaining.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    ///
+    /// let is_link = true;
+    /// let set = EnumSet::builder()
+    ///     .with(TextStyle::Bold)
+    ///     .with_if(is_link, TextStyle::Underline)
+    ///     .without(TextStyle::Bold)
+    ///     .build();
+    /// assert_eq!(set, enums![TextStyle::Underline]);
+    /// ```
+    #[inline]
+    pub const fn builder() -> EnumSetBuilder<T> {
+        EnumSetBuilder { set: Self::new() }
+    }
+
+    /// Adds `added` to the set and takes `removed` out of it, as when applying a delta received
+    /// from elsewhere. Fails without changing the set if a value is both added and removed, or
+    /// if a removed value is not in the set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Conflict::Overlap`] if `added` and `removed` have values in common, and
+    /// otherwise [`Conflict::AbsentRemoval`] if `removed` has values that are not in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    /// use enumeration::set::Conflict;
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
+    ///
+    /// let mut set = enums![TextStyle::Bold];
+    /// set.apply_diff(enums![TextStyle::Italic], enums![TextStyle::Bold]).unwrap();
+    /// assert_eq!(set, enums![TextStyle::Italic]);
+    ///
+    /// assert_eq!(
+    ///     set.apply_diff(EnumSet::new(), enums![TextStyle::Bold]),
+    ///     Err(Conflict::AbsentRemoval(enums![TextStyle::Bold]))
+    /// );
+    /// assert_eq!(
+    ///     set.apply_diff(enums![TextStyle::Italic], enums![TextStyle::Italic]),
+    ///     Err(Conflict::Overlap(enums![TextStyle::Italic]))
+    /// );
+    /// assert_eq!(set, enums![TextStyle::Italic]);
+    /// ```
+    #[inline]
+    pub fn apply_diff(&mut self, added: Self, removed: Self) -> Result<(), Conflict<T>> {
+        self.apply_diff_with(added, removed, DiffPolicy::Strict)
+    }
+
+    /// Like [`apply_diff`](Self::apply_diff), but with a configurable policy for removals of
+    /// values that are not in the set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Conflict::Overlap`] if `added` and `removed` have values in common. With
+    /// [`DiffPolicy::Strict`], returns [`Conflict::AbsentRemoval`] if `removed` has values that
+    /// are not in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    /// use enumeration::set::DiffPolicy;
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
+    ///
+    /// let mut set = enums![TextStyle::Bold];
+    /// let result = set.apply_diff_with(
+    ///     enums![TextStyle::Underline],
+    ///     enums![TextStyle::Italic],
+    ///     DiffPolicy::IgnoreAbsentRemovals,
+    /// );
+    /// assert_eq!(result, Ok(()));
+    /// assert_eq!(set, enums![TextStyle::Bold, TextStyle::Underline]);
+    /// ```
+    pub fn apply_diff_with(
+        &mut self,
+        added: Self,
+        removed: Self,
+        policy: DiffPolicy,
+    ) -> Result<(), Conflict<T>> {
+        let overlap = added.intersection(&removed);
+        if !overlap.is_empty() {
+            return Err(Conflict::Overlap(overlap));
+        }
+        let absent = removed.difference(self);
+        if policy == DiffPolicy::Strict && !absent.is_empty() {
+            return Err(Conflict::AbsentRemoval(absent));
+        }
+        *self = self.difference(&removed).union(&added);
+        Ok(())
+    }
+
+    /// Inserts or removes each value according to its flag, in order, so a value that appears
+    /// more than once ends up as its last assignment says.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
+    ///
+    /// let mut set = enums![TextStyle::Bold];
+    /// set.assign([
+    ///     (TextStyle::Bold, false),
+    ///     (TextStyle::Italic, true),
+    ///     (TextStyle::Underline, true),
+    ///     (TextStyle::Underline, false),
+    /// ]);
+    /// assert_eq!(set, enums![TextStyle::Italic]);
+    /// ```
+    pub fn assign<I: IntoIterator<Item = (T, bool)>>(&mut self, assignments: I) {
+        for (x, present) in assignments {
+            if present {
+                self.insert(x);
+            } else {
+                self.remove(x);
+            }
+        }
+    }
+
+    /// An iterator visiting every value of `T` in order, paired with whether it is in the set.
+    /// [`assign`](Self::assign) turns the result back into a set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
+    ///
+    /// let set = enums![TextStyle::Italic];
+    /// assert_eq!(
+    ///     set.to_assignments().collect::<Vec<_>>(),
+    ///     [
+    ///         (TextStyle::Bold, false),
+    ///         (TextStyle::Italic, true),
+    ///         (TextStyle::Strikeout, false),
+    ///         (TextStyle::Underline, false),
+    ///     ]
+    /// );
+    ///
+    /// let mut copy = EnumSet::new();
+    /// copy.assign(set.to_assignments());
+    /// assert_eq!(copy, set);
+    /// ```
+    #[inline]
+    pub fn to_assignments(&self) -> impl ExactSizeIterator<Item = (T, bool)> + DoubleEndedIterator {
+        let set = *self;
+        T::enumerate_all(..).map(move |x| (x, set.contains(x)))
+    }
+
+    /// Constructs a set from the underlying bit representation of the enum flags. Intended for FFI.
+    ///
+    /// Bits that do not correspond to any value of `T` are cleared.
+    ///
+    /// Clearing them requires `T::Rep`'s `&` operator, which cannot be called in constant
+    /// expressions, so this is not a `const fn`. Constants should use
+    /// [`from_raw_unchecked`](Self::from_raw_unchecked) with bits known to be in range, such as
+    /// unions of [`Enum::bit`] values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
+    ///
+    /// const BOLD_ITALIC: EnumSet<TextStyle> =
+    ///     EnumSet::from_raw_unchecked(TextStyle::Bold.bit() | TextStyle::Italic.bit());
+    /// assert_eq!(BOLD_ITALIC, enums![TextStyle::Bold, TextStyle::Italic]);
+    ///
+    /// let set = EnumSet::<TextStyle>::from_raw(0b1111_0101);
+    /// assert_eq!(set, enums![TextStyle::Bold, TextStyle::Strikeout]);
+    /// assert_eq!(set.to_raw(), 0b0101);
+    /// ```
+    #[inline]
+    pub fn from_raw(raw: T::Rep) -> Self {
+        Self {
+            raw: raw & T::BITMASK,
+        }
+    }
+
+    /// Constructs a set from the underlying bit representation of the enum flags without
+    /// clearing bits that do not correspond to any value of `T`.
+    ///
+    /// Unlike [`from_raw`](Self::from_raw), this can be used in constant expressions. If `raw`
+    /// has bits outside of `T::BITMASK` set, [`len`](Self::len), iteration and comparisons will
+    /// give incorrect results until the set is [`normalize`](Self::normalize)d.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
+    ///
+    /// const STYLES: EnumSet<TextStyle> = EnumSet::from_raw_unchecked(0b0011);
+    /// assert_eq!(STYLES, enums![TextStyle::Bold, TextStyle::Italic]);
+    /// ```
+    #[inline]
+    pub const fn from_raw_unchecked(raw: T::Rep) -> Self {
+        Self { raw }
+    }
+
+    /// Returns the bits of the set as 64-bit words, least significant first, for interop with
+    /// bitmap libraries. Bit `i` of the set is bit `i % 64` of word `i / 64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
+    ///
+    /// let set = enums![TextStyle::Bold, TextStyle::Underline];
+    /// assert_eq!(set.words().collect::<Vec<_>>(), [0b1001]);
+    /// ```
+    #[inline]
+    pub fn words(&self) -> impl ExactSizeIterator<Item = u64> + DoubleEndedIterator {
+        let raw = self.raw;
+        (0..T::Rep::WORDS).map(move |i| Wordlike::word(raw, i))
+    }
+
+    /// Creates a set from 64-bit words laid out as by [`words`](Self::words).
+    ///
+    /// Bits that do not correspond to any value of `T`, including every bit of any words past
+    /// the end of the representation, are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
+    ///
+    /// let set = EnumSet::<TextStyle>::from_words(&[0b1_0011, u64::MAX]);
+    /// assert_eq!(set, enums![TextStyle::Bold, TextStyle::Italic]);
+    /// ```
+    pub fn from_words(words: &[u64]) -> Self {
+        let raw = words
+            .iter()
+            .enumerate()
+            .take(T::Rep::WORDS)
+            .fold(T::Rep::ZERO, |raw, (i, &word)| raw.with_word(i, word));
+        Self::from_raw(raw)
+    }
+
+    /// Calls a function with the index and value of each 64-bit word of the set, laid out as by
+    /// [`words`](Self::words), least significant first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
+    ///
+    /// let mut ones = 0;
+    /// enums![TextStyle::Bold, TextStyle::Underline].for_each_word(|_, word| {
+    ///     ones += word.count_ones();
+    /// });
+    /// assert_eq!(ones, 2);
+    /// ```
+    #[inline]
+    pub fn for_each_word<F: FnMut(usize, u64)>(&self, mut f: F) {
+        for (i, word) in self.words().enumerate() {
+            f(i, word);
+        }
+    }
+
+    /// Replaces each 64-bit word of the set with the result of a function called with its index
+    /// and value, laid out as by [`words`](Self::words).
+    ///
+    /// Bits that the function sets outside of `T::BITMASK` are cleared afterward, so the set
+    /// stays normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
+    ///
+    /// let mut set = enums![TextStyle::Bold, TextStyle::Underline];
+    /// set.map_words(|_, word| !word);
+    /// assert_eq!(set, enums![TextStyle::Italic, TextStyle::Strikeout]);
+    /// assert_eq!(set.len(), 2);
+    /// ```
+    pub fn map_words<F: FnMut(usize, u64) -> u64>(&mut self, mut f: F) {
+        let raw = (0..T::Rep::WORDS).fold(self.raw, |raw, i| {
+            let word = f(i, Wordlike::word(raw, i));
+            raw.with_word(i, word)
+        });
+        self.raw = raw & T::BITMASK;
     }
 
-    /// Returns `true` if `self` has no elements in common with `other`.
-    /// This is equivalent to checking for an empty intersection.
+    /// Converts the set into a set of another enum whose representation is at least as wide,
+    /// mapping each value to the value of `U` with the same [`index`](Enum::index).
+    ///
+    /// This is meant for upgrading sets, such as persisted raw masks, after an enum has gained
+    /// variants at the end. The conversion only compiles if every `T::Rep` fits in a `U::Rep`;
+    /// use [`truncate`](Self::truncate) to go the other way.
     ///
     /// # Examples
     ///
@@ -313,24 +1319,26 @@ where
     /// use enumeration::{Enum, EnumSet, enums};
     ///
     /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
-    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    /// pub enum TextStyleV1 { Bold, Italic, Strikeout, Underline }
     ///
-    /// let a = enums![TextStyle::Blink, TextStyle::Bold, TextStyle::Italic];
-    /// let mut b = EnumSet::new();
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline, B1, B2, B3, B4, Overline }
     ///
-    /// assert_eq!(a.is_disjoint(&b), true);
-    /// b.insert(TextStyle::Highlight);
-    /// assert_eq!(a.is_disjoint(&b), true);
-    /// b.insert(TextStyle::Blink);
-    /// assert_eq!(a.is_disjoint(&b), false);
+    /// let old = enums![TextStyleV1::Bold, TextStyleV1::Underline];
+    /// let new: EnumSet<TextStyle> = old.widen();
+    /// assert_eq!(new, enums![TextStyle::Bold, TextStyle::Underline]);
     /// ```
     #[inline]
-    pub fn is_disjoint(&self, other: &Self) -> bool {
-        self.raw & other.raw == Wordlike::ZERO
+    pub fn widen<U: Enum>(&self) -> EnumSet<U>
+    where
+        U::Rep: From<T::Rep>,
+    {
+        EnumSet::from_raw(self.raw.into())
     }
 
-    /// Returns `true` if the set is a subset of another,
-    /// i.e., `other` contains at least all the values in `self`.
+    /// Converts the set into a set of another enum, mapping each value to the value of `U`
+    /// with the same [`index`](Enum::index) and dropping values whose index is out of range
+    /// for `U`.
     ///
     /// # Examples
     ///
@@ -338,24 +1346,32 @@ where
     /// use enumeration::{Enum, EnumSet, enums};
     ///
     /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
-    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
     ///
-    /// let sup = enums![TextStyle::Blink, TextStyle::Bold, TextStyle::Italic];
-    /// let mut set = EnumSet::new();
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum BasicStyle { Bold, Italic }
     ///
-    /// assert_eq!(set.is_subset(&sup), true);
-    /// set.insert(TextStyle::Blink);
-    /// assert_eq!(set.is_subset(&sup), true);
-    /// set.insert(TextStyle::Highlight);
-    /// assert_eq!(set.is_subset(&sup), false);
+    /// let set = enums![TextStyle::Italic, TextStyle::Underline];
+    /// assert_eq!(set.truncate::<BasicStyle>(), enums![BasicStyle::Italic]);
+    /// assert_eq!(set.try_truncate::<BasicStyle>(), None);
     /// ```
-    #[inline]
-    pub fn is_subset(&self, other: &Self) -> bool {
-        self.raw | other.raw == other.raw
+    pub fn truncate<U: Enum>(&self) -> EnumSet<U> {
+        let raw = self
+            .words()
+            .enumerate()
+            .take(U::Rep::WORDS)
+            .fold(U::Rep::ZERO, |raw, (i, word)| raw.with_word(i, word));
+        EnumSet::from_raw(raw)
     }
 
-    /// Returns `true` if the set is a superset of another,
-    /// i.e., `self` contains at least all the values in `other`.
+    /// Like [`truncate`](Self::truncate), but returns `None` instead of dropping values.
+    pub fn try_truncate<U: Enum>(&self) -> Option<EnumSet<U>> {
+        let truncated = self.truncate();
+        (truncated.len() == self.len()).then_some(truncated)
+    }
+
+    /// Returns the bits of the set for the values whose index is less than 128, discarding
+    /// the rest.
     ///
     /// # Examples
     ///
@@ -363,26 +1379,32 @@ where
     /// use enumeration::{Enum, EnumSet, enums};
     ///
     /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
-    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
-    ///
-    /// let sub = enums![TextStyle::Blink, TextStyle::Bold];
-    /// let mut set = EnumSet::new();
-    ///
-    /// assert_eq!(set.is_superset(&sub), false);
-    ///
-    /// set.insert(TextStyle::Blink);
-    /// set.insert(TextStyle::Highlight);
-    /// assert_eq!(set.is_superset(&sub), false);
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
     ///
-    /// set.insert(TextStyle::Bold);
-    /// assert_eq!(set.is_superset(&sub), true);
+    /// assert_eq!(enums![TextStyle::Bold, TextStyle::Underline].to_u128_truncated(), 0b1001);
     /// ```
     #[inline]
-    pub fn is_superset(&self, other: &Self) -> bool {
-        self.raw | other.raw == self.raw
+    pub fn to_u128_truncated(&self) -> u128 {
+        u128::from(Wordlike::word(self.raw, 0)) | u128::from(Wordlike::word(self.raw, 1)) << 64
     }
 
-    /// Adds a value to the set.
+    /// Returns the bits of the set as a `u128`, or `None` if the set contains a value whose
+    /// index is 128 or more.
+    #[inline]
+    pub fn try_to_u128(&self) -> Option<u128> {
+        self.words()
+            .skip(2)
+            .all(|word| word == 0)
+            .then(|| self.to_u128_truncated())
+    }
+
+    /// Returns a hash of the set that is the same on every platform, process and build.
+    ///
+    /// Unlike [`Hash`], the result does not depend on a [`Hasher`](std::hash::Hasher), so it
+    /// can be used for cache keys shared between processes or written to disk. The algorithm is
+    /// 64-bit FNV-1a over the little-endian bytes of each of [`words`](Self::words), in order.
+    /// It changes if the enum gains enough variants to widen its representation to another
+    /// word.
     ///
     /// # Examples
     ///
@@ -390,19 +1412,25 @@ where
     /// use enumeration::{Enum, EnumSet, enums};
     ///
     /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
-    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
-    ///
-    /// let mut set = enums![TextStyle::Blink];
-    /// set.insert(TextStyle::Bold);
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
     ///
-    /// assert_eq!(set, enums![TextStyle::Blink, TextStyle::Bold]);
+    /// assert_eq!(EnumSet::<TextStyle>::new().stable_hash64(), 0xa8c7_f832_281a_39c5);
+    /// assert_eq!(enums![TextStyle::Bold].stable_hash64(), 0x89cd_3129_1d2a_efa4);
     /// ```
-    #[inline]
-    pub fn insert(&mut self, x: T) {
-        self.raw |= x.bit();
+    pub fn stable_hash64(&self) -> u64 {
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0000_0100_0000_01b3;
+        self.words()
+            .flat_map(u64::to_le_bytes)
+            .fold(OFFSET_BASIS, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+            })
     }
 
-    /// Removes a value from the set.
+    /// Clears bits that do not correspond to any value of `T`.
+    ///
+    /// Sets built by any method other than [`from_raw_unchecked`](Self::from_raw_unchecked) are
+    /// always normalized, so this is only needed for sets built from untrusted raw bits.
     ///
     /// # Examples
     ///
@@ -410,28 +1438,20 @@ where
     /// use enumeration::{Enum, EnumSet, enums};
     ///
     /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
-    /// pub enum TextStyle { Blink, Bold, Highlight, Italic, Strikeout, Underline }
-    ///
-    /// let mut set = EnumSet::new();
+    /// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
     ///
-    /// set.insert(TextStyle::Blink);
-    /// set.insert(TextStyle::Bold);
-    /// set.remove(TextStyle::Bold);
-    /// assert_eq!(set, enums![TextStyle::Blink]);
+    /// let mut set = EnumSet::<TextStyle>::from_raw_unchecked(0b1000_0001);
+    /// assert_eq!(set.len(), 2);
+    /// set.normalize();
+    /// assert_eq!(set, enums![TextStyle::Bold]);
     /// ```
     #[inline]
-    pub fn remove(&mut self, x: T) {
-        self.raw &= !x.bit();
+    pub fn normalize(&mut self) {
+        self.raw &= T::BITMASK;
     }
 
     /// Returns the underlying bit representation of the enum flags. Intended for FFI.
     #[inline]
-    pub const fn from_raw(raw: T::Rep) -> Self {
-        Self { raw }
-    }
-
-    /// Constructs a set from the underlying bit representation of the enum flags. Intended for FFI.
-    #[inline]
     pub const fn to_raw(&self) -> T::Rep {
         self.raw
     }
@@ -461,11 +1481,59 @@ impl<T: Enum> PartialEq for EnumSet<T> {
 }
 impl<T: Enum> Eq for EnumSet<T> {}
 
+/// Compares the set with the elements of an array, ignoring order and duplicates.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::{Enum, EnumSet, enums};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
+///
+/// let set = enums![TextStyle::Bold, TextStyle::Underline];
+/// assert_eq!(set, [TextStyle::Underline, TextStyle::Bold, TextStyle::Bold]);
+/// assert_ne!(set, [TextStyle::Bold]);
+/// ```
+impl<T: Enum, const N: usize> PartialEq<[T; N]> for EnumSet<T> {
+    #[inline]
+    fn eq(&self, other: &[T; N]) -> bool {
+        *self == Self::from_iter(other)
+    }
+}
+
+/// Compares the set with the elements of a slice, ignoring order and duplicates.
+impl<T: Enum> PartialEq<[T]> for EnumSet<T> {
+    #[inline]
+    fn eq(&self, other: &[T]) -> bool {
+        *self == Self::from_iter(other)
+    }
+}
+
+/// Compares the set with the elements of a slice, ignoring order and duplicates.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::{Enum, EnumSet, enums};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum TextStyle { Bold, Italic, Strikeout, Underline }
+///
+/// let styles = vec![TextStyle::Italic, TextStyle::Bold];
+/// assert_eq!(enums![TextStyle::Bold, TextStyle::Italic], styles.as_slice());
+/// ```
+impl<T: Enum> PartialEq<&[T]> for EnumSet<T> {
+    #[inline]
+    fn eq(&self, other: &&[T]) -> bool {
+        *self == Self::from_iter(*other)
+    }
+}
+
 impl<T: Enum> PartialOrd for EnumSet<T> {
-    #[allow(clippy::non_canonical_partial_ord_impl)]
     #[inline]
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.raw.partial_cmp(&other.raw)
+        Some(self.cmp(other))
     }
 }
 
@@ -502,12 +1570,57 @@ impl<T: Enum> Default for EnumSet<T> {
     }
 }
 
+/// Formats the values of a set as a plain list, even in alternate mode.
+struct Entries<T: Enum>(EnumSet<T>);
+
+impl<T: Enum + Debug> Debug for Entries<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_list().entries(self.0).finish()
+    }
+}
+
 impl<T: Enum> Debug for EnumSet<T>
 where
     T: Debug,
 {
+    /// Formats the set as a list of its values.
+    ///
+    /// The alternate form (`{:#?}`) also shows the length, the capacity, and the values that
+    /// are absent from the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum TextStyle { Bold, Italic, Underline }
+    ///
+    /// let set = enums![TextStyle::Italic];
+    /// assert_eq!(format!("{set:?}"), "[Italic]");
+    /// assert_eq!(format!("{set:#?}"), "\
+    /// EnumSet {
+    ///     len: 1,
+    ///     capacity: 3,
+    ///     present: [
+    ///         Italic,
+    ///     ],
+    ///     absent: [
+    ///         Bold,
+    ///         Underline,
+    ///     ],
+    /// }");
+    /// ```
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        f.debug_list().entries(*self).finish()
+        if !f.alternate() {
+            return Entries(*self).fmt(f);
+        }
+        f.debug_struct("EnumSet")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .field("present", &Entries(*self))
+            .field("absent", &Entries(self.inverse()))
+            .finish()
     }
 }
 
@@ -529,7 +1642,7 @@ macro_rules! bitop {
             #[inline]
             fn $f(self, other: T) -> Self::Output {
                 Self {
-                    raw: self.raw.$f(other.bit()),
+                    raw: self.raw.$f(bit_of(other)),
                 }
             }
         }
@@ -546,7 +1659,7 @@ macro_rules! bitassign {
         impl<T: Enum> $t<T> for EnumSet<T> {
             #[inline]
             fn $f(&mut self, other: T) {
-                self.raw.$f(other.bit())
+                self.raw.$f(bit_of(other))
             }
         }
     };
@@ -564,7 +1677,7 @@ impl<T: Enum> FromIterator<T> for EnumSet<T> {
         Self {
             raw: iter
                 .into_iter()
-                .map(T::bit)
+                .map(bit_of)
                 .fold(Wordlike::ZERO, BitOr::bitor),
         }
     }
@@ -576,7 +1689,7 @@ impl<'a, T: Enum> FromIterator<&'a T> for EnumSet<T> {
         Self {
             raw: iter
                 .into_iter()
-                .map(|&x| T::bit(x))
+                .map(|&x| bit_of(x))
                 .fold(Wordlike::ZERO, BitOr::bitor),
         }
     }
@@ -589,6 +1702,25 @@ impl<T: Enum, const N: usize> From<[T; N]> for EnumSet<T> {
     }
 }
 
+impl<T: Enum> From<&[T]> for EnumSet<T> {
+    #[inline]
+    fn from(value: &[T]) -> Self {
+        Self::from_iter(value)
+    }
+}
+
+/// Fails on the first duplicate, unlike the infallible conversion from a slice, which ignores
+/// them.
+#[cfg(feature = "alloc")]
+impl<T: Enum> TryFrom<Vec<T>> for EnumSet<T> {
+    type Error = DuplicateError<T>;
+
+    #[inline]
+    fn try_from(value: Vec<T>) -> Result<Self, Self::Error> {
+        Self::with_policy(value, DuplicatePolicy::ErrorOnDuplicate)
+    }
+}
+
 impl<T: Enum> IntoIterator for EnumSet<T> {
     type Item = T;
     type IntoIter = Iter<T>;
@@ -599,6 +1731,16 @@ impl<T: Enum> IntoIterator for EnumSet<T> {
     }
 }
 
+impl<T: Enum> IntoIterator for &EnumSet<T> {
+    type Item = T;
+    type IntoIter = Iter<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl<T: Enum> Extend<T> for EnumSet<T> {
     #[cfg_attr(feature = "inline-more", inline)]
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
@@ -617,6 +1759,114 @@ impl<'a, T: Enum> Extend<&'a T> for EnumSet<T> {
     }
 }
 
+/// Sums sets by taking their union. The sum of no sets is the empty set.
+impl<T: Enum> Sum for EnumSet<T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(), |acc, set| acc | set)
+    }
+}
+
+impl<'a, T: Enum> Sum<&'a EnumSet<T>> for EnumSet<T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+/// Multiplies sets by taking their intersection. The product of no sets is the set of all
+/// values.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::{Enum, EnumSet, enums};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Right { Read, Write, Share }
+///
+/// let roles = [enums![Right::Read, Right::Write], enums![Right::Read, Right::Share]];
+/// assert_eq!(roles.iter().product::<EnumSet<Right>>(), enums![Right::Read]);
+/// assert_eq!(roles.iter().sum::<EnumSet<Right>>(), EnumSet::all());
+///
+/// let no_roles: [EnumSet<Right>; 0] = [];
+/// assert_eq!(no_roles.into_iter().product::<EnumSet<Right>>(), EnumSet::all());
+/// ```
+impl<T: Enum> Product for EnumSet<T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::all(), |acc, set| acc & set)
+    }
+}
+
+impl<'a, T: Enum> Product<&'a EnumSet<T>> for EnumSet<T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
+/// A builder for an [`EnumSet`], created by [`EnumSet::builder`].
+#[must_use = "call `build` to get the set"]
+pub struct EnumSetBuilder<T: Enum> {
+    set: EnumSet<T>,
+}
+
+impl<T: Enum> EnumSetBuilder<T> {
+    /// Adds a value.
+    #[inline]
+    pub fn with(self, x: T) -> Self {
+        Self {
+            set: self.set.with(x),
+        }
+    }
+
+    /// Adds a value if `condition` is `true`.
+    #[inline]
+    pub fn with_if(self, condition: bool, x: T) -> Self {
+        Self {
+            set: self.set.with_if(condition, x),
+        }
+    }
+
+    /// Removes a value.
+    #[inline]
+    pub fn without(self, x: T) -> Self {
+        Self {
+            set: self.set.without(x),
+        }
+    }
+
+    /// Returns the built set.
+    #[inline]
+    pub const fn build(self) -> EnumSet<T> {
+        self.set
+    }
+}
+
+impl<T: Enum> Copy for EnumSetBuilder<T> {}
+
+#[allow(clippy::expl_impl_clone_on_copy)]
+impl<T: Enum> Clone for EnumSetBuilder<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Enum> Default for EnumSetBuilder<T> {
+    #[inline]
+    fn default() -> Self {
+        EnumSet::builder()
+    }
+}
+
+impl<T: Enum + Debug> Debug for EnumSetBuilder<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("EnumSetBuilder").field(&self.set).finish()
+    }
+}
+
 #[doc(hidden)]
 pub mod __private {
     use super::{Enum, EnumSet};
@@ -625,6 +1875,11 @@ pub mod __private {
     pub const fn construct_set<T: Enum>(raw: T::Rep, _type_holder: T) -> EnumSet<T> {
         EnumSet { raw }
     }
+
+    #[inline]
+    pub const fn bitmask<T: Enum>(_sets: &[EnumSet<T>]) -> T::Rep {
+        T::BITMASK
+    }
 }
 
 #[macro_export]
@@ -644,6 +1899,139 @@ macro_rules! enums {
     });
 }
 
+/// Fails to compile unless the given sets, which must be constants, have no elements in
+/// common.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::{Enum, EnumSet, assert_disjoint, enums};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Opcode { Get, Put, Ack, Nak }
+///
+/// const REQUESTS: EnumSet<Opcode> = EnumSet::from_raw_unchecked(0b0011);
+/// const RESPONSES: EnumSet<Opcode> = EnumSet::from_raw_unchecked(0b1100);
+///
+/// assert_disjoint!(REQUESTS, RESPONSES);
+/// ```
+///
+/// ```compile_fail
+/// # use enumeration::{Enum, EnumSet, assert_disjoint};
+/// # #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// # pub enum Opcode { Get, Put, Ack, Nak }
+/// const REQUESTS: EnumSet<Opcode> = EnumSet::from_raw_unchecked(0b0011);
+/// const RESPONSES: EnumSet<Opcode> = EnumSet::from_raw_unchecked(0b1110);
+///
+/// assert_disjoint!(REQUESTS, RESPONSES);
+/// ```
+#[macro_export]
+macro_rules! assert_disjoint {
+    ($($set:expr),+ $(,)?) => {
+        const _: () = {
+            // Checks that the sets have the same type, and gives `seen` their representation.
+            let sets = [$($set),+];
+            let mut seen = 0 & $crate::__private::bitmask(&sets);
+            $(
+                ::core::assert!(
+                    seen & $set.to_raw() == 0,
+                    ::core::concat!("`", ::core::stringify!($set), "` overlaps an earlier set"),
+                );
+                seen |= $set.to_raw();
+            )+
+        };
+    };
+}
+
+/// Fails to compile unless the union of the given sets, which must be constants, contains every
+/// value of their element type.
+///
+/// Combined with [`assert_disjoint!`], this checks that every value belongs to exactly one of the
+/// sets.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::{Enum, EnumSet, assert_covers_all, assert_disjoint};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Opcode { Get, Put, Ack, Nak }
+///
+/// const REQUESTS: EnumSet<Opcode> = EnumSet::from_raw_unchecked(0b0011);
+/// const RESPONSES: EnumSet<Opcode> = EnumSet::from_raw_unchecked(0b1100);
+///
+/// assert_covers_all!(REQUESTS, RESPONSES);
+/// assert_disjoint!(REQUESTS, RESPONSES);
+/// ```
+///
+/// ```compile_fail
+/// # use enumeration::{Enum, EnumSet, assert_covers_all};
+/// # #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// # pub enum Opcode { Get, Put, Ack, Nak }
+/// const REQUESTS: EnumSet<Opcode> = EnumSet::from_raw_unchecked(0b0011);
+/// const RESPONSES: EnumSet<Opcode> = EnumSet::from_raw_unchecked(0b0100);
+///
+/// assert_covers_all!(REQUESTS, RESPONSES);
+/// ```
+#[macro_export]
+macro_rules! assert_covers_all {
+    ($($set:expr),+ $(,)?) => {
+        const _: () = {
+            let sets = [$($set),+];
+            let all = $crate::__private::bitmask(&sets);
+            ::core::assert!(
+                all & !(0 $(| $set.to_raw())+) == 0,
+                "the sets do not cover every value",
+            );
+        };
+    };
+}
+
+/// Dispatches on the only element of a set. The final `_` arm is required, and runs if the set
+/// does not have exactly one element or if no other arm matches.
+///
+/// Every arm but the last must end with a comma.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::{Enum, EnumSet, enums, match_single};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum State { Idle, Running, Stopped }
+///
+/// fn describe(state: EnumSet<State>) -> &'static str {
+///     match_single!(state, {
+///         State::Idle => "waiting",
+///         State::Running | State::Stopped => "started",
+///         _ => "ambiguous",
+///     })
+/// }
+///
+/// assert_eq!(describe(enums![State::Idle]), "waiting");
+/// assert_eq!(describe(enums![State::Stopped]), "started");
+/// assert_eq!(describe(enums![State::Idle, State::Running]), "ambiguous");
+/// assert_eq!(describe(EnumSet::new()), "ambiguous");
+/// ```
+#[macro_export]
+macro_rules! match_single {
+    (@arms $single:ident [$($arms:tt)*] _ => $fallback:expr $(,)?) => {
+        match $single {
+            $($arms)*
+            _ => $fallback,
+        }
+    };
+    (@arms $single:ident [$($arms:tt)*] $pat:pat => $body:expr, $($rest:tt)*) => {
+        $crate::match_single!(
+            @arms $single [$($arms)* ::core::option::Option::Some($pat) => $body,] $($rest)*
+        )
+    };
+    ($set:expr, { $($arms:tt)* }) => {{
+        let single = $crate::EnumSet::exactly_one(&$set);
+        $crate::match_single!(@arms single [] $($arms)*)
+    }};
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -682,4 +2070,238 @@ mod tests {
         ];
         assert_eq!(to_vec(set.inverse()), to_vec(inverse));
     }
+
+    #[test]
+    fn test_indices_roundtrip() {
+        for set in all_sets() {
+            assert_eq!(EnumSet::from_indices(set.iter_indices()), set);
+            assert!(set
+                .iter_indices()
+                .rev()
+                .eq(set.into_iter().rev().map(Enum::index)));
+        }
+    }
+
+    #[test]
+    fn test_exactly_one() {
+        for set in all_sets() {
+            let expected = if set.len() == 1 {
+                set.into_iter().next()
+            } else {
+                None
+            };
+            assert_eq!(set.exactly_one(), expected, "{set:?}");
+            assert_eq!(set.is_single(), expected.is_some());
+        }
+    }
+
+    #[test]
+    fn test_navigation() {
+        for set in all_sets() {
+            let values = to_vec(set);
+            assert_eq!(
+                set.first_absent(),
+                DemoEnum::enumerate(..).find(|x| !values.contains(x))
+            );
+            for x in DemoEnum::enumerate(..) {
+                assert_eq!(
+                    set.next_present_after(x),
+                    DemoEnum::enumerate(x..)
+                        .skip(1)
+                        .find(|y| values.contains(y))
+                );
+                assert_eq!(
+                    set.next_absent_after(x),
+                    DemoEnum::enumerate(x..)
+                        .skip(1)
+                        .find(|y| !values.contains(y))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_stepping() {
+        let expected: Vec<_> = all_sets().collect();
+        assert_eq!(EnumSet::all_sets().collect::<Vec<_>>(), expected);
+        assert_eq!(
+            EnumSet::<DemoEnum>::all_sets().size_hint(),
+            (expected.len(), Some(expected.len()))
+        );
+        assert!(EnumSet::all_sets().rev().eq(expected.iter().copied().rev()));
+        for pair in expected.windows(2) {
+            assert_eq!(pair[0].next_set(), Some(pair[1]));
+            assert_eq!(pair[1].prev_set(), Some(pair[0]));
+        }
+        assert_eq!(EnumSet::<DemoEnum>::all().next_set(), None);
+        assert_eq!(EnumSet::<DemoEnum>::new().prev_set(), None);
+        assert_eq!(EnumSet::<bool>::all_sets().size_hint(), (4, Some(4)));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_error_on_duplicate() {
+        let strict = DuplicatePolicy::ErrorOnDuplicate;
+        for set in all_sets() {
+            let values: Vec<_> = set.into_iter().collect();
+            assert_eq!(EnumSet::from(values.as_slice()), set);
+            assert_eq!(EnumSet::with_policy(values.clone(), strict), Ok(set));
+            if let Some(&last) = values.last() {
+                let position = values.len();
+                let mut repeated = values;
+                repeated.push(last);
+                assert_eq!(EnumSet::from(repeated.as_slice()), set);
+                assert_eq!(
+                    EnumSet::with_policy(repeated, strict),
+                    Err(DuplicateError {
+                        value: last,
+                        position
+                    })
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_try_from_vec() {
+        for set in all_sets() {
+            let values: Vec<_> = set.into_iter().collect();
+            assert_eq!(EnumSet::try_from(values.clone()), Ok(set));
+            if let Some(&first) = values.first() {
+                let mut repeated = values;
+                repeated.insert(1, first);
+                assert_eq!(
+                    EnumSet::try_from(repeated),
+                    Err(DuplicateError {
+                        value: first,
+                        position: 1
+                    })
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_words_roundtrip() {
+        for set in all_sets() {
+            let words: Vec<_> = set.words().collect();
+            assert_eq!(words, [u64::from(set.to_raw())]);
+            assert_eq!(EnumSet::from_words(&words), set);
+        }
+    }
+
+    #[test]
+    fn test_wide_words() {
+        #[rustfmt::skip] #[allow(dead_code)]
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+        enum Wide {
+            A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, B0, B1, B2, B3, B4, B5, B6, B7, B8, B9,
+            C0, C1, C2, C3, C4, C5, C6, C7, C8, C9, D0, D1, D2, D3, D4, D5, D6, D7, D8, D9,
+            E0, E1, E2, E3, E4, E5, E6, E7, E8, E9, F0, F1, F2, F3, F4, F5, F6, F7, F8, F9,
+            G0, G1, G2, G3, G4, G5, G6, G7, G8, G9,
+        }
+
+        let set = enums![Wide::A0, Wide::G3, Wide::G9];
+        let words: Vec<_> = set.words().collect();
+        assert_eq!(words, [1 | (1 << 63), 1 << 5]);
+        assert_eq!(EnumSet::from_words(&words), set);
+        assert_eq!(EnumSet::<Wide>::from_words(&[0, u64::MAX]).len(), 6);
+        assert_eq!(set.inverse().words().nth(1), Some(0b01_1111));
+
+        let mut seen = Vec::new();
+        set.for_each_word(|i, word| seen.push((i, word)));
+        assert_eq!(seen, [(0, words[0]), (1, words[1])]);
+        let mut inverse = set;
+        inverse.map_words(|_, word| !word);
+        assert_eq!(inverse, set.inverse());
+
+        let narrow = set.truncate::<DemoEnum>();
+        assert_eq!(narrow, enums![DemoEnum::A]);
+        assert_eq!(set.try_truncate::<DemoEnum>(), None);
+        assert_eq!(narrow.widen::<Wide>(), enums![Wide::A0]);
+        assert_eq!(narrow.try_truncate::<Wide>(), Some(enums![Wide::A0]));
+        assert_eq!(set.try_to_u128(), Some(1 | (1 << 63) | (1 << 69)));
+    }
+
+    #[test]
+    fn test_truncate_same_enum() {
+        for set in all_sets() {
+            assert_eq!(set.truncate::<DemoEnum>(), set);
+            assert_eq!(set.widen::<DemoEnum>(), set);
+            assert_eq!(set.to_u128_truncated(), u128::from(set.to_raw()));
+        }
+    }
+
+    fn assert_normalized(set: EnumSet<DemoEnum>) {
+        assert_eq!(
+            set.to_raw() & !DemoEnum::BITMASK,
+            0,
+            "stray bits in {set:?}"
+        );
+        assert_eq!(set.len(), set.into_iter().count());
+    }
+
+    fn all_sets() -> impl Iterator<Item = EnumSet<DemoEnum>> {
+        (0..=DemoEnum::BITMASK).map(EnumSet::from_raw)
+    }
+
+    #[test]
+    fn test_from_raw_masks() {
+        for raw in 0..=u16::MAX {
+            let set = EnumSet::<DemoEnum>::from_raw(raw);
+            assert_normalized(set);
+            let mut unchecked = EnumSet::<DemoEnum>::from_raw_unchecked(raw);
+            unchecked.normalize();
+            assert_eq!(unchecked, set);
+        }
+    }
+
+    #[test]
+    fn test_unary_ops_normalized() {
+        for set in all_sets() {
+            assert_normalized(set);
+            assert_normalized(set.inverse());
+            assert_normalized(!set);
+            assert_eq!(set.len() + set.inverse().len(), DemoEnum::SIZE);
+            assert_eq!(set.iter_absent().collect::<EnumSet<_>>(), set.inverse());
+            assert_normalized(set.partition(|x| x.index() % 2 == 0).0);
+            assert_normalized(set.partition(|x| x.index() % 2 == 0).1);
+            for x in DemoEnum::enumerate(..) {
+                let mut inserted = set;
+                inserted.insert(x);
+                assert_normalized(inserted);
+                assert!(inserted.contains(x));
+                let mut removed = set;
+                removed.remove(x);
+                assert_normalized(removed);
+                assert!(!removed.contains(x));
+            }
+        }
+    }
+
+    #[test]
+    fn test_binary_ops_normalized() {
+        for a in all_sets() {
+            for b in all_sets() {
+                let union = a | b;
+                let intersection = a & b;
+                let symmetric_difference = a ^ b;
+                let difference = a.difference(&b);
+                assert_normalized(union);
+                assert_normalized(intersection);
+                assert_normalized(symmetric_difference);
+                assert_normalized(difference);
+                assert_eq!(union, a.union(&b));
+                assert_eq!(intersection, a.intersection(&b));
+                assert_eq!(symmetric_difference, a.symmetric_difference(&b));
+                assert_eq!(union.len() + intersection.len(), a.len() + b.len());
+                assert_eq!(symmetric_difference, union.difference(&intersection));
+                assert_eq!(difference, a & !b);
+                assert_eq!(a.is_subset(&b), union == b);
+                assert_eq!(a.is_superset(&b), intersection == b);
+                assert_eq!(a.is_disjoint(&b), intersection.is_empty());
+            }
+        }
+    }
 }
@@ -1,7 +1,8 @@
-use std::iter::{ExactSizeIterator, FusedIterator, Iterator};
+use core::iter::{ExactSizeIterator, FusedIterator, Iterator};
 
 use super::enum_set::EnumSet;
 use crate::enumerate::{Enum, Enumeration};
+use crate::wordlike::Wordlike;
 
 fn enum_fold<T: Enum, B, F>(set: EnumSet<T>, mut fold: F) -> impl FnMut(B, T) -> B
 where
@@ -19,7 +20,7 @@ where
 #[must_use = "iterators are lazy and do nothing unless consumed"]
 pub struct Iter<T: Enum> {
     set: EnumSet<T>,
-    iter: Enumeration<T>,
+    inner: Enumeration<T>,
     remaining: usize,
 }
 
@@ -27,7 +28,7 @@ impl<T: Enum> Iter<T> {
     #[cfg_attr(feature = "inline-more", inline)]
     pub(super) fn new(set: EnumSet<T>) -> Self {
         Self {
-            iter: T::enumerate(..),
+            inner: T::enumerate_all(..),
             remaining: set.len(),
             set,
         }
@@ -38,7 +39,7 @@ impl<T: Enum> Clone for Iter<T> {
     fn clone(&self) -> Self {
         Self {
             set: self.set,
-            iter: self.iter.clone(),
+            inner: self.inner.clone(),
             remaining: self.remaining,
         }
     }
@@ -50,7 +51,7 @@ impl<T: Enum> Iterator for Iter<T> {
     #[cfg_attr(feature = "inline-more", inline)]
     fn next(&mut self) -> Option<Self::Item> {
         let set = self.set;
-        let next = self.iter.find(move |&x| set.contains(x));
+        let next = self.inner.find(move |&x| set.contains(x));
         if next.is_some() {
             self.remaining -= 1;
         }
@@ -72,7 +73,7 @@ impl<T: Enum> Iterator for Iter<T> {
     where
         F: FnMut(B, Self::Item) -> B,
     {
-        self.iter.fold(init, enum_fold(self.set, fold))
+        self.inner.fold(init, enum_fold(self.set, fold))
     }
 }
 
@@ -87,7 +88,7 @@ impl<T: Enum> DoubleEndedIterator for Iter<T> {
     #[cfg_attr(feature = "inline-more", inline)]
     fn next_back(&mut self) -> Option<Self::Item> {
         let set = self.set;
-        let next = self.iter.rfind(move |&x| set.contains(x));
+        let next = self.inner.rfind(move |&x| set.contains(x));
         if next.is_some() {
             self.remaining -= 1;
         }
@@ -99,8 +100,168 @@ impl<T: Enum> DoubleEndedIterator for Iter<T> {
     where
         F: FnMut(B, Self::Item) -> B,
     {
-        self.iter.rfold(init, enum_fold(self.set, fold))
+        self.inner.rfold(init, enum_fold(self.set, fold))
     }
 }
 
 impl<T: Enum> FusedIterator for Iter<T> {}
+
+/// An iterator over the indices of the elements of an `EnumSet`.
+///
+/// This `struct` is created by the [`iter_indices`] method on [`EnumSet`].
+/// See its documentation for more.
+///
+/// [`iter_indices`]: crate::EnumSet::iter_indices
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Indices<T: Enum> {
+    inner: Iter<T>,
+}
+
+impl<T: Enum> Indices<T> {
+    #[inline]
+    pub(super) fn new(set: EnumSet<T>) -> Self {
+        Self {
+            inner: Iter::new(set),
+        }
+    }
+}
+
+impl<T: Enum> Clone for Indices<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: Enum> Iterator for Indices<T> {
+    type Item = usize;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(T::index)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.inner.count()
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn fold<B, F>(self, init: B, mut fold: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.inner.fold(init, |acc, x| fold(acc, x.index()))
+    }
+}
+
+impl<T: Enum> ExactSizeIterator for Indices<T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<T: Enum> DoubleEndedIterator for Indices<T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(T::index)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn rfold<B, F>(self, init: B, mut fold: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.inner.rfold(init, |acc, x| fold(acc, x.index()))
+    }
+}
+
+impl<T: Enum> FusedIterator for Indices<T> {}
+
+/// An iterator over every possible `EnumSet`, in increasing order of raw bits.
+///
+/// This `struct` is created by [`EnumSet::all_sets`]. See its documentation for more.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct AllSets<T: Enum> {
+    front: EnumSet<T>,
+    back: EnumSet<T>,
+    finished: bool,
+}
+
+impl<T: Enum> AllSets<T> {
+    #[inline]
+    pub(super) fn new() -> Self {
+        Self {
+            front: EnumSet::new(),
+            back: EnumSet::all(),
+            finished: false,
+        }
+    }
+}
+
+impl<T: Enum> Clone for AllSets<T> {
+    fn clone(&self) -> Self {
+        Self {
+            front: self.front,
+            back: self.back,
+            finished: self.finished,
+        }
+    }
+}
+
+impl<T: Enum> Iterator for AllSets<T> {
+    type Item = EnumSet<T>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let next = self.front;
+        if next == self.back {
+            self.finished = true;
+        } else {
+            self.front = EnumSet::from_raw_unchecked(next.to_raw().incr());
+        }
+        Some(next)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            return (0, Some(0));
+        }
+        let as_u128 = |set: EnumSet<T>| {
+            let raw = set.to_raw();
+            u128::from(Wordlike::word(raw, 0)) | u128::from(Wordlike::word(raw, 1)) << 64
+        };
+        match usize::try_from(as_u128(self.back) - as_u128(self.front)) {
+            Ok(n) if n < usize::MAX => (n + 1, Some(n + 1)),
+            _ => (usize::MAX, None),
+        }
+    }
+}
+
+impl<T: Enum> DoubleEndedIterator for AllSets<T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let next = self.back;
+        if next == self.front {
+            self.finished = true;
+        } else {
+            self.back = EnumSet::from_raw_unchecked(next.to_raw().decr());
+        }
+        Some(next)
+    }
+}
+
+impl<T: Enum> FusedIterator for AllSets<T> {}
@@ -0,0 +1,229 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+
+use super::enum_set::EnumSet;
+use crate::enumerate::Enum;
+
+/// A set of variant names that keeps names it does not recognize.
+///
+/// Names listed in [`Enum::NAMES`] are stored in an [`EnumSet`]; any other name is kept as a
+/// string. Writing the set back out produces the known names in order, followed by the unknown
+/// ones in the order they were first seen. Configuration tools can use this to avoid dropping
+/// flags written by a newer version of the enum.
+///
+/// With the `serde` feature, a `LossySet` (de)serializes as a sequence of names.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::{Enum, LossySet, enums};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Feature { Export, Import, Sync }
+///
+/// let mut features: LossySet<Feature> = LossySet::from_names(["Sync", "Audit", "Export"]);
+/// assert_eq!(features.known(), enums![Feature::Export, Feature::Sync]);
+/// assert_eq!(features.unknown(), ["Audit"]);
+///
+/// features.known_mut().remove(Feature::Sync);
+/// assert_eq!(features.names().collect::<Vec<_>>(), ["Export", "Audit"]);
+/// ```
+pub struct LossySet<T: Enum> {
+    known: EnumSet<T>,
+    unknown: Vec<String>,
+}
+
+impl<T: Enum> LossySet<T> {
+    /// Creates an empty set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, LossySet};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Feature { Export, Import, Sync }
+    ///
+    /// let set: LossySet<Feature> = LossySet::new();
+    /// assert!(set.is_empty());
+    /// ```
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            known: EnumSet::new(),
+            unknown: Vec::new(),
+        }
+    }
+
+    /// Builds a set from variant names. Names that are not in [`Enum::NAMES`] are kept as
+    /// unknown names. Repeated names are only kept once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, LossySet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Feature { Export, Import, Sync }
+    ///
+    /// let set: LossySet<Feature> = LossySet::from_names(["Import", "Audit", "Audit"]);
+    /// assert_eq!(set.known(), enums![Feature::Import]);
+    /// assert_eq!(set.unknown(), ["Audit"]);
+    /// ```
+    pub fn from_names<I>(names: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut set = Self::new();
+        for name in names {
+            set.insert_name(name.as_ref());
+        }
+        set
+    }
+
+    /// Adds a value to the set by name. Returns whether the name was not already present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, LossySet};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Feature { Export, Import, Sync }
+    ///
+    /// let mut set: LossySet<Feature> = LossySet::new();
+    /// assert!(set.insert_name("Sync"));
+    /// assert!(set.insert_name("Audit"));
+    /// assert!(!set.insert_name("Audit"));
+    /// assert_eq!(set.len(), 2);
+    /// ```
+    pub fn insert_name(&mut self, name: &str) -> bool {
+        if let Some(value) = T::from_name(name) {
+            let added = !self.known.contains(value);
+            self.known.insert(value);
+            return added;
+        }
+        if self.unknown.iter().any(|unknown| unknown == name) {
+            return false;
+        }
+        self.unknown.push(name.to_owned());
+        true
+    }
+
+    /// Returns the recognized values.
+    #[inline]
+    pub const fn known(&self) -> EnumSet<T> {
+        self.known
+    }
+
+    /// Returns a mutable reference to the recognized values.
+    #[inline]
+    pub fn known_mut(&mut self) -> &mut EnumSet<T> {
+        &mut self.known
+    }
+
+    /// Returns the unrecognized names, in the order they were first seen.
+    #[inline]
+    pub fn unknown(&self) -> &[String] {
+        &self.unknown
+    }
+
+    /// Returns the number of known values plus the number of unknown names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, LossySet};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Feature { Export, Import, Sync }
+    ///
+    /// let set: LossySet<Feature> = LossySet::from_names(["Export", "Audit"]);
+    /// assert_eq!(set.len(), 2);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.known.len() + self.unknown.len()
+    }
+
+    /// Returns `true` if the set contains neither known values nor unknown names.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.known.is_empty() && self.unknown.is_empty()
+    }
+
+    /// An iterator visiting the names of the known values in order, followed by the unknown
+    /// names. Known values without a name in [`Enum::NAMES`] are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, LossySet};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Feature { Export, Import, Sync }
+    ///
+    /// let set: LossySet<Feature> = LossySet::from_names(["Audit", "Sync", "Export"]);
+    /// assert_eq!(set.names().collect::<Vec<_>>(), ["Export", "Sync", "Audit"]);
+    /// ```
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.known
+            .into_iter()
+            .filter_map(|x| -> Option<&str> { x.name() })
+            .chain(self.unknown.iter().map(String::as_str))
+    }
+
+    /// Splits the set into its known values and unknown names.
+    #[inline]
+    pub fn into_parts(self) -> (EnumSet<T>, Vec<String>) {
+        (self.known, self.unknown)
+    }
+}
+
+impl<T: Enum> Clone for LossySet<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            known: self.known,
+            unknown: self.unknown.clone(),
+        }
+    }
+}
+
+impl<T: Enum> Default for LossySet<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Enum> PartialEq for LossySet<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.known == other.known && self.unknown == other.unknown
+    }
+}
+
+impl<T: Enum> Eq for LossySet<T> {}
+
+impl<T: Enum + Debug> Debug for LossySet<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("LossySet")
+            .field("known", &self.known)
+            .field("unknown", &self.unknown)
+            .finish()
+    }
+}
+
+impl<T: Enum> From<EnumSet<T>> for LossySet<T> {
+    #[inline]
+    fn from(known: EnumSet<T>) -> Self {
+        Self {
+            known,
+            unknown: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,346 @@
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::ops::Not;
+
+use super::enum_set::EnumSet;
+use super::iter::Iter;
+use crate::enumerate::Enum;
+
+/// A set whose complement is taken relative to a fixed universe rather than all of `T`.
+///
+/// This is created by [`EnumSet::restricted_to`]. It is useful when only some values of an
+/// enum are legal in a given context: inverting a `MaskedSet` never produces a value outside
+/// its universe, and values outside the universe can never be inserted.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::{Enum, EnumSet, enums};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Feature { Export, Import, Sync, Audit }
+///
+/// let basic_tier = enums![Feature::Export, Feature::Import];
+/// let enabled = enums![Feature::Export].restricted_to(basic_tier);
+///
+/// // Only features of the tier are reported as missing.
+/// assert_eq!(enabled.complement().to_set(), enums![Feature::Import]);
+/// ```
+pub struct MaskedSet<T: Enum> {
+    set: EnumSet<T>,
+    universe: EnumSet<T>,
+}
+
+impl<T: Enum> MaskedSet<T> {
+    #[inline]
+    pub(super) fn new(set: EnumSet<T>, universe: EnumSet<T>) -> Self {
+        Self {
+            set: set & universe,
+            universe,
+        }
+    }
+
+    /// Returns the universe the set is restricted to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Feature { Export, Import, Sync, Audit }
+    ///
+    /// let universe = enums![Feature::Export, Feature::Sync];
+    /// let set = EnumSet::new().restricted_to(universe);
+    /// assert_eq!(set.universe(), universe);
+    /// ```
+    #[inline]
+    pub const fn universe(&self) -> EnumSet<T> {
+        self.universe
+    }
+
+    /// Returns the values of the set as a plain [`EnumSet`].
+    ///
+    /// Values that were outside the universe when the view was created are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Feature { Export, Import, Sync, Audit }
+    ///
+    /// let set = enums![Feature::Export, Feature::Audit];
+    /// let masked = set.restricted_to(enums![Feature::Export, Feature::Import]);
+    /// assert_eq!(masked.to_set(), enums![Feature::Export]);
+    /// ```
+    #[inline]
+    pub const fn to_set(&self) -> EnumSet<T> {
+        self.set
+    }
+
+    /// Returns the number of elements in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Feature { Export, Import, Sync, Audit }
+    ///
+    /// let masked = EnumSet::all().restricted_to(enums![Feature::Export, Feature::Import]);
+    /// assert_eq!(masked.len(), 2);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    /// Returns `true` if the set contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Feature { Export, Import, Sync, Audit }
+    ///
+    /// let masked = enums![Feature::Audit].restricted_to(enums![Feature::Export]);
+    /// assert!(masked.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    /// Returns `true` if the set contains every value of its universe.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Feature { Export, Import, Sync, Audit }
+    ///
+    /// let mut masked = enums![Feature::Export].restricted_to(enums![Feature::Export, Feature::Sync]);
+    /// assert!(!masked.is_full());
+    /// masked.insert(Feature::Sync);
+    /// assert!(masked.is_full());
+    /// ```
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.set == self.universe
+    }
+
+    /// Returns `true` if the set contains a value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Feature { Export, Import, Sync, Audit }
+    ///
+    /// let masked = enums![Feature::Export].restricted_to(EnumSet::all());
+    /// assert!(masked.contains(Feature::Export));
+    /// assert!(!masked.contains(Feature::Sync));
+    /// ```
+    #[inline]
+    pub fn contains(&self, x: T) -> bool {
+        self.set.contains(x)
+    }
+
+    /// Adds a value to the set if it belongs to the universe.
+    ///
+    /// Returns whether the value belongs to the universe. If it does not, the set is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Feature { Export, Import, Sync, Audit }
+    ///
+    /// let mut masked = EnumSet::new().restricted_to(enums![Feature::Export]);
+    /// assert!(masked.insert(Feature::Export));
+    /// assert!(!masked.insert(Feature::Audit));
+    /// assert_eq!(masked.to_set(), enums![Feature::Export]);
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, x: T) -> bool {
+        let allowed = self.universe.contains(x);
+        if allowed {
+            self.set.insert(x);
+        }
+        allowed
+    }
+
+    /// Removes a value from the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Feature { Export, Import, Sync, Audit }
+    ///
+    /// let mut masked = EnumSet::all().restricted_to(enums![Feature::Export, Feature::Sync]);
+    /// masked.remove(Feature::Export);
+    /// assert_eq!(masked.to_set(), enums![Feature::Sync]);
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, x: T) {
+        self.set.remove(x);
+    }
+
+    /// Returns the values of the universe that are not in the set, as a view over the same
+    /// universe.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Feature { Export, Import, Sync, Audit }
+    ///
+    /// let universe = enums![Feature::Export, Feature::Import, Feature::Sync];
+    /// let masked = enums![Feature::Import].restricted_to(universe);
+    /// assert_eq!(masked.complement().to_set(), enums![Feature::Export, Feature::Sync]);
+    /// assert_eq!(!masked, masked.complement());
+    /// ```
+    #[inline]
+    #[must_use = "newly constructed set is unused"]
+    pub fn complement(&self) -> Self {
+        Self {
+            set: self.universe.difference(&self.set),
+            universe: self.universe,
+        }
+    }
+
+    /// An iterator visiting all elements in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Feature { Export, Import, Sync, Audit }
+    ///
+    /// let masked = EnumSet::all().restricted_to(enums![Feature::Sync, Feature::Import]);
+    /// let values: Vec<_> = masked.iter().collect();
+    /// assert_eq!(values, [Feature::Import, Feature::Sync]);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<T> {
+        self.set.into_iter()
+    }
+
+    /// An iterator visiting the values of the universe not contained by this set, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Feature { Export, Import, Sync, Audit }
+    ///
+    /// let masked = enums![Feature::Export].restricted_to(enums![Feature::Export, Feature::Sync]);
+    /// let absent: Vec<_> = masked.iter_absent().collect();
+    /// assert_eq!(absent, [Feature::Sync]);
+    /// ```
+    #[inline]
+    pub fn iter_absent(&self) -> Iter<T> {
+        self.complement().iter()
+    }
+}
+
+impl<T: Enum> Copy for MaskedSet<T> {}
+
+#[allow(clippy::expl_impl_clone_on_copy)]
+impl<T: Enum> Clone for MaskedSet<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Enum> PartialEq for MaskedSet<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.set == other.set && self.universe == other.universe
+    }
+}
+
+impl<T: Enum> Eq for MaskedSet<T> {}
+
+impl<T: Enum> Hash for MaskedSet<T>
+where
+    T::Rep: Hash,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.set.hash(state);
+        self.universe.hash(state);
+    }
+}
+
+impl<T: Enum> Not for MaskedSet<T> {
+    type Output = Self;
+
+    #[inline]
+    fn not(self) -> Self::Output {
+        self.complement()
+    }
+}
+
+impl<T: Enum + Debug> Debug for MaskedSet<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_list().entries(self.set).finish()
+    }
+}
+
+impl<T: Enum> From<MaskedSet<T>> for EnumSet<T> {
+    #[inline]
+    fn from(value: MaskedSet<T>) -> Self {
+        value.set
+    }
+}
+
+impl<T: Enum> IntoIterator for MaskedSet<T> {
+    type Item = T;
+    type IntoIter = Iter<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Enum> IntoIterator for &MaskedSet<T> {
+    type Item = T;
+    type IntoIter = Iter<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Enum> Extend<T> for MaskedSet<T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.insert(item);
+        }
+    }
+}
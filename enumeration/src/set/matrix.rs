@@ -0,0 +1,437 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::iter::{DoubleEndedIterator, ExactSizeIterator, Iterator};
+use core::marker::PhantomData;
+
+use super::enum_set::EnumSet;
+use crate::enumerate::Enum;
+
+/// A bit matrix with rows keyed by `R` and columns keyed by `C`.
+///
+/// Each row is stored as a single [`EnumSet<C>`], so the matrix occupies `R::SIZE` words of
+/// `C::Rep`. Like [`EnumMap`], the matrix does not allocate until a bit is first set.
+///
+/// [`EnumMap`]: crate::EnumMap
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::{Enum, EnumMatrix, EnumSet, enums};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Role { Guest, Member, Admin }
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Permission { Read, Write, Delete }
+///
+/// let mut acl = EnumMatrix::new();
+/// acl.set(Role::Guest, Permission::Read);
+/// acl.set(Role::Member, Permission::Read);
+/// acl.set(Role::Member, Permission::Write);
+/// acl.set_row(Role::Admin, EnumSet::all());
+///
+/// assert!(acl.contains(Role::Member, Permission::Write));
+/// assert!(!acl.contains(Role::Guest, Permission::Delete));
+/// assert_eq!(acl.row(Role::Member), enums![Permission::Read, Permission::Write]);
+/// assert_eq!(acl.column(Permission::Write), enums![Role::Member, Role::Admin]);
+/// ```
+pub struct EnumMatrix<R: Enum, C: Enum> {
+    rows: Vec<EnumSet<C>>,
+    marker: PhantomData<R>,
+}
+
+impl<R: Enum, C: Enum> EnumMatrix<R, C> {
+    /// Creates an empty `EnumMatrix`.
+    ///
+    /// The matrix is initially created with a capacity of 0, so it will not allocate until a
+    /// bit is first set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMatrix;
+    ///
+    /// let matrix: EnumMatrix<Ordering, bool> = EnumMatrix::new();
+    /// assert!(matrix.is_empty());
+    /// ```
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            rows: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn allocate(&mut self) {
+        if self.rows.is_empty() {
+            self.rows.resize_with(R::SIZE, EnumSet::new);
+        }
+    }
+
+    /// Returns the number of set bits in the matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMatrix;
+    ///
+    /// let mut matrix = EnumMatrix::new();
+    /// matrix.set(Ordering::Less, true);
+    /// matrix.set(Ordering::Greater, false);
+    /// assert_eq!(matrix.len(), 2);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.rows.iter().map(EnumSet::len).sum()
+    }
+
+    /// Returns `true` if no bits are set in the matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMatrix;
+    ///
+    /// let mut matrix = EnumMatrix::new();
+    /// assert!(matrix.is_empty());
+    /// matrix.set(Ordering::Less, true);
+    /// assert!(!matrix.is_empty());
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.rows.iter().all(EnumSet::is_empty)
+    }
+
+    /// Clears the matrix, unsetting all bits. Keeps the allocated memory for reuse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMatrix;
+    ///
+    /// let mut matrix = EnumMatrix::new();
+    /// matrix.set(Ordering::Less, true);
+    /// matrix.clear();
+    /// assert!(matrix.is_empty());
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn clear(&mut self) {
+        self.rows.fill(EnumSet::new());
+    }
+
+    /// Returns `true` if the bit at row `r` and column `c` is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMatrix;
+    ///
+    /// let mut matrix = EnumMatrix::new();
+    /// matrix.set(Ordering::Less, true);
+    /// assert!(matrix.contains(Ordering::Less, true));
+    /// assert!(!matrix.contains(Ordering::Less, false));
+    /// ```
+    #[inline]
+    pub fn contains(&self, r: R, c: C) -> bool {
+        self.row(r).contains(c)
+    }
+
+    /// Sets the bit at row `r` and column `c`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMatrix;
+    ///
+    /// let mut matrix = EnumMatrix::new();
+    /// matrix.set(Ordering::Equal, false);
+    /// assert!(matrix.contains(Ordering::Equal, false));
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn set(&mut self, r: R, c: C) {
+        self.allocate();
+        self.rows[r.index()].insert(c);
+    }
+
+    /// Unsets the bit at row `r` and column `c`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMatrix;
+    ///
+    /// let mut matrix = EnumMatrix::new();
+    /// matrix.set(Ordering::Equal, false);
+    /// matrix.unset(Ordering::Equal, false);
+    /// assert!(!matrix.contains(Ordering::Equal, false));
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn unset(&mut self, r: R, c: C) {
+        if let Some(row) = self.rows.get_mut(r.index()) {
+            row.remove(c);
+        }
+    }
+
+    /// Returns the set of columns whose bits are set in row `r`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::{EnumMatrix, EnumSet};
+    ///
+    /// let mut matrix = EnumMatrix::new();
+    /// matrix.set(Ordering::Less, true);
+    /// assert_eq!(matrix.row(Ordering::Less), EnumSet::from([true]));
+    /// assert_eq!(matrix.row(Ordering::Greater), EnumSet::new());
+    /// ```
+    #[inline]
+    pub fn row(&self, r: R) -> EnumSet<C> {
+        match self.rows.get(r.index()) {
+            Some(&row) => row,
+            None => EnumSet::new(),
+        }
+    }
+
+    /// Replaces row `r` with `row`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::{EnumMatrix, EnumSet};
+    ///
+    /// let mut matrix: EnumMatrix<Ordering, bool> = EnumMatrix::new();
+    /// matrix.set_row(Ordering::Less, EnumSet::all());
+    /// assert_eq!(matrix.len(), 2);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn set_row(&mut self, r: R, row: EnumSet<C>) {
+        self.allocate();
+        self.rows[r.index()] = row;
+    }
+
+    /// Returns the set of rows whose bits are set in column `c`.
+    ///
+    /// # Performance
+    ///
+    /// Rows are stored contiguously, so this operation takes O(`R::SIZE`) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::{EnumMatrix, enums};
+    ///
+    /// let mut matrix = EnumMatrix::new();
+    /// matrix.set(Ordering::Less, true);
+    /// matrix.set(Ordering::Greater, true);
+    /// assert_eq!(matrix.column(true), enums![Ordering::Less, Ordering::Greater]);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn column(&self, c: C) -> EnumSet<R> {
+        self.rows()
+            .filter(|(_, row)| row.contains(c))
+            .map(|(r, _)| r)
+            .collect()
+    }
+
+    /// An iterator visiting every row in order, including empty ones.
+    /// The iterator element type is `(R, EnumSet<C>)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::{EnumMatrix, EnumSet};
+    ///
+    /// let mut matrix = EnumMatrix::new();
+    /// matrix.set(Ordering::Equal, true);
+    ///
+    /// let rows: Vec<_> = matrix.rows().collect();
+    /// assert_eq!(rows, [
+    ///     (Ordering::Less, EnumSet::new()),
+    ///     (Ordering::Equal, EnumSet::from([true])),
+    ///     (Ordering::Greater, EnumSet::new()),
+    /// ]);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn rows(
+        &self,
+    ) -> impl '_ + DoubleEndedIterator<Item = (R, EnumSet<C>)> + ExactSizeIterator {
+        R::enumerate_all(..).map(move |r| (r, self.row(r)))
+    }
+
+    /// An iterator visiting every column in order, including empty ones.
+    /// The iterator element type is `(C, EnumSet<R>)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::{EnumMatrix, EnumSet};
+    ///
+    /// let mut matrix = EnumMatrix::new();
+    /// matrix.set(Ordering::Equal, true);
+    ///
+    /// let columns: Vec<_> = matrix.columns().collect();
+    /// assert_eq!(columns, [
+    ///     (false, EnumSet::new()),
+    ///     (true, EnumSet::from([Ordering::Equal])),
+    /// ]);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn columns(
+        &self,
+    ) -> impl '_ + DoubleEndedIterator<Item = (C, EnumSet<R>)> + ExactSizeIterator {
+        C::enumerate_all(..).map(move |c| (c, self.column(c)))
+    }
+
+    /// An iterator visiting the coordinates of all set bits in row-major order.
+    /// The iterator element type is `(R, C)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMatrix;
+    ///
+    /// let mut matrix = EnumMatrix::new();
+    /// matrix.set(Ordering::Greater, false);
+    /// matrix.set(Ordering::Less, true);
+    ///
+    /// let bits: Vec<_> = matrix.iter().collect();
+    /// assert_eq!(bits, [(Ordering::Less, true), (Ordering::Greater, false)]);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn iter(&self) -> impl '_ + Iterator<Item = (R, C)> {
+        self.rows()
+            .flat_map(|(r, row)| row.into_iter().map(move |c| (r, c)))
+    }
+
+    /// Returns the transpose of the matrix, with rows and columns swapped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumMatrix;
+    ///
+    /// let mut matrix = EnumMatrix::new();
+    /// matrix.set(Ordering::Less, true);
+    ///
+    /// let transposed = matrix.transpose();
+    /// assert!(transposed.contains(true, Ordering::Less));
+    /// ```
+    #[must_use = "newly constructed matrix is unused"]
+    pub fn transpose(&self) -> EnumMatrix<C, R> {
+        let mut transposed = EnumMatrix::new();
+        for (r, c) in self.iter() {
+            transposed.set(c, r);
+        }
+        transposed
+    }
+}
+
+impl<R: Enum, C: Enum> Clone for EnumMatrix<R, C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            rows: self.rows.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<R: Enum, C: Enum> Default for EnumMatrix<R, C> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: Enum, C: Enum> PartialEq for EnumMatrix<R, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.rows().eq(other.rows())
+    }
+}
+
+impl<R: Enum, C: Enum> Eq for EnumMatrix<R, C> {}
+
+impl<R: Enum, C: Enum> Hash for EnumMatrix<R, C>
+where
+    C::Rep: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for (_, row) in self.rows() {
+            row.hash(state);
+        }
+    }
+}
+
+impl<R: Enum + Debug, C: Enum + Debug> Debug for EnumMatrix<R, C> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self.rows()).finish()
+    }
+}
+
+impl<R: Enum, C: Enum> FromIterator<(R, C)> for EnumMatrix<R, C> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn from_iter<I: IntoIterator<Item = (R, C)>>(iter: I) -> Self {
+        let mut matrix = Self::new();
+        matrix.extend(iter);
+        matrix
+    }
+}
+
+impl<R: Enum, C: Enum> Extend<(R, C)> for EnumMatrix<R, C> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn extend<I: IntoIterator<Item = (R, C)>>(&mut self, iter: I) {
+        for (r, c) in iter {
+            self.set(r, c);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rustfmt::skip] #[allow(dead_code)]
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Enum)]
+    enum DemoEnum { A, B, C, D, E, F, G, H, I, J }
+
+    #[test]
+    fn test_transpose() {
+        let matrix: EnumMatrix<DemoEnum, DemoEnum> = [
+            (DemoEnum::A, DemoEnum::J),
+            (DemoEnum::C, DemoEnum::C),
+            (DemoEnum::E, DemoEnum::B),
+        ]
+        .into_iter()
+        .collect();
+        let transposed = matrix.transpose();
+        for (r, c) in matrix.iter() {
+            assert!(transposed.contains(c, r));
+        }
+        assert_eq!(transposed.len(), matrix.len());
+        assert_eq!(transposed.transpose(), matrix);
+    }
+
+    #[test]
+    fn test_unallocated_eq() {
+        let mut matrix: EnumMatrix<DemoEnum, DemoEnum> = EnumMatrix::new();
+        matrix.set(DemoEnum::A, DemoEnum::B);
+        matrix.unset(DemoEnum::A, DemoEnum::B);
+        assert_eq!(matrix, EnumMatrix::new());
+    }
+}
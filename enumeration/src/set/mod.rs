@@ -1,5 +1,41 @@
+mod diff;
+pub use diff::{Conflict, DiffPolicy};
+
+mod duplicates;
+pub use duplicates::{DuplicateError, DuplicatePolicy};
+
 mod enum_set;
-pub use enum_set::{EnumSet, __private};
+pub use enum_set::{__private, EnumSet, EnumSetBuilder};
 
 mod iter;
-pub use iter::Iter;
+pub use iter::{AllSets, Indices, Iter};
+
+#[cfg(feature = "alloc")]
+mod matrix;
+#[cfg(feature = "alloc")]
+pub use matrix::EnumMatrix;
+
+#[cfg(feature = "alloc")]
+mod lossy;
+#[cfg(feature = "alloc")]
+pub use lossy::LossySet;
+
+mod masked;
+pub use masked::MaskedSet;
+
+mod observed;
+pub use observed::ObservedEnumSet;
+
+mod quota;
+pub use quota::QuotaExceeded;
+
+#[cfg(feature = "std")]
+mod timed;
+#[cfg(feature = "std")]
+pub use timed::TimedEnumSet;
+
+mod tracked;
+pub use tracked::TrackedEnumSet;
+
+mod tristate;
+pub use tristate::TriStateEnumMask;
@@ -0,0 +1,148 @@
+use core::fmt::{self, Debug, Formatter};
+
+use super::enum_set::EnumSet;
+use crate::enumerate::Enum;
+
+/// A set that calls an observer whenever a value enters or leaves it.
+///
+/// The observer receives the value and whether it is now present. It is only called for actual
+/// changes, so inserting a value that is already present does nothing. This suits flags that
+/// drive side effects, such as enabling and disabling subsystems, where diffing snapshots of the
+/// set every frame would be wasteful.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::{Enum, ObservedEnumSet};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Subsystem { Audio, Physics, Network }
+///
+/// let mut log = Vec::new();
+/// let mut enabled = ObservedEnumSet::new(|subsystem, on| log.push((subsystem, on)));
+/// enabled.insert(Subsystem::Audio);
+/// enabled.insert(Subsystem::Audio);
+/// enabled.remove(Subsystem::Network);
+/// enabled.remove(Subsystem::Audio);
+/// drop(enabled);
+///
+/// assert_eq!(log, [(Subsystem::Audio, true), (Subsystem::Audio, false)]);
+/// ```
+pub struct ObservedEnumSet<T: Enum, F: FnMut(T, bool)> {
+    set: EnumSet<T>,
+    observer: F,
+}
+
+impl<T: Enum, F: FnMut(T, bool)> ObservedEnumSet<T, F> {
+    /// Creates an empty set with an observer.
+    #[inline]
+    pub const fn new(observer: F) -> Self {
+        Self::from_set(EnumSet::new(), observer)
+    }
+
+    /// Wraps an existing set. The observer is not called for the values already in it.
+    #[inline]
+    pub const fn from_set(set: EnumSet<T>, observer: F) -> Self {
+        Self { set, observer }
+    }
+
+    /// Returns the values of the set as a plain [`EnumSet`].
+    #[inline]
+    pub const fn to_set(&self) -> EnumSet<T> {
+        self.set
+    }
+
+    /// Unwraps the set and its observer.
+    #[inline]
+    pub fn into_parts(self) -> (EnumSet<T>, F) {
+        (self.set, self.observer)
+    }
+
+    /// Returns the number of elements in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    /// Returns `true` if the set contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    /// Returns `true` if the set contains a value.
+    #[inline]
+    pub fn contains(&self, x: T) -> bool {
+        self.set.contains(x)
+    }
+
+    /// Adds a value to the set. Returns `true` if it was not already present.
+    #[inline]
+    pub fn insert(&mut self, x: T) -> bool {
+        self.modify(|set| set.insert(x))
+    }
+
+    /// Removes a value from the set. Returns `true` if it was present.
+    #[inline]
+    pub fn remove(&mut self, x: T) -> bool {
+        self.modify(|set| set.remove(x))
+    }
+
+    /// Clears the set, removing all values. Returns `true` if the set was not already empty.
+    #[inline]
+    pub fn clear(&mut self) -> bool {
+        self.modify(EnumSet::clear)
+    }
+
+    /// Replaces the contents of the set. Returns `true` if they changed.
+    #[inline]
+    pub fn replace(&mut self, set: EnumSet<T>) -> bool {
+        self.modify(|old| *old = set)
+    }
+
+    /// Applies an arbitrary change to the set, then calls the observer for every value that
+    /// entered or left it, in order. Returns `true` if the contents changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, EnumSet, ObservedEnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Subsystem { Audio, Physics, Network }
+    ///
+    /// let mut changes = 0;
+    /// let mut enabled = ObservedEnumSet::from_set(
+    ///     enums![Subsystem::Audio],
+    ///     |_, _| changes += 1,
+    /// );
+    /// assert!(enabled.modify(|set| *set = !*set));
+    /// assert_eq!(enabled.to_set(), enums![Subsystem::Physics, Subsystem::Network]);
+    /// drop(enabled);
+    /// assert_eq!(changes, 3);
+    /// ```
+    pub fn modify<G: FnOnce(&mut EnumSet<T>)>(&mut self, f: G) -> bool {
+        let before = self.set;
+        f(&mut self.set);
+        let changed = self.set.symmetric_difference(&before);
+        for x in changed {
+            (self.observer)(x, self.set.contains(x));
+        }
+        !changed.is_empty()
+    }
+}
+
+impl<T: Enum, F: FnMut(T, bool)> Extend<T> for ObservedEnumSet<T, F> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.modify(|set| set.extend(iter));
+    }
+}
+
+impl<T: Enum + Debug, F: FnMut(T, bool)> Debug for ObservedEnumSet<T, F> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("ObservedEnumSet")
+            .field("set", &self.set)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,18 @@
+use core::error::Error;
+use core::fmt::{self, Display, Formatter};
+
+/// The error returned by [`EnumSet::try_insert_within`](super::EnumSet::try_insert_within) when
+/// inserting a value would grow a set past its limit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QuotaExceeded {
+    /// The maximum number of elements the set may hold.
+    pub limit: usize,
+}
+
+impl Display for QuotaExceeded {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "set already holds its limit of {} values", self.limit)
+    }
+}
+
+impl Error for QuotaExceeded {}
@@ -0,0 +1,240 @@
+use core::fmt::{self, Debug, Formatter};
+use std::time::{Duration, Instant};
+
+use super::enum_set::EnumSet;
+use crate::enumerate::Enum;
+use crate::map::EnumMap;
+
+/// A set that records when each element was inserted.
+///
+/// This suits debouncing and cool-down tracking for events identified by an enum: insert an
+/// event when it fires, check its [`age`](Self::age) before acting on it again, and
+/// periodically [`expire_older_than`](Self::expire_older_than) to forget old events.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use enumeration::{Enum, TimedEnumSet};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Button { Jump, Fire, Pause }
+///
+/// let cooldown = Duration::from_secs(60);
+/// let mut pressed = TimedEnumSet::new();
+///
+/// for button in [Button::Fire, Button::Fire] {
+///     if pressed.age(button).is_some_and(|age| age < cooldown) {
+///         continue; // Still cooling down.
+///     }
+///     pressed.insert_now(button);
+/// }
+/// assert_eq!(pressed.len(), 1);
+///
+/// pressed.expire_older_than(cooldown);
+/// assert!(pressed.contains(Button::Fire));
+/// ```
+pub struct TimedEnumSet<T: Enum> {
+    inserted: EnumMap<T, Instant>,
+}
+
+impl<T: Enum> TimedEnumSet<T> {
+    /// Creates an empty set.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            inserted: EnumMap::new(),
+        }
+    }
+
+    /// Returns the elements of the set, without their timestamps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, TimedEnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Button { Jump, Fire, Pause }
+    ///
+    /// let mut set = TimedEnumSet::new();
+    /// set.insert_now(Button::Pause);
+    /// set.insert_now(Button::Jump);
+    /// assert_eq!(set.to_set(), enums![Button::Jump, Button::Pause]);
+    /// ```
+    #[inline]
+    pub fn to_set(&self) -> EnumSet<T> {
+        EnumSet::from(&self.inserted)
+    }
+
+    /// Returns the number of elements in the set.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.inserted.len()
+    }
+
+    /// Returns `true` if the set contains no elements.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.inserted.is_empty()
+    }
+
+    /// Returns `true` if the set contains a value.
+    #[inline]
+    pub fn contains(&self, x: T) -> bool {
+        self.inserted.contains_key(x)
+    }
+
+    /// Adds a value to the set, timestamped with the current time. Returns the previous
+    /// timestamp if the value was already present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, TimedEnumSet};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Button { Jump, Fire, Pause }
+    ///
+    /// let mut set = TimedEnumSet::new();
+    /// assert_eq!(set.insert_now(Button::Fire), None);
+    /// assert!(set.insert_now(Button::Fire).is_some());
+    /// ```
+    #[inline]
+    pub fn insert_now(&mut self, x: T) -> Option<Instant> {
+        self.insert_at(x, Instant::now())
+    }
+
+    /// Adds a value to the set with the given timestamp. Returns the previous timestamp if the
+    /// value was already present.
+    #[inline]
+    pub fn insert_at(&mut self, x: T, at: Instant) -> Option<Instant> {
+        self.inserted.insert(x, at)
+    }
+
+    /// Removes a value from the set, returning its timestamp if it was present.
+    #[inline]
+    pub fn remove(&mut self, x: T) -> Option<Instant> {
+        self.inserted.remove(x)
+    }
+
+    /// Clears the set, removing all values.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.inserted.clear();
+    }
+
+    /// Returns when a value was inserted, or `None` if it is not in the set.
+    #[inline]
+    pub fn inserted_at(&self, x: T) -> Option<Instant> {
+        self.inserted.get(x).copied()
+    }
+
+    /// Returns how long ago a value was inserted, or `None` if it is not in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use enumeration::{Enum, TimedEnumSet};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Button { Jump, Fire, Pause }
+    ///
+    /// let mut set = TimedEnumSet::new();
+    /// set.insert_now(Button::Jump);
+    /// assert!(set.age(Button::Jump).unwrap() < Duration::from_secs(60));
+    /// assert_eq!(set.age(Button::Fire), None);
+    /// ```
+    #[inline]
+    pub fn age(&self, x: T) -> Option<Duration> {
+        self.inserted_at(x).map(|at| at.elapsed())
+    }
+
+    /// Removes every value inserted before `deadline`, and returns them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use enumeration::{Enum, TimedEnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Button { Jump, Fire, Pause }
+    ///
+    /// let start = Instant::now();
+    /// let mut set = TimedEnumSet::new();
+    /// set.insert_at(Button::Jump, start);
+    /// set.insert_at(Button::Fire, start + Duration::from_secs(5));
+    ///
+    /// let expired = set.expire_before(start + Duration::from_secs(1));
+    /// assert_eq!(expired, enums![Button::Jump]);
+    /// assert_eq!(set.to_set(), enums![Button::Fire]);
+    /// ```
+    pub fn expire_before(&mut self, deadline: Instant) -> EnumSet<T> {
+        let mut expired = EnumSet::new();
+        self.inserted.retain(|x, &mut at| {
+            let keep = at >= deadline;
+            if !keep {
+                expired.insert(x);
+            }
+            keep
+        });
+        expired
+    }
+
+    /// Removes every value inserted more than `max_age` ago, and returns them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use enumeration::{Enum, TimedEnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Button { Jump, Fire, Pause }
+    ///
+    /// let mut set = TimedEnumSet::new();
+    /// set.insert_now(Button::Pause);
+    /// assert!(set.expire_older_than(Duration::from_secs(60)).is_empty());
+    ///
+    /// std::thread::sleep(Duration::from_millis(2));
+    /// assert_eq!(set.expire_older_than(Duration::from_millis(1)), enums![Button::Pause]);
+    /// ```
+    #[inline]
+    pub fn expire_older_than(&mut self, max_age: Duration) -> EnumSet<T> {
+        match Instant::now().checked_sub(max_age) {
+            Some(deadline) => self.expire_before(deadline),
+            // Nothing can be older than the earliest representable instant.
+            None => EnumSet::new(),
+        }
+    }
+
+    /// An iterator visiting all elements in order, along with their timestamps.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (T, Instant)> + '_ {
+        self.inserted.iter().map(|(x, &at)| (x, at))
+    }
+}
+
+impl<T: Enum> Clone for TimedEnumSet<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inserted: self.inserted.clone(),
+        }
+    }
+}
+
+impl<T: Enum> Default for TimedEnumSet<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Enum + Debug> Debug for TimedEnumSet<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
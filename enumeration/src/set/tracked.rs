@@ -0,0 +1,171 @@
+use core::fmt::{self, Debug, Formatter};
+
+use super::enum_set::EnumSet;
+use crate::enumerate::Enum;
+
+/// A set that counts how many times its contents have changed.
+///
+/// Every mutation that changes the contents of the set increments its
+/// [`generation`](Self::generation). A cache of values derived from the set can store the
+/// generation it was computed at, and compare it to the current one to tell whether it is stale,
+/// without keeping a copy of the set around to diff against. Mutations that leave the contents
+/// unchanged, such as inserting a value that is already present, do not count.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::{Enum, TrackedEnumSet};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Layer { Terrain, Units, Fog }
+///
+/// let mut visible = TrackedEnumSet::new();
+/// visible.insert(Layer::Terrain);
+/// let rendered_at = visible.generation();
+///
+/// visible.insert(Layer::Terrain);
+/// assert_eq!(visible.generation(), rendered_at);
+///
+/// visible.insert(Layer::Fog);
+/// assert_ne!(visible.generation(), rendered_at);
+/// ```
+pub struct TrackedEnumSet<T: Enum> {
+    set: EnumSet<T>,
+    generation: u64,
+}
+
+impl<T: Enum> TrackedEnumSet<T> {
+    /// Creates an empty set at generation 0.
+    #[inline]
+    pub const fn new() -> Self {
+        Self::from_set(EnumSet::new())
+    }
+
+    /// Wraps an existing set, starting at generation 0.
+    #[inline]
+    pub const fn from_set(set: EnumSet<T>) -> Self {
+        Self { set, generation: 0 }
+    }
+
+    /// Returns the number of times the contents of the set have changed.
+    ///
+    /// The counter wraps around on overflow.
+    #[inline]
+    pub const fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Returns the values of the set as a plain [`EnumSet`].
+    #[inline]
+    pub const fn to_set(&self) -> EnumSet<T> {
+        self.set
+    }
+
+    /// Returns the number of elements in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    /// Returns `true` if the set contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    /// Returns `true` if the set contains a value.
+    #[inline]
+    pub fn contains(&self, x: T) -> bool {
+        self.set.contains(x)
+    }
+
+    /// Adds a value to the set. Returns `true` if it was not already present.
+    #[inline]
+    pub fn insert(&mut self, x: T) -> bool {
+        self.modify(|set| set.insert(x))
+    }
+
+    /// Removes a value from the set. Returns `true` if it was present.
+    #[inline]
+    pub fn remove(&mut self, x: T) -> bool {
+        self.modify(|set| set.remove(x))
+    }
+
+    /// Clears the set, removing all values. Returns `true` if the set was not already empty.
+    #[inline]
+    pub fn clear(&mut self) -> bool {
+        self.modify(EnumSet::clear)
+    }
+
+    /// Replaces the contents of the set. Returns `true` if they changed.
+    #[inline]
+    pub fn replace(&mut self, set: EnumSet<T>) -> bool {
+        self.modify(|old| *old = set)
+    }
+
+    /// Applies an arbitrary change to the set. Returns `true`, and advances the generation, if
+    /// the contents changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, TrackedEnumSet, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Layer { Terrain, Units, Fog }
+    ///
+    /// let mut visible = TrackedEnumSet::from_set(enums![Layer::Terrain, Layer::Fog]);
+    /// assert!(visible.modify(|set| set.retain(|layer| layer != Layer::Fog)));
+    /// assert!(!visible.modify(|set| set.retain(|layer| layer != Layer::Fog)));
+    /// assert_eq!(visible.generation(), 1);
+    /// ```
+    pub fn modify<F: FnOnce(&mut EnumSet<T>)>(&mut self, f: F) -> bool {
+        let before = self.set;
+        f(&mut self.set);
+        let changed = self.set != before;
+        if changed {
+            self.generation = self.generation.wrapping_add(1);
+        }
+        changed
+    }
+}
+
+impl<T: Enum> Copy for TrackedEnumSet<T> {}
+
+#[allow(clippy::expl_impl_clone_on_copy)]
+impl<T: Enum> Clone for TrackedEnumSet<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Enum> Default for TrackedEnumSet<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Enum> From<EnumSet<T>> for TrackedEnumSet<T> {
+    #[inline]
+    fn from(set: EnumSet<T>) -> Self {
+        Self::from_set(set)
+    }
+}
+
+impl<T: Enum> Extend<T> for TrackedEnumSet<T> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.modify(|set| set.extend(iter));
+    }
+}
+
+impl<T: Enum + Debug> Debug for TrackedEnumSet<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("TrackedEnumSet")
+            .field("set", &self.set)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
@@ -0,0 +1,208 @@
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+
+use super::enum_set::EnumSet;
+use crate::enumerate::Enum;
+
+/// A three-valued mask that marks each value as explicitly allowed, explicitly denied, or
+/// unset.
+///
+/// Unset values take their membership from a default set when the mask is
+/// [resolved](Self::resolve). This is the usual shape of layered permissions: a base set of
+/// defaults, adjusted by explicit overrides.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::{Enum, TriStateEnumMask, enums};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Permission { Read, Write, Delete, Share }
+///
+/// let defaults = enums![Permission::Read, Permission::Share];
+///
+/// let mut overrides = TriStateEnumMask::new();
+/// overrides.allow(Permission::Write);
+/// overrides.deny(Permission::Share);
+///
+/// assert_eq!(overrides.get(Permission::Read), None);
+/// assert_eq!(overrides.get(Permission::Share), Some(false));
+/// assert_eq!(overrides.resolve(defaults), enums![Permission::Read, Permission::Write]);
+/// ```
+pub struct TriStateEnumMask<T: Enum> {
+    allowed: EnumSet<T>,
+    denied: EnumSet<T>,
+}
+
+impl<T: Enum> TriStateEnumMask<T> {
+    /// Creates a mask with every value unset.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            allowed: EnumSet::new(),
+            denied: EnumSet::new(),
+        }
+    }
+
+    /// Creates a mask from its explicitly allowed and denied values. A value in both sets is
+    /// denied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, TriStateEnumMask, enums};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Permission { Read, Write, Delete, Share }
+    ///
+    /// let mask = TriStateEnumMask::from_sets(
+    ///     enums![Permission::Read, Permission::Write],
+    ///     enums![Permission::Write],
+    /// );
+    /// assert_eq!(mask.allowed(), enums![Permission::Read]);
+    /// assert_eq!(mask.get(Permission::Write), Some(false));
+    /// ```
+    #[inline]
+    pub fn from_sets(allowed: EnumSet<T>, denied: EnumSet<T>) -> Self {
+        Self {
+            allowed: allowed.difference(&denied),
+            denied,
+        }
+    }
+
+    /// Marks a value as explicitly allowed.
+    #[inline]
+    pub fn allow(&mut self, x: T) {
+        self.allowed.insert(x);
+        self.denied.remove(x);
+    }
+
+    /// Marks a value as explicitly denied.
+    #[inline]
+    pub fn deny(&mut self, x: T) {
+        self.denied.insert(x);
+        self.allowed.remove(x);
+    }
+
+    /// Clears any explicit setting for a value, so that it falls back to the default.
+    #[inline]
+    pub fn unset(&mut self, x: T) {
+        self.allowed.remove(x);
+        self.denied.remove(x);
+    }
+
+    /// Returns `Some(true)` if a value is allowed, `Some(false)` if it is denied, and `None` if
+    /// it is unset.
+    #[inline]
+    pub fn get(&self, x: T) -> Option<bool> {
+        if self.allowed.contains(x) {
+            Some(true)
+        } else if self.denied.contains(x) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the explicitly allowed values.
+    #[inline]
+    pub const fn allowed(&self) -> EnumSet<T> {
+        self.allowed
+    }
+
+    /// Returns the explicitly denied values.
+    #[inline]
+    pub const fn denied(&self) -> EnumSet<T> {
+        self.denied
+    }
+
+    /// Returns the values that are unset.
+    #[inline]
+    pub fn unset_values(&self) -> EnumSet<T> {
+        !self.allowed.union(&self.denied)
+    }
+
+    /// Resolves the mask against a default: the allowed values, plus the values of `default`
+    /// that are not denied.
+    #[inline]
+    pub fn resolve(&self, default: EnumSet<T>) -> EnumSet<T> {
+        default.difference(&self.denied).union(&self.allowed)
+    }
+
+    /// Layers another mask on top of this one. Values set in `other` take its setting, and
+    /// values unset in `other` keep this mask's setting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use enumeration::{Enum, TriStateEnumMask};
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+    /// pub enum Permission { Read, Write, Delete, Share }
+    ///
+    /// let mut team = TriStateEnumMask::new();
+    /// team.deny(Permission::Delete);
+    /// team.allow(Permission::Share);
+    ///
+    /// let mut user = TriStateEnumMask::new();
+    /// user.allow(Permission::Delete);
+    ///
+    /// let effective = team.overlay(&user);
+    /// assert_eq!(effective.get(Permission::Delete), Some(true));
+    /// assert_eq!(effective.get(Permission::Share), Some(true));
+    /// assert_eq!(effective.get(Permission::Read), None);
+    /// ```
+    #[inline]
+    pub fn overlay(&self, other: &Self) -> Self {
+        Self {
+            allowed: self.allowed.difference(&other.denied).union(&other.allowed),
+            denied: self.denied.difference(&other.allowed).union(&other.denied),
+        }
+    }
+}
+
+impl<T: Enum> Copy for TriStateEnumMask<T> {}
+
+#[allow(clippy::expl_impl_clone_on_copy)]
+impl<T: Enum> Clone for TriStateEnumMask<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Enum> Default for TriStateEnumMask<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Enum> PartialEq for TriStateEnumMask<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.allowed == other.allowed && self.denied == other.denied
+    }
+}
+
+impl<T: Enum> Eq for TriStateEnumMask<T> {}
+
+impl<T: Enum> Hash for TriStateEnumMask<T>
+where
+    T::Rep: Hash,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.allowed.hash(state);
+        self.denied.hash(state);
+    }
+}
+
+impl<T: Enum + Debug> Debug for TriStateEnumMask<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("TriStateEnumMask")
+            .field("allowed", &self.allowed)
+            .field("denied", &self.denied)
+            .finish()
+    }
+}
@@ -0,0 +1,291 @@
+use alloc::boxed::Box;
+use alloc::vec;
+use core::fmt::{self, Debug, Formatter};
+use core::marker::PhantomData;
+use core::ops::{Index, IndexMut};
+use core::{mem, slice};
+
+use super::iter::Iter;
+use crate::enumerate::Enum;
+use crate::map::EnumMap;
+
+/// A total map from enum values to values: every key always has a value.
+///
+/// Unlike an [`EnumMap`], an `EnumTable` cannot be missing a key, so it stores its values in a
+/// plain slice with no occupancy tracking, and lookups return `&V` rather than `Option<&V>`. It
+/// suits lookup tables and per-variant configuration.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::{Enum, EnumTable};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Level { Debug, Info, Warn, Error }
+///
+/// let mut colors = EnumTable::from_fn(|level| match level {
+///     Level::Warn => "yellow",
+///     Level::Error => "red",
+///     _ => "default",
+/// });
+/// assert_eq!(colors[Level::Error], "red");
+///
+/// colors[Level::Debug] = "gray";
+/// assert_eq!(colors.values().copied().collect::<Vec<_>>(), ["gray", "default", "yellow", "red"]);
+/// ```
+pub struct EnumTable<K: Enum, V> {
+    values: Box<[V]>,
+    marker: PhantomData<fn(K) -> V>,
+}
+
+impl<K: Enum, V> EnumTable<K, V> {
+    /// Creates a table holding the result of `f` for each key, calling `f` in key order.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn from_fn<F: FnMut(K) -> V>(f: F) -> Self {
+        Self {
+            values: K::enumerate_all(..).map(f).collect(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Creates a table holding a clone of `value` for each key.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn splat(value: V) -> Self
+    where
+        V: Clone,
+    {
+        Self::from_fn(|_| value.clone())
+    }
+
+    /// Returns the number of entries in the table, which is always `K::SIZE`.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the key type has no values.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns a reference to the value for a key.
+    #[inline]
+    pub fn get(&self, k: K) -> &V {
+        &self.values[k.index()]
+    }
+
+    /// Returns a mutable reference to the value for a key.
+    #[inline]
+    pub fn get_mut(&mut self, k: K) -> &mut V {
+        &mut self.values[k.index()]
+    }
+
+    /// Replaces the value for a key, returning the old value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumTable;
+    ///
+    /// let mut table = EnumTable::splat(0);
+    /// assert_eq!(table.replace(Ordering::Less, 5), 0);
+    /// assert_eq!(table[Ordering::Less], 5);
+    /// ```
+    #[inline]
+    pub fn replace(&mut self, k: K, v: V) -> V {
+        mem::replace(self.get_mut(k), v)
+    }
+
+    /// Returns the values as a slice, in key order.
+    #[inline]
+    pub const fn as_slice(&self) -> &[V] {
+        &self.values
+    }
+
+    /// Returns the values as a mutable slice, in key order.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [V] {
+        &mut self.values
+    }
+
+    /// An iterator visiting every key and its value, in key order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumTable;
+    ///
+    /// let table = EnumTable::from_fn(|k: Ordering| k as i8);
+    /// let mut iter = table.iter();
+    /// assert_eq!(iter.len(), 3);
+    /// assert_eq!(iter.next_back(), Some((Ordering::Greater, &1)));
+    /// assert_eq!(iter.next(), Some((Ordering::Less, &-1)));
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<K, slice::Iter<'_, V>> {
+        Iter::new(self.values.iter())
+    }
+
+    /// An iterator visiting every key and a mutable reference to its value, in key order.
+    #[inline]
+    pub fn iter_mut(&mut self) -> Iter<K, slice::IterMut<'_, V>> {
+        Iter::new(self.values.iter_mut())
+    }
+
+    /// An iterator visiting every value, in key order.
+    #[inline]
+    pub fn values(&self) -> slice::Iter<'_, V> {
+        self.values.iter()
+    }
+
+    /// An iterator visiting a mutable reference to every value, in key order.
+    #[inline]
+    pub fn values_mut(&mut self) -> slice::IterMut<'_, V> {
+        self.values.iter_mut()
+    }
+
+    /// Creates a table by applying `f` to every key and value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::EnumTable;
+    ///
+    /// let table = EnumTable::from_fn(|k: Ordering| k as i8);
+    /// let labels = table.map(|_, n| n.to_string());
+    /// assert_eq!(labels[Ordering::Greater], "1");
+    /// ```
+    pub fn map<U, F: FnMut(K, V) -> U>(self, mut f: F) -> EnumTable<K, U> {
+        EnumTable {
+            values: self.into_iter().map(|(k, v)| f(k, v)).collect(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<K: Enum, V: Clone> Clone for EnumTable<K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<K: Enum, V: Default> Default for EnumTable<K, V> {
+    /// Creates a table holding `V::default()` for each key.
+    #[inline]
+    fn default() -> Self {
+        Self::from_fn(|_| V::default())
+    }
+}
+
+impl<K: Enum, V: PartialEq> PartialEq for EnumTable<K, V> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+impl<K: Enum, V: Eq> Eq for EnumTable<K, V> {}
+
+impl<K: Enum, V: core::hash::Hash> core::hash::Hash for EnumTable<K, V> {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.values.hash(state);
+    }
+}
+
+impl<K: Enum + Debug, V: Debug> Debug for EnumTable<K, V> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Enum, V> Index<K> for EnumTable<K, V> {
+    type Output = V;
+
+    #[inline]
+    fn index(&self, k: K) -> &V {
+        self.get(k)
+    }
+}
+
+impl<K: Enum, V> IndexMut<K> for EnumTable<K, V> {
+    #[inline]
+    fn index_mut(&mut self, k: K) -> &mut V {
+        self.get_mut(k)
+    }
+}
+
+impl<K: Enum, V> IntoIterator for EnumTable<K, V> {
+    type Item = (K, V);
+    type IntoIter = Iter<K, vec::IntoIter<V>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(self.values.into_vec().into_iter())
+    }
+}
+
+impl<'a, K: Enum, V> IntoIterator for &'a EnumTable<K, V> {
+    type Item = (K, &'a V);
+    type IntoIter = Iter<K, slice::Iter<'a, V>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K: Enum, V> IntoIterator for &'a mut EnumTable<K, V> {
+    type Item = (K, &'a mut V);
+    type IntoIter = Iter<K, slice::IterMut<'a, V>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K: Enum, V> From<EnumTable<K, V>> for EnumMap<K, V> {
+    /// Converts a table into a map with every key present.
+    #[inline]
+    fn from(table: EnumTable<K, V>) -> Self {
+        table.into_iter().collect()
+    }
+}
+
+impl<K: Enum, V> TryFrom<EnumMap<K, V>> for EnumTable<K, V> {
+    type Error = EnumMap<K, V>;
+
+    /// Converts a map into a table if every key is present. Otherwise, returns the map
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use enumeration::{EnumMap, EnumTable};
+    ///
+    /// let partial = EnumMap::from([(Ordering::Less, 1)]);
+    /// assert_eq!(EnumTable::try_from(partial.clone()), Err(partial));
+    ///
+    /// let full = EnumMap::from([(Ordering::Less, 1), (Ordering::Equal, 2), (Ordering::Greater, 3)]);
+    /// assert_eq!(EnumTable::try_from(full).unwrap().as_slice(), [1, 2, 3]);
+    /// ```
+    fn try_from(map: EnumMap<K, V>) -> Result<Self, Self::Error> {
+        if map.len() != K::SIZE {
+            return Err(map);
+        }
+        Ok(Self {
+            values: map.into_values().collect(),
+            marker: PhantomData,
+        })
+    }
+}
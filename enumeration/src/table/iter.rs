@@ -0,0 +1,61 @@
+use core::iter::{FusedIterator, Iterator, Zip};
+
+use crate::enumerate::{Enum, Enumeration};
+
+/// An iterator over the keys of an [`EnumTable`](super::EnumTable) and its values, in key order.
+///
+/// `I` is the iterator over the values: a slice iterator for [`iter`](super::EnumTable::iter)
+/// and [`iter_mut`](super::EnumTable::iter_mut), or a vector's for
+/// [`into_iter`](super::EnumTable::into_iter).
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Clone, Debug)]
+pub struct Iter<K, I> {
+    inner: Zip<Enumeration<K>, I>,
+}
+
+impl<K: Enum, I: ExactSizeIterator> Iter<K, I> {
+    #[inline]
+    pub(super) fn new(values: I) -> Self {
+        Self {
+            inner: K::enumerate_all(..).zip(values),
+        }
+    }
+}
+
+impl<K: Enum, I: ExactSizeIterator> Iterator for Iter<K, I> {
+    type Item = (K, I::Item);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth(n)
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.inner.fold(init, f)
+    }
+}
+
+impl<K: Enum, I: ExactSizeIterator + DoubleEndedIterator> DoubleEndedIterator for Iter<K, I> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<K: Enum, I: ExactSizeIterator> ExactSizeIterator for Iter<K, I> {}
+
+impl<K: Enum, I: ExactSizeIterator + FusedIterator> FusedIterator for Iter<K, I> {}
@@ -0,0 +1,5 @@
+mod enum_table;
+pub use enum_table::EnumTable;
+
+mod iter;
+pub use iter::Iter;
@@ -0,0 +1,243 @@
+//! Law checks for hand-written [`Enum`] implementations.
+//!
+//! `#[derive(Enum)]` always produces a lawful implementation. Types that implement [`Enum`]
+//! manually can call these functions from their own tests to check that [`EnumSet`] and
+//! [`EnumMap`] will behave correctly with them.
+//!
+//! # Examples
+//!
+//! ```
+//! use enumeration::testing::{assert_enum_laws, assert_map_roundtrip, assert_set_roundtrip};
+//!
+//! assert_enum_laws::<bool>();
+//! assert_set_roundtrip::<bool>();
+//! assert_map_roundtrip::<bool>();
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::iter::Iterator;
+
+use crate::enumerate::Enum;
+use crate::map::EnumMap;
+use crate::set::EnumSet;
+use crate::wordlike::Wordlike;
+
+/// Asserts that `T` follows the rules documented on each item of [`Enum`].
+///
+/// # Panics
+///
+/// Panics with a description of the first broken rule.
+pub fn assert_enum_laws<T: Enum + Debug>() {
+    let values: Vec<T> = T::enumerate_all(..).collect();
+    assert_eq!(
+        values.len(),
+        T::SIZE,
+        "enumerate_all(..) must yield SIZE values"
+    );
+    assert_eq!(
+        values.first(),
+        Some(&T::MIN),
+        "enumeration must start at MIN"
+    );
+    assert_eq!(values.last(), Some(&T::MAX), "enumeration must end at MAX");
+
+    let mut bitmask = T::Rep::ZERO;
+    for (i, &x) in values.iter().enumerate() {
+        assert_eq!(x.index(), i, "{x:?}.index() must match its position");
+        assert_eq!(
+            T::from_index(i),
+            Some(x),
+            "from_index({i}) must return {x:?}"
+        );
+        assert_eq!(
+            x.succ(),
+            values.get(i + 1).copied(),
+            "{x:?}.succ() must return the next value"
+        );
+        assert_eq!(
+            x.pred(),
+            i.checked_sub(1).map(|j| values[j]),
+            "{x:?}.pred() must return the previous value"
+        );
+        if let Some(&prev) = i.checked_sub(1).and_then(|j| values.get(j)) {
+            assert!(prev < x, "{prev:?} must compare less than {x:?}");
+        }
+        let bit = x.bit();
+        assert_eq!(
+            Wordlike::count_ones(bit),
+            1,
+            "{x:?}.bit() must have exactly one bit set"
+        );
+        assert!(
+            bitmask & bit == T::Rep::ZERO,
+            "{x:?}.bit() must not overlap the bit of another value"
+        );
+        bitmask |= bit;
+    }
+    assert!(
+        bitmask == T::BITMASK,
+        "BITMASK must be the union of every value's bit"
+    );
+    assert_eq!(
+        T::from_index(T::SIZE),
+        None,
+        "from_index(SIZE) must be None"
+    );
+
+    let mut stable_indices = Vec::with_capacity(T::SIZE);
+    for &x in &values {
+        let i = x.stable_index();
+        assert!(
+            !stable_indices.contains(&i),
+            "{x:?}.stable_index() must not equal the stable index of another value"
+        );
+        assert_eq!(
+            T::from_stable_index(i),
+            Some(x),
+            "from_stable_index({i}) must return {x:?}"
+        );
+        stable_indices.push(i);
+    }
+
+    if !T::NAMES.is_empty() {
+        assert_eq!(
+            T::NAMES.len(),
+            T::SIZE,
+            "NAMES must be empty or have SIZE entries"
+        );
+        for &x in &values {
+            let name = x.name().unwrap();
+            assert_eq!(
+                T::from_name(name),
+                Some(x),
+                "from_name({name:?}) must return {x:?}"
+            );
+        }
+    }
+}
+
+/// Checks that the bits of `T` are laid out the way [`EnumSet`] expects: `x.bit()` is
+/// `1 << x.index()` for every value `x`, and [`Enum::BITMASK`] is the union of those bits.
+///
+/// Composite implementations, such as ones for `Option<T>` or tuples, are easy to get wrong in
+/// ways that make two values share a bit, which silently corrupts every set that holds them.
+/// Unlike [`assert_enum_laws`], this does not panic, so it can also be called at startup.
+/// Debug builds additionally check each bit as it is added to or looked up in a set.
+///
+/// # Errors
+///
+/// Returns a description of the first broken rule.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::testing::validate_enum_impl;
+///
+/// assert_eq!(validate_enum_impl::<bool>(), Ok(()));
+/// ```
+pub fn validate_enum_impl<T: Enum + Debug>() -> Result<(), String> {
+    let mut union = T::Rep::ZERO;
+    let mut prev: Option<(T, T::Rep)> = None;
+    for (i, x) in T::enumerate_all(..).enumerate() {
+        if x.index() != i {
+            return Err(format!("{x:?}.index() is {}, expected {i}", x.index()));
+        }
+        let bit = x.bit();
+        if Wordlike::count_ones(bit) != 1 {
+            return Err(format!("{x:?}.bit() does not have exactly one bit set"));
+        }
+        if union & bit != T::Rep::ZERO {
+            return Err(format!("{x:?}.bit() overlaps the bit of another value"));
+        }
+        if let Some((prev, prev_bit)) = prev {
+            if prev_bit > bit {
+                return Err(format!("{x:?}.bit() is lower than {prev:?}.bit()"));
+            }
+        } else if bit != T::Rep::ZERO.incr() {
+            return Err(format!("{x:?}.bit() is not the lowest bit"));
+        }
+        union |= bit;
+        prev = Some((x, bit));
+    }
+    // Distinct single bits in increasing order equal `1 << index()` exactly when they leave no
+    // gaps, i.e. when their union is a run of ones starting from the lowest bit.
+    if union != !T::Rep::ZERO && union & union.incr() != T::Rep::ZERO {
+        return Err("bits are not contiguous".to_owned());
+    }
+    if union != T::BITMASK {
+        return Err("BITMASK is not the union of every value's bit".to_owned());
+    }
+    Ok(())
+}
+
+/// Asserts that every subset of `T` built from single values survives a round trip through
+/// [`EnumSet`]'s constructors, iterators, and raw representation.
+///
+/// # Panics
+///
+/// Panics with a description of the first mismatch.
+pub fn assert_set_roundtrip<T: Enum + Debug>() {
+    let all: EnumSet<T> = T::enumerate_all(..).collect();
+    assert_eq!(
+        all,
+        EnumSet::all(),
+        "collecting every value must give EnumSet::all()"
+    );
+    assert_eq!(all.len(), T::SIZE);
+    assert!(all.inverse().is_empty(), "inverse of all() must be empty");
+    assert_eq!(
+        all.into_iter().collect::<Vec<_>>(),
+        T::enumerate_all(..).collect::<Vec<_>>(),
+        "iteration must visit values in order"
+    );
+
+    for x in T::enumerate_all(..) {
+        let single: EnumSet<T> = core::iter::once(x).collect();
+        assert_eq!(single.len(), 1, "set of {x:?} must have length 1");
+        assert!(single.contains(x), "set of {x:?} must contain it");
+        assert_eq!(single.into_iter().collect::<Vec<_>>(), [x]);
+        assert_eq!(EnumSet::from_raw(single.to_raw()), single);
+
+        let rest = single.inverse();
+        assert_eq!(rest.len(), T::SIZE - 1);
+        assert!(
+            !rest.contains(x),
+            "inverse of {{{x:?}}} must not contain it"
+        );
+        assert_eq!(rest | single, all);
+    }
+}
+
+/// Asserts that every value of `T` can be used as an [`EnumMap`] key and is recovered in order
+/// by iteration.
+///
+/// # Panics
+///
+/// Panics with a description of the first mismatch.
+pub fn assert_map_roundtrip<T: Enum + Debug>() {
+    let mut map = EnumMap::new();
+    for x in T::enumerate_all(..) {
+        assert_eq!(
+            map.insert(x, x.index()),
+            None,
+            "{x:?} must not already be a key"
+        );
+        assert_eq!(map.get(x), Some(&x.index()));
+    }
+    assert_eq!(map.len(), T::SIZE);
+    assert_eq!(
+        map.iter().map(|(k, &v)| (k, v)).collect::<Vec<_>>(),
+        T::enumerate_all(..)
+            .map(|x| (x, x.index()))
+            .collect::<Vec<_>>(),
+        "iteration must visit keys in order"
+    );
+    for x in T::enumerate_all(..) {
+        assert_eq!(map.remove(x), Some(x.index()));
+    }
+    assert!(map.is_empty());
+}
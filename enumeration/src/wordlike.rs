@@ -1,7 +1,22 @@
-use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+use core::cmp::Ordering;
+use core::mem::size_of;
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+use core::ptr;
 
+mod private {
+    pub trait Sealed {
+        /// Size in bytes of the integers that make up a value, each stored in native byte order.
+        const CHUNK_BYTES: usize;
+    }
+}
+
+/// The bit representations usable as [`Enum::Rep`](crate::Enum::Rep): the unsigned integers and
+/// [`Words`].
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
 pub trait Wordlike:
-    BitAnd<Output = Self>
+    private::Sealed
+    + BitAnd<Output = Self>
     + BitAndAssign
     + BitOr<Output = Self>
     + BitOrAssign
@@ -13,22 +28,102 @@ pub trait Wordlike:
     + Ord
 {
     const ZERO: Self;
+    /// Number of 64-bit words needed to hold a value.
+    const WORDS: usize;
     fn count_ones(this: Self) -> usize;
+    fn trailing_zeros(this: Self) -> usize;
     fn incr(self) -> Self;
+    fn decr(self) -> Self;
+    /// Returns a value with the lowest `bits` bits set.
+    fn low_mask(bits: usize) -> Self;
+    /// Returns the `i`th 64-bit word, counting from the least significant, or 0 if `i` is not
+    /// less than `WORDS`.
+    fn word(this: Self, i: usize) -> u64;
+    /// Replaces the `i`th 64-bit word, discarding bits that do not fit.
+    #[must_use]
+    fn with_word(self, i: usize, word: u64) -> Self;
+}
+
+/// Returns the offset of the byte of a `W` that holds bit `i`.
+const fn byte_of<W: Wordlike>(i: usize) -> usize {
+    let byte = i / 8;
+    assert!(byte < size_of::<W>(), "bit index out of range");
+    if cfg!(target_endian = "little") {
+        byte
+    } else {
+        let chunk = <W as private::Sealed>::CHUNK_BYTES;
+        byte - byte % chunk + (chunk - 1 - byte % chunk)
+    }
+}
+
+/// Returns `true` if bit `i` of `this` is set. Unlike the trait methods, this can be used in
+/// constant expressions.
+pub(crate) const fn const_test_bit<W: Wordlike>(this: &W, i: usize) -> bool {
+    // SAFETY: every `Wordlike` consists of integers, so all of its bytes are initialized, and
+    // `byte_of` checks that the offset is in bounds.
+    let byte = unsafe { *ptr::from_ref(this).cast::<u8>().add(byte_of::<W>(i)) };
+    (byte >> (i % 8)) & 1 == 1
+}
+
+/// Sets bit `i` of `this`. Unlike the trait methods, this can be used in constant expressions.
+pub(crate) const fn const_set_bit<W: Wordlike>(this: &mut W, i: usize) {
+    // SAFETY: as in `const_test_bit`. Any byte value is valid for an integer.
+    let byte = unsafe { &mut *ptr::from_mut(this).cast::<u8>().add(byte_of::<W>(i)) };
+    *byte |= 1 << (i % 8);
 }
 
 macro_rules! impl_word {
     ($n: ty) => {
+        impl private::Sealed for $n {
+            const CHUNK_BYTES: usize = size_of::<$n>();
+        }
+
         impl Wordlike for $n {
             const ZERO: Self = 0;
+            const WORDS: usize = (<$n>::BITS as usize).div_ceil(64);
             #[inline]
             fn count_ones(this: Self) -> usize {
                 this.count_ones() as usize
             }
             #[inline]
+            fn trailing_zeros(this: Self) -> usize {
+                this.trailing_zeros() as usize
+            }
+            #[inline]
             fn incr(self) -> Self {
                 self + 1
             }
+            #[inline]
+            fn decr(self) -> Self {
+                self - 1
+            }
+            #[inline]
+            fn low_mask(bits: usize) -> Self {
+                if bits >= <$n>::BITS as usize {
+                    !0
+                } else {
+                    (1 << bits) - 1
+                }
+            }
+            #[allow(clippy::cast_lossless, clippy::cast_possible_truncation)]
+            #[inline]
+            fn word(this: Self, i: usize) -> u64 {
+                if i < Self::WORDS {
+                    (this >> (64 * i)) as u64
+                } else {
+                    0
+                }
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            #[inline]
+            fn with_word(self, i: usize, word: u64) -> Self {
+                if i < Self::WORDS {
+                    let shift = 64 * i;
+                    self & !((u64::MAX as $n) << shift) | ((word as $n) << shift)
+                } else {
+                    self
+                }
+            }
         }
     };
 }
@@ -39,3 +134,238 @@ impl_word!(u32);
 impl_word!(u64);
 impl_word!(u128);
 impl_word!(usize);
+
+/// A bitset of `64 * N` bits, used as the [`Rep`](crate::Enum::Rep) of enums with more values
+/// than fit in a `u128`.
+///
+/// Word `i` holds bits `64 * i` through `64 * i + 63`, so values compare as if they were a
+/// single integer. `#[derive(Enum)]` picks this representation automatically for enums with 128
+/// or more variants.
+///
+/// # Examples
+///
+/// ```
+/// use enumeration::Words;
+///
+/// let bits = Words::<2>::bit(70).union(Words::bit(3));
+/// assert_eq!(bits, Words([1 << 3, 1 << 6]));
+/// assert_eq!(Words::<2>::low_mask(65), Words([u64::MAX, 1]));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[repr(transparent)]
+pub struct Words<const N: usize>(pub [u64; N]);
+
+impl<const N: usize> Words<N> {
+    /// Returns a value with only bit `i` set, or no bits if `i` is out of range.
+    #[inline]
+    pub const fn bit(i: usize) -> Self {
+        let mut words = [0; N];
+        if i < 64 * N {
+            words[i / 64] = 1 << (i % 64);
+        }
+        Self(words)
+    }
+
+    /// Returns a value with the lowest `bits` bits set.
+    #[inline]
+    pub const fn low_mask(bits: usize) -> Self {
+        let mut words = [0; N];
+        let mut i = 0;
+        while i < N {
+            words[i] = if bits >= 64 * (i + 1) {
+                u64::MAX
+            } else if bits > 64 * i {
+                (1 << (bits - 64 * i)) - 1
+            } else {
+                0
+            };
+            i += 1;
+        }
+        Self(words)
+    }
+
+    /// Returns the bitwise OR of two values. Unlike `|`, this can be used in constant
+    /// expressions.
+    #[inline]
+    #[must_use]
+    pub const fn union(self, other: Self) -> Self {
+        let mut words = self.0;
+        let mut i = 0;
+        while i < N {
+            words[i] |= other.0[i];
+            i += 1;
+        }
+        Self(words)
+    }
+
+    #[inline]
+    fn zip_with(mut self, other: Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        for (word, other) in self.0.iter_mut().zip(other.0) {
+            *word = f(*word, other);
+        }
+        self
+    }
+}
+
+impl<const N: usize> Default for Words<N> {
+    #[inline]
+    fn default() -> Self {
+        Self([0; N])
+    }
+}
+
+impl<const N: usize> PartialOrd for Words<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for Words<N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl<const N: usize> Not for Words<N> {
+    type Output = Self;
+
+    #[inline]
+    fn not(mut self) -> Self {
+        for word in &mut self.0 {
+            *word = !*word;
+        }
+        self
+    }
+}
+
+macro_rules! impl_words_op {
+    ($op:ident, $f:ident, $op_assign:ident, $f_assign:ident, $sym:tt) => {
+        impl<const N: usize> $op for Words<N> {
+            type Output = Self;
+
+            #[inline]
+            fn $f(self, rhs: Self) -> Self {
+                self.zip_with(rhs, |a, b| a $sym b)
+            }
+        }
+
+        impl<const N: usize> $op_assign for Words<N> {
+            #[inline]
+            fn $f_assign(&mut self, rhs: Self) {
+                *self = self.zip_with(rhs, |a, b| a $sym b);
+            }
+        }
+    };
+}
+
+impl_words_op!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+impl_words_op!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+impl_words_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+
+impl<const N: usize> private::Sealed for Words<N> {
+    const CHUNK_BYTES: usize = size_of::<u64>();
+}
+
+impl<const N: usize> Wordlike for Words<N> {
+    const ZERO: Self = Self([0; N]);
+    const WORDS: usize = N;
+    #[inline]
+    fn count_ones(this: Self) -> usize {
+        this.0.iter().map(|word| word.count_ones() as usize).sum()
+    }
+    #[inline]
+    fn trailing_zeros(this: Self) -> usize {
+        match this.0.iter().position(|&word| word != 0) {
+            Some(i) => 64 * i + this.0[i].trailing_zeros() as usize,
+            None => 64 * N,
+        }
+    }
+    #[inline]
+    fn incr(mut self) -> Self {
+        for word in &mut self.0 {
+            let (sum, carry) = word.overflowing_add(1);
+            *word = sum;
+            if !carry {
+                break;
+            }
+        }
+        self
+    }
+    #[inline]
+    fn decr(mut self) -> Self {
+        for word in &mut self.0 {
+            let (difference, borrow) = word.overflowing_sub(1);
+            *word = difference;
+            if !borrow {
+                break;
+            }
+        }
+        self
+    }
+    #[inline]
+    fn low_mask(bits: usize) -> Self {
+        Self::low_mask(bits)
+    }
+    #[inline]
+    fn word(this: Self, i: usize) -> u64 {
+        this.0.get(i).copied().unwrap_or(0)
+    }
+    #[inline]
+    fn with_word(mut self, i: usize, word: u64) -> Self {
+        if let Some(slot) = self.0.get_mut(i) {
+            *slot = word;
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_words_carry() {
+        let low = Words([u64::MAX, 0, 5]);
+        assert_eq!(low.incr(), Words([0, 1, 5]));
+        assert_eq!(low.incr().decr(), low);
+        assert_eq!(Words::<2>::ZERO.decr(), Words([u64::MAX; 2]));
+    }
+
+    #[test]
+    fn test_words_order() {
+        assert!(Words([0, 1]) > Words([u64::MAX, 0]));
+        assert!(Words([2, 1]) > Words([1, 1]));
+    }
+
+    #[test]
+    fn test_words_bits() {
+        let mask = Words::<3>::low_mask(130);
+        assert_eq!(Wordlike::count_ones(mask), 130);
+        assert_eq!(Wordlike::trailing_zeros(Words::<3>::bit(129)), 129);
+        assert_eq!(Wordlike::trailing_zeros(Words::<3>::ZERO), 192);
+        assert_eq!(Words::<3>::bit(192), Words::ZERO);
+        assert_eq!(mask & !Words::bit(0), Words([u64::MAX - 1, u64::MAX, 0b11]));
+    }
+
+    #[test]
+    fn test_const_bits() {
+        const RAW: u128 = {
+            let mut raw = 0;
+            const_set_bit(&mut raw, 9);
+            const_set_bit(&mut raw, 100);
+            raw
+        };
+        const WORDS: Words<2> = {
+            let mut raw = Words::ZERO;
+            const_set_bit(&mut raw, 70);
+            raw
+        };
+        assert_eq!(RAW, 1 << 9 | 1 << 100);
+        assert_eq!(WORDS, Words::bit(70));
+        assert!(const_test_bit(&RAW, 100) && !const_test_bit(&RAW, 101));
+        assert!(const_test_bit(&WORDS, 70) && !const_test_bit(&WORDS, 6));
+        assert!(const_test_bit(&0x80_u8, 7));
+    }
+}
@@ -0,0 +1,165 @@
+//! Parsing of `#[enumeration(...)]` attributes.
+
+use syn::{
+    Attribute, Error, Ident, Lit, LitStr, Meta, MetaList, MetaNameValue, NestedMeta, Result, Type,
+};
+
+/// Every item of every `#[enumeration(...)]` attribute, in order.
+fn items(attrs: &[Attribute]) -> Result<Vec<NestedMeta>> {
+    let mut items = Vec::new();
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("enumeration"))
+    {
+        let Meta::List(list) = attr.parse_meta()? else {
+            return Err(Error::new_spanned(attr, "expected #[enumeration(...)]"));
+        };
+        items.extend(list.nested);
+    }
+    Ok(items)
+}
+
+fn unknown(item: NestedMeta) -> Error {
+    Error::new_spanned(item, "unknown enumeration attribute")
+}
+
+/// Options set on the enum itself.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Default)]
+pub struct EnumOptions {
+    /// `#[enumeration(display)]`: implement `Display` with the variant name.
+    pub display: bool,
+    /// `#[enumeration(names)]`: add `as_str` and implement `FromStr`.
+    pub names: bool,
+    /// `#[enumeration(conversions)]`: convert to and from `usize` indices.
+    pub conversions: bool,
+    /// `#[enumeration(unchecked_from_index)]`: implement `Enum::from_index_unchecked` without
+    /// a range check, keeping only a debug assertion.
+    pub unchecked_from_index: bool,
+    /// `#[enumeration(table(name = "NAME", type = "T"))]`: a per-variant constant. May be
+    /// repeated.
+    pub tables: Vec<Table>,
+}
+
+/// A table declared by `#[enumeration(table(...))]`.
+pub struct Table {
+    pub name: Ident,
+    pub ty: Type,
+}
+
+impl Table {
+    fn parse(list: MetaList) -> Result<Self> {
+        let mut name = None;
+        let mut ty = None;
+        for item in &list.nested {
+            let NestedMeta::Meta(Meta::NameValue(meta)) = item else {
+                return Err(unknown(item.clone()));
+            };
+            let Lit::Str(value) = &meta.lit else {
+                return Err(Error::new_spanned(&meta.lit, "expected a string"));
+            };
+            if meta.path.is_ident("name") {
+                name = Some(value.parse::<Ident>().map_err(|_| {
+                    Error::new_spanned(value, "expected an identifier such as \"LABEL\"")
+                })?);
+            } else if meta.path.is_ident("type") {
+                ty = Some(value.parse::<Type>()?);
+            } else {
+                return Err(unknown(item.clone()));
+            }
+        }
+        match (name, ty) {
+            (Some(name), Some(ty)) => Ok(Self { name, ty }),
+            _ => Err(Error::new_spanned(
+                list,
+                "expected table(name = \"...\", type = \"...\")",
+            )),
+        }
+    }
+}
+
+impl EnumOptions {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = Self::default();
+        for item in items(attrs)? {
+            if let NestedMeta::Meta(Meta::List(list)) = item {
+                if !list.path.is_ident("table") {
+                    return Err(unknown(NestedMeta::Meta(Meta::List(list))));
+                }
+                let table = Table::parse(list)?;
+                if options.tables.iter().any(|other| other.name == table.name) {
+                    return Err(Error::new_spanned(&table.name, "duplicate table"));
+                }
+                options.tables.push(table);
+                continue;
+            }
+            let NestedMeta::Meta(Meta::Path(path)) = &item else {
+                return Err(unknown(item));
+            };
+            let flag = if path.is_ident("display") {
+                &mut options.display
+            } else if path.is_ident("names") {
+                &mut options.names
+            } else if path.is_ident("conversions") {
+                &mut options.conversions
+            } else if path.is_ident("unchecked_from_index") {
+                &mut options.unchecked_from_index
+            } else {
+                return Err(unknown(item));
+            };
+            *flag = true;
+        }
+        Ok(options)
+    }
+}
+
+/// Options set on a single variant.
+#[derive(Default)]
+pub struct VariantOptions {
+    /// `#[enumeration(index = N)]`: pin the variant's stable index.
+    pub index: Option<usize>,
+    /// `#[enumeration(group = "name")]`: add the variant to a named group. May be repeated.
+    pub groups: Vec<LitStr>,
+    /// `#[enumeration(deprecated)]`: add the variant to `Enum::DEPRECATED`.
+    pub deprecated: bool,
+    /// `#[enumeration(NAME = value)]`: the variant's entry in a table.
+    pub table_values: Vec<MetaNameValue>,
+}
+
+impl VariantOptions {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = Self::default();
+        for item in items(attrs)? {
+            match item {
+                NestedMeta::Meta(Meta::NameValue(meta)) if meta.path.is_ident("index") => {
+                    let Lit::Int(int) = meta.lit else {
+                        return Err(Error::new_spanned(meta.lit, "expected an integer"));
+                    };
+                    options.index = Some(int.base10_parse()?);
+                }
+                NestedMeta::Meta(Meta::NameValue(meta)) if meta.path.is_ident("group") => {
+                    let Lit::Str(group) = meta.lit else {
+                        return Err(Error::new_spanned(meta.lit, "expected a string"));
+                    };
+                    options.groups.push(group);
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("deprecated") => {
+                    options.deprecated = true;
+                }
+                NestedMeta::Meta(Meta::NameValue(meta)) if meta.path.get_ident().is_some() => {
+                    let name = meta.path.get_ident();
+                    if options
+                        .table_values
+                        .iter()
+                        .any(|other| other.path.get_ident() == name)
+                    {
+                        return Err(Error::new_spanned(meta.path, "duplicate table value"));
+                    }
+                    options.table_values.push(meta);
+                }
+                other => return Err(unknown(other)),
+            }
+        }
+        Ok(options)
+    }
+}
@@ -1,10 +1,14 @@
 use proc_macro::TokenStream;
 use proc_macro2::Span;
-use quote::quote;
+use quote::{quote, ToTokens};
 use std::convert::TryFrom;
+use syn::ext::IdentExt;
 #[allow(clippy::wildcard_imports)]
 use syn::*;
 
+mod attrs;
+use attrs::{EnumOptions, Table, VariantOptions};
+
 #[allow(dead_code)]
 #[repr(C)]
 enum SizedEnum {
@@ -15,84 +19,445 @@ enum SizedEnum {
 /// Probably 32.
 const C_ENUM_BITS: usize = std::mem::size_of::<SizedEnum>() * 8;
 
+/// Derives `Enum` for an enum whose variants carry no data.
+///
+/// Variants may hold `PhantomData` fields, which lets a generic enum use its type parameters.
+/// Such a variant is a value like any other, so it is usually the last variant and often
+/// `#[enumeration(deprecated)]`. The implementations for a generic enum require it to be `Copy`
+/// and `Ord`, which derived impls make conditional on its type parameters.
+///
+/// `Enum::Rep` is the smallest unsigned integer with more bits than the enum has variants. Enums
+/// with 128 or more variants use `enumeration::Words` instead, which has no integer operators,
+/// so `EnumSet` constants for them are built with `Words::union` rather than `|`.
+///
+/// Besides the `Enum` implementation, the derive always adds `VARIANT_COUNT`, an associated
+/// constant equal to `Enum::SIZE`, and `const fn bit(self)`, a version of `Enum::bit` that can
+/// be used in constant expressions. Every generated item is documented.
+///
+/// Enums with explicit discriminants also get `const fn discriminant(self) -> i64` and
+/// `const fn from_discriminant(i64) -> Option<Self>`. Indices stay dense and follow the order of
+/// the discriminants, as derived `Ord` does, whatever order the variants are declared in. Every
+/// discriminant must fit in `i64`. Discriminants given by expressions other than integer literals
+/// cannot be sorted by the derive, so they must increase in declaration order.
+///
+/// # Enum options
+///
+/// Extra code can be generated for an individual enum with `#[enumeration(...)]`:
+///
+/// - `display`: implements `Display` by writing the variant name.
+/// - `names`: adds `const fn as_str(self) -> &'static str` and implements `FromStr`, which
+///   fails with `ParseEnumError`.
+/// - `conversions`: implements `From<Self> for usize` and `TryFrom<usize>`, which fails with
+///   `OutOfRange`.
+/// - `unchecked_from_index`: implements `Enum::from_index_unchecked` without a range check, for
+///   callers that already know the index came from `index`. Out-of-range indices are still
+///   caught by a debug assertion. The generated code uses `unsafe`, so this is unavailable in
+///   crates that forbid it.
+/// - `table(name = "NAME", type = "T")`: declares a table of per-variant constants of type `T`,
+///   which every variant fills in with `#[enumeration(NAME = value)]`. This generates an
+///   associated `const NAMES: [T; SIZE]` in index order and a `const fn name(self) -> T`
+///   accessor. Several tables may be declared.
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use enumeration::Enum;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// #[enumeration(display, names, conversions)]
+/// pub enum Suit { Clubs, Diamonds, Hearts, Spades }
+///
+/// assert_eq!(Suit::Hearts.to_string(), "Hearts");
+/// assert_eq!("Spades".parse(), Ok(Suit::Spades));
+/// assert_eq!(usize::from(Suit::Diamonds), 1);
+/// assert_eq!(Suit::try_from(0), Ok(Suit::Clubs));
+/// ```
+///
+/// ```
+/// use enumeration::Enum;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// #[enumeration(table(name = "LABEL", type = "&'static str"))]
+/// #[enumeration(table(name = "WEIGHT", type = "u32"))]
+/// pub enum Size {
+///     #[enumeration(LABEL = "S", WEIGHT = 1)]
+///     Small,
+///     #[enumeration(LABEL = "M", WEIGHT = 2)]
+///     Medium,
+///     #[enumeration(LABEL = "L", WEIGHT = 4)]
+///     Large,
+/// }
+///
+/// assert_eq!(Size::LABELS, ["S", "M", "L"]);
+/// assert_eq!(Size::Medium.label(), "M");
+/// assert_eq!(Size::WEIGHTS[Size::Large.index()], 4);
+/// ```
+///
+/// # Variant options
+///
+/// - `#[enumeration(index = N)]`: pins the variant's `Enum::stable_index`.
+/// - `#[enumeration(group = "name")]`: adds the variant to a group. Each group becomes an
+///   associated `EnumSet<Self>` constant named after the group in upper snake case. A variant
+///   may belong to several groups.
+/// - `#[enumeration(deprecated)]`: adds the variant to `Enum::DEPRECATED`. Deprecated variants
+///   keep their indices, but `Enum::enumerate` skips them; `Enum::enumerate_all` yields them.
+///
+/// ```
+/// use enumeration::{Enum, enums};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+/// pub enum Letter {
+///     #[enumeration(group = "vowel")]
+///     A,
+///     B,
+///     C,
+///     #[enumeration(group = "vowel")]
+///     E,
+///     #[enumeration(group = "vowel", group = "sometimes vowel")]
+///     Y,
+/// }
+///
+/// assert_eq!(Letter::VOWEL, enums![Letter::A, Letter::E, Letter::Y]);
+/// assert_eq!(Letter::SOMETIMES_VOWEL, enums![Letter::Y]);
+/// ```
 #[allow(clippy::too_many_lines)]
-#[proc_macro_derive(Enum)]
+#[proc_macro_derive(Enum, attributes(enumeration))]
 pub fn derive_enum(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as ItemEnum);
+    let mut input = parse_macro_input!(input as ItemEnum);
 
-    let name = input.ident;
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    // Stable indices follow declaration order, so collect them before sorting by discriminant.
+    let mut stable_indices = match stable_indices(&input.variants) {
+        Ok(stable_indices) => stable_indices,
+        Err(e) => return TokenStream::from(e.into_compile_error()),
+    };
+    let sorted = match discriminant_order(&input.variants) {
+        Ok(sorted) => sorted,
+        Err(e) => return TokenStream::from(e.into_compile_error()),
+    };
+    if let Some(order) = &sorted {
+        input.variants = order.iter().map(|&i| input.variants[i].clone()).collect();
+        if let Some(indices) = &mut stable_indices {
+            *indices = order.iter().map(|&i| indices[i]).collect();
+        }
+    }
 
-    assert!(!input.variants.is_empty(), "type must not be empty");
+    let name = input.ident;
+    let generics = bounded_generics(&name, &input.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let path = variant_path(&name, &generics);
 
-    if let Some(variant) = input.variants.iter().find(|x| x.discriminant.is_some()) {
+    if input.variants.is_empty() {
         return TokenStream::from(
-            syn::Error::new_spanned(variant, "manual discriminants are unsupported")
-                .into_compile_error(),
+            syn::Error::new(name.span(), "type must not be empty").into_compile_error(),
         );
     }
 
+    let has_discriminants = input.variants.iter().any(|x| x.discriminant.is_some());
+    let has_fields = input.variants.iter().any(|x| !x.fields.is_empty());
+
+    if let Err(e) = check_fields(&input.variants) {
+        return TokenStream::from(e.into_compile_error());
+    }
+    let patterns = input
+        .variants
+        .iter()
+        .map(|variant| variant_pattern(&path, variant))
+        .collect::<Vec<_>>();
+    let values = input
+        .variants
+        .iter()
+        .map(|variant| variant_value(&path, variant))
+        .collect::<Vec<_>>();
+    let variants = &input.variants;
+    let value_of = |ident: &Ident| {
+        let i = variants.iter().position(|x| x.ident == *ident);
+        &values[i.unwrap()]
+    };
+
     let size = input.variants.len();
-    let size32 = u32::try_from(size).unwrap();
+    let rep = Rep::for_size(size + 1);
 
-    let Some(rep) = rep_for_size(size + 1) else {
-        panic!("too many variants");
+    let options = match EnumOptions::parse(&input.attrs) {
+        Ok(options) => options,
+        Err(e) => return TokenStream::from(e.into_compile_error()),
     };
 
-    let min_bound = &input.variants.first().unwrap().ident;
-    let max_bound = &input.variants.last().unwrap().ident;
+    let groups = match groups(&input.variants) {
+        Ok(groups) => groups,
+        Err(e) => return TokenStream::from(e.into_compile_error()),
+    };
+
+    let deprecated = match deprecated_variants(&input.variants) {
+        Ok(variants) if variants.is_empty() => quote!(),
+        Ok(variants) => {
+            let union = rep.union(variants.iter().map(|variant| {
+                let value = value_of(variant);
+                quote!(#value.bit())
+            }));
+            quote! {
+            const DEPRECATED: ::enumeration::EnumSet<Self> =
+                ::enumeration::EnumSet::from_raw_unchecked(#union);
+            }
+        }
+        Err(e) => return TokenStream::from(e.into_compile_error()),
+    };
+
+    let tables = match table_values(&input.variants, &options.tables) {
+        Ok(tables) => tables,
+        Err(e) => return TokenStream::from(e.into_compile_error()),
+    };
+
+    let lint_attrs = input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("allow"))
+        .collect::<Vec<_>>();
+    let lints = quote!(#(#lint_attrs)*);
+
+    let names = input
+        .variants
+        .iter()
+        .map(|variant| variant.ident.unraw().to_string());
+
+    let min_bound = values.first().unwrap();
+    let max_bound = values.last().unwrap();
 
     #[cfg(feature = "inline")]
     let inline = quote!(#[inline]);
     #[cfg(not(feature = "inline"))]
     let inline = quote!();
 
+    let stable = match stable_indices {
+        Some(stable_indices) => {
+            let stable_indices2 = stable_indices.clone();
+            quote! {
+                const STABLE_INDICES: bool = true;
+
+                #inline
+                fn stable_index(self) -> usize {
+                    match self {
+                        #(#patterns => #stable_indices,)*
+                    }
+                }
+
+                #inline
+                fn from_stable_index(i: usize) -> ::core::option::Option<Self> {
+                    match i {
+                        #(#stable_indices2 => ::core::option::Option::Some(#values),)*
+                        _ => ::core::option::Option::None,
+                    }
+                }
+            }
+        }
+        None => quote!(),
+    };
+
+    let unchecked_from_index = if options.unchecked_from_index {
+        let indices = 0..size;
+        quote! {
+            #inline
+            unsafe fn from_index_unchecked(i: usize) -> Self {
+                ::core::debug_assert!(i < #size, "index out of range");
+                match i {
+                    #(#indices => #values,)*
+                    // SAFETY: the caller guarantees that `i < SIZE`.
+                    _ => unsafe { ::core::hint::unreachable_unchecked() },
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let bitmask = rep.bitmask(size);
+    let bit_doc = quote! {
+        /// Returns the bit that represents the variant in an
+        /// [`EnumSet`](::enumeration::EnumSet). This is the same as
+        /// [`Enum::bit`](::enumeration::Enum::bit), but can be used in constant expressions.
+        ///
+        /// Generated by `#[derive(Enum)]`.
+    };
+    let self_bit = rep.bit(&quote!((self as usize)));
+    let index_bit = rep.bit(&quote!(<Self as ::enumeration::Enum>::index(self)));
     let prologue = quote! {
         type Rep = #rep;
         const SIZE: usize = #size;
-        const MIN: Self = #name::#min_bound;
-        const MAX: Self = #name::#max_bound;
-        const BITMASK: Self::Rep = !0 >> (Self::Rep::BITS - #size32);
+        const MIN: Self = #min_bound;
+        const MAX: Self = #max_bound;
+        const BITMASK: Self::Rep = #bitmask;
+        const NAMES: &'static [&'static str] = &[#(#names),*];
+        #deprecated
+        #stable
+        #unchecked_from_index
     };
 
-    let idx = match find_repr(&input.attrs) {
+    let repr = find_repr(&input.attrs);
+    // An explicit integer repr needs no size check, and `align(N)` would make the sizes differ.
+    let guessed_repr = !repr.as_ref().is_some_and(is_int_repr);
+    let idx = match repr {
+        None if size > 256 => Some(Ident::new("u16", Span::call_site())),
         None if size > 2 => Some(Ident::new("u8", Span::call_site())),
         idx => idx,
     };
 
-    let expanded = if let Some(idx) = idx {
-        let size_assertion_error = format!("unable to find a suitable repr\nspecify #[repr(u8)] or another integer type\n(guessed {idx})");
+    let expanded = if has_discriminants || has_fields {
+        let indices = 0..size;
+        let index_match = quote! {
+            match self {
+                #(#patterns => #indices,)*
+            }
+        };
+        let match_bit = rep.bit(&quote!((#index_match)));
+        let indices = 0..size;
+        // Discriminants can only be read with `as` from fieldless enums.
+        let discriminants = if has_fields {
+            quote!()
+        } else {
+            let variants = input
+                .variants
+                .iter()
+                .map(|variant| &variant.ident)
+                .collect::<Vec<_>>();
+            // Literal discriminants were sorted and range-checked above; anything else has to be
+            // checked once the compiler has evaluated it.
+            let checks = if sorted.is_some() {
+                quote!()
+            } else {
+                let increasing = variants.windows(2).map(|pair| {
+                    let (a, b) = (pair[0], pair[1]);
+                    quote!((#name::#a as i128) < (#name::#b as i128))
+                });
+                let in_range = variants.iter().map(|variant| {
+                    quote!((#name::#variant as i128) >= i64::MIN as i128 && (#name::#variant as i128) <= i64::MAX as i128)
+                });
+                quote! {
+                    #lints
+                    const _: () = assert!(
+                        true #(&& #increasing)*,
+                        "discriminants that are not integer literals must be strictly increasing in declaration order",
+                    );
+
+                    #lints
+                    const _: () = assert!(
+                        true #(&& #in_range)*,
+                        "discriminants must fit in `i64`",
+                    );
+                }
+            };
+            quote! {
+                #checks
+
+                #lints
+                impl #impl_generics #name #ty_generics #where_clause {
+                    /// Returns the discriminant of the value.
+                    ///
+                    /// Generated by `#[derive(Enum)]`.
+                    #inline
+                    pub const fn discriminant(self) -> i64 {
+                        self as i64
+                    }
+
+                    /// Inverse of `discriminant`. Returns `None` if no value has the given discriminant.
+                    ///
+                    /// Generated by `#[derive(Enum)]`.
+                    #inline
+                    pub const fn from_discriminant(discriminant: i64) -> ::core::option::Option<Self> {
+                        match discriminant {
+                            #(_ if discriminant == #name::#variants as i64 => ::core::option::Option::Some(#name::#variants),)*
+                            _ => ::core::option::Option::None,
+                        }
+                    }
+                }
+            }
+        };
 
         quote! {
-            const _: () = assert!(
-                std::mem::size_of::<#name>() == std::mem::size_of::<#idx>(),
-                #size_assertion_error,
-            );
+            #discriminants
 
-            impl #impl_generics Enum for #name #ty_generics #where_clause {
+            #lints
+            #[automatically_derived]
+            impl #impl_generics ::enumeration::Enum for #name #ty_generics #where_clause {
                 #prologue
 
                 #inline
-                fn succ(self) -> Option<Self> {
-                    if self == #name::#max_bound {
-                        None
-                    } else {
-                        Some(unsafe { std::mem::transmute(self as #idx + 1) })
+                fn succ(self) -> ::core::option::Option<Self> {
+                    <Self as ::enumeration::Enum>::from_index(<Self as ::enumeration::Enum>::index(self) + 1)
+                }
+
+                #inline
+                fn pred(self) -> ::core::option::Option<Self> {
+                    match <Self as ::enumeration::Enum>::index(self).checked_sub(1) {
+                        ::core::option::Option::Some(i) => <Self as ::enumeration::Enum>::from_index(i),
+                        ::core::option::Option::None => ::core::option::Option::None,
                     }
                 }
 
                 #inline
-                fn pred(self) -> Option<Self> {
-                    if self == #name::#min_bound {
-                        None
-                    } else {
-                        Some(unsafe { std::mem::transmute(self as #idx - 1) })
+                fn bit(self) -> Self::Rep {
+                    #index_bit
+                }
+
+                #inline
+                fn index(self) -> usize {
+                    #index_match
+                }
+
+                #inline
+                fn from_index(i: usize) -> ::core::option::Option<Self> {
+                    match i {
+                        #(#indices => ::core::option::Option::Some(#values),)*
+                        _ => ::core::option::Option::None,
+                    }
+                }
+            }
+
+            #lints
+            impl #impl_generics #name #ty_generics #where_clause {
+                #bit_doc
+                #inline
+                pub const fn bit(self) -> #rep {
+                    #match_bit
+                }
+            }
+        }
+    } else if let Some(idx) = idx {
+        let size_assertion_error = format!("unable to find a suitable repr\nspecify #[repr(u8)] or another integer type\n(guessed {idx})");
+        let indices = 0..size;
+        let size_assertion = if guessed_repr {
+            quote! {
+                #lints
+                const _: () = assert!(
+                    ::core::mem::size_of::<#name>() == ::core::mem::size_of::<#idx>(),
+                    #size_assertion_error,
+                );
+            }
+        } else {
+            quote!()
+        };
+
+        quote! {
+            #size_assertion
+
+            #lints
+            #[automatically_derived]
+            impl #impl_generics ::enumeration::Enum for #name #ty_generics #where_clause {
+                #prologue
+
+                #inline
+                fn succ(self) -> ::core::option::Option<Self> {
+                    <Self as ::enumeration::Enum>::from_index(self as usize + 1)
+                }
+
+                #inline
+                fn pred(self) -> ::core::option::Option<Self> {
+                    match (self as usize).checked_sub(1) {
+                        ::core::option::Option::Some(i) => <Self as ::enumeration::Enum>::from_index(i),
+                        ::core::option::Option::None => ::core::option::Option::None,
                     }
                 }
 
                 #inline
                 fn bit(self) -> Self::Rep {
-                    1 << (self as #idx)
+                    #self_bit
                 }
 
                 #inline
@@ -101,41 +466,43 @@ pub fn derive_enum(input: TokenStream) -> TokenStream {
                 }
 
                 #inline
-                fn from_index(i: usize) -> Option<Self> {
-                    if i < #size {
-                        Some(unsafe { std::mem::transmute(i as #idx) })
-                    } else {
-                        None
+                fn from_index(i: usize) -> ::core::option::Option<Self> {
+                    match i {
+                        #(#indices => ::core::option::Option::Some(#values),)*
+                        _ => ::core::option::Option::None,
                     }
                 }
             }
 
+            #lints
             impl #impl_generics #name #ty_generics #where_clause {
-                #[doc(hidden)]
+                #bit_doc
                 #inline
                 pub const fn bit(self) -> #rep {
-                    1 << (self as #idx)
+                    #self_bit
                 }
             }
         }
     } else if size == 1 {
         quote! {
-            impl #impl_generics Enum for #name #ty_generics #where_clause {
+            #lints
+            #[automatically_derived]
+            impl #impl_generics ::enumeration::Enum for #name #ty_generics #where_clause {
                 #prologue
 
                 #inline
-                fn succ(self) -> Option<Self> {
-                    None
+                fn succ(self) -> ::core::option::Option<Self> {
+                    ::core::option::Option::None
                 }
 
                 #inline
-                fn pred(self) -> Option<Self> {
-                    None
+                fn pred(self) -> ::core::option::Option<Self> {
+                    ::core::option::Option::None
                 }
 
                 #inline
                 fn bit(self) -> Self::Rep {
-                    0
+                    1
                 }
 
                 #inline
@@ -144,46 +511,49 @@ pub fn derive_enum(input: TokenStream) -> TokenStream {
                 }
 
                 #inline
-                fn from_index(i: usize) -> Option<Self> {
+                fn from_index(i: usize) -> ::core::option::Option<Self> {
                     match i {
-                        0 => Some(#name::#min_bound),
-                        _ => None,
+                        0 => ::core::option::Option::Some(#min_bound),
+                        _ => ::core::option::Option::None,
                     }
                 }
             }
 
+            #lints
             impl #impl_generics #name #ty_generics #where_clause {
-                #[doc(hidden)]
+                #bit_doc
                 #inline
                 pub const fn bit(self) -> #rep {
-                    0
+                    1
                 }
             }
         }
     } else {
         quote! {
-            impl #impl_generics Enum for #name #ty_generics #where_clause {
+            #lints
+            #[automatically_derived]
+            impl #impl_generics ::enumeration::Enum for #name #ty_generics #where_clause {
                 #prologue
 
                 #inline
-                fn succ(self) -> Option<Self> {
+                fn succ(self) -> ::core::option::Option<Self> {
                     match self {
-                        #name::#max_bound => None,
-                        #name::#min_bound => Some(#name::#max_bound)
+                        #max_bound => ::core::option::Option::None,
+                        #min_bound => ::core::option::Option::Some(#max_bound)
                     }
                 }
 
                 #inline
-                fn pred(self) -> Option<Self> {
+                fn pred(self) -> ::core::option::Option<Self> {
                     match self {
-                        #name::#min_bound => None,
-                        #name::#max_bound => Some(#name::#min_bound)
+                        #min_bound => ::core::option::Option::None,
+                        #max_bound => ::core::option::Option::Some(#min_bound)
                     }
                 }
 
                 #inline
                 fn bit(self) -> Self::Rep {
-                    self as #rep
+                    1 << (self as #rep)
                 }
 
                 #inline
@@ -192,66 +562,549 @@ pub fn derive_enum(input: TokenStream) -> TokenStream {
                 }
 
                 #inline
-                fn from_index(i: usize) -> Option<Self> {
+                fn from_index(i: usize) -> ::core::option::Option<Self> {
                     match i {
-                        0 => Some(#name::#min_bound),
-                        1 => Some(#name::#max_bound),
-                        _ => None,
+                        0 => ::core::option::Option::Some(#min_bound),
+                        1 => ::core::option::Option::Some(#max_bound),
+                        _ => ::core::option::Option::None,
                     }
                 }
             }
 
+            #lints
             impl #impl_generics #name #ty_generics #where_clause {
-                #[doc(hidden)]
+                #bit_doc
                 #inline
                 pub const fn bit(self) -> #rep {
-                    self as #rep
+                    1 << (self as #rep)
                 }
             }
         }
     };
 
-    TokenStream::from(expanded)
-}
+    let names = input
+        .variants
+        .iter()
+        .map(|variant| variant.ident.unraw().to_string())
+        .collect::<Vec<_>>();
+
+    let display_impl = if options.display {
+        quote! {
+            #lints
+            #[automatically_derived]
+            impl #impl_generics ::core::fmt::Display for #name #ty_generics #where_clause {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.pad(match self {
+                        #(#patterns => #names,)*
+                    })
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let names_impl = if options.names {
+        quote! {
+            #lints
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Returns the name of the variant.
+                ///
+                /// Generated by `#[derive(Enum)]`.
+                #inline
+                pub const fn as_str(self) -> &'static str {
+                    match self {
+                        #(#patterns => #names,)*
+                    }
+                }
+            }
+
+            #lints
+            #[automatically_derived]
+            impl #impl_generics ::core::str::FromStr for #name #ty_generics #where_clause {
+                type Err = ::enumeration::ParseEnumError;
+
+                fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                    match s {
+                        #(#names => ::core::result::Result::Ok(#values),)*
+                        _ => ::core::result::Result::Err(::enumeration::ParseEnumError),
+                    }
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
 
-fn rep_for_size(size: usize) -> Option<proc_macro2::TokenStream> {
-    if size <= 8 {
-        Some(quote!(u8))
-    } else if size <= 16 {
-        Some(quote!(u16))
-    } else if size <= 32 {
-        Some(quote!(u32))
-    } else if size <= 64 {
-        Some(quote!(u64))
-    } else if size <= 128 {
-        Some(quote!(u128))
+    let conversions_impl = if options.conversions {
+        quote! {
+            #lints
+            #[automatically_derived]
+            impl #impl_generics ::core::convert::From<#name #ty_generics> for usize #where_clause {
+                #inline
+                fn from(value: #name #ty_generics) -> Self {
+                    <#name #ty_generics as ::enumeration::Enum>::index(value)
+                }
+            }
+
+            #lints
+            #[automatically_derived]
+            impl #impl_generics ::core::convert::TryFrom<usize> for #name #ty_generics #where_clause {
+                type Error = ::enumeration::OutOfRange;
+
+                #inline
+                fn try_from(index: usize) -> ::core::result::Result<Self, Self::Error> {
+                    <Self as ::enumeration::Enum>::try_from_index(index)
+                }
+            }
+        }
     } else {
-        None
+        quote!()
+    };
+
+    let groups_impl = if groups.is_empty() {
+        quote!()
+    } else {
+        let consts = groups.iter().map(|(group, members)| {
+            let union = rep.union(members.iter().map(|member| {
+                let value = value_of(member);
+                quote!(#value.bit())
+            }));
+            let doc = format!(
+                "Variants in the `{}` group.\n\nGenerated by `#[derive(Enum)]`.",
+                group.value()
+            );
+            let ident = Ident::new(&group_const_name(&group.value()), group.span());
+            quote! {
+                #[doc = #doc]
+                pub const #ident: ::enumeration::EnumSet<Self> =
+                    ::enumeration::EnumSet::from_raw_unchecked(#union);
+            }
+        });
+        quote! {
+            #lints
+            impl #impl_generics #name #ty_generics #where_clause {
+                #(#consts)*
+            }
+        }
+    };
+
+    let tables_impl = if tables.is_empty() {
+        quote!()
+    } else {
+        let items = options.tables.iter().zip(&tables).map(|(table, values)| {
+            let ty = &table.ty;
+            let consts = Ident::new(&format!("{}S", table.name), table.name.span());
+            let accessor = Ident::new(&table.name.to_string().to_lowercase(), table.name.span());
+            let consts_doc = format!(
+                "The `{}` of each variant, in index order.\n\nGenerated by `#[derive(Enum)]`.",
+                table.name
+            );
+            let accessor_doc = format!(
+                "Returns the `{}` of the variant.\n\nGenerated by `#[derive(Enum)]`.",
+                table.name
+            );
+            quote! {
+                #[doc = #consts_doc]
+                pub const #consts: [#ty; #size] = [#(#values),*];
+
+                #[doc = #accessor_doc]
+                #inline
+                pub const fn #accessor(self) -> #ty {
+                    match self {
+                        #(#patterns => #values,)*
+                    }
+                }
+            }
+        });
+        quote! {
+            #lints
+            impl #impl_generics #name #ty_generics #where_clause {
+                #(#items)*
+            }
+        }
+    };
+
+    let count_impl = quote! {
+        #lints
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// The number of variants, equal to [`Enum::SIZE`](::enumeration::Enum::SIZE).
+            ///
+            /// Generated by `#[derive(Enum)]`.
+            pub const VARIANT_COUNT: usize = #size;
+        }
+    };
+
+    TokenStream::from(quote! {
+        #expanded
+        #count_impl
+        #display_impl
+        #names_impl
+        #conversions_impl
+        #groups_impl
+        #tables_impl
+    })
+}
+
+/// The `Enum::Rep` of a derived type: the smallest integer that fits, or `Words` past 128 bits.
+struct Rep {
+    ty: proc_macro2::TokenStream,
+    /// Whether the type is `Words`, which has no integer operators.
+    wide: bool,
+}
+
+impl Rep {
+    fn for_size(size: usize) -> Self {
+        let int = |ty| Self { ty, wide: false };
+        if size <= 8 {
+            int(quote!(u8))
+        } else if size <= 16 {
+            int(quote!(u16))
+        } else if size <= 32 {
+            int(quote!(u32))
+        } else if size <= 64 {
+            int(quote!(u64))
+        } else if size <= 128 {
+            int(quote!(u128))
+        } else {
+            let words = size.div_ceil(64);
+            Self {
+                ty: quote!(::enumeration::Words<#words>),
+                wide: true,
+            }
+        }
+    }
+
+    /// A constant expression for the bit at `index`.
+    fn bit(&self, index: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let ty = &self.ty;
+        if self.wide {
+            quote!(<#ty>::bit(#index))
+        } else {
+            quote!(1 << #index)
+        }
+    }
+
+    /// A constant expression for the lowest `size` bits.
+    fn bitmask(&self, size: usize) -> proc_macro2::TokenStream {
+        let ty = &self.ty;
+        if self.wide {
+            quote!(<#ty>::low_mask(#size))
+        } else {
+            let size = u32::try_from(size).unwrap();
+            quote!(!0 >> (Self::Rep::BITS - #size))
+        }
+    }
+
+    /// A constant expression for the union of `bits`.
+    fn union<I>(&self, bits: I) -> proc_macro2::TokenStream
+    where
+        I: IntoIterator<Item = proc_macro2::TokenStream>,
+    {
+        let ty = &self.ty;
+        let bits = bits.into_iter();
+        if self.wide {
+            quote!(<#ty>::low_mask(0) #(.union(#bits))*)
+        } else {
+            quote!(0 #(| #bits)*)
+        }
     }
 }
 
+impl quote::ToTokens for Rep {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        self.ty.to_tokens(tokens);
+    }
+}
+
+/// Adds a `Self: Copy + Ord` bound for generic enums, since derived `Copy` and `Ord`
+/// implementations only hold for some type arguments.
+fn bounded_generics(name: &Ident, generics: &Generics) -> Generics {
+    let mut generics = generics.clone();
+    if generics.type_params().next().is_some() {
+        let (_, ty_generics, _) = generics.split_for_impl();
+        let bound: WherePredicate =
+            parse_quote!(#name #ty_generics: ::core::marker::Copy + ::core::cmp::Ord);
+        generics.make_where_clause().predicates.push(bound);
+    }
+    generics
+}
+
+/// The path that unit and tuple variants are named through, with explicit generic arguments
+/// so that constants of generic enums need no inference.
+fn variant_path(name: &Ident, generics: &Generics) -> proc_macro2::TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let turbofish = ty_generics.as_turbofish();
+    quote!(#name #turbofish)
+}
+
+/// Checks that every variant field is a `PhantomData`, which is the only way to use a type
+/// parameter in an enum whose variants carry no data.
+fn check_fields<'a, I>(variants: I) -> Result<()>
+where
+    I: IntoIterator<Item = &'a Variant>,
+{
+    for field in variants.into_iter().flat_map(|variant| &variant.fields) {
+        let is_phantom = match &field.ty {
+            Type::Path(ty) => ty
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "PhantomData"),
+            _ => false,
+        };
+        if !is_phantom {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                "variant fields must be `PhantomData`",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// A pattern that matches `variant`, ignoring its fields.
+fn variant_pattern(path: &proc_macro2::TokenStream, variant: &Variant) -> proc_macro2::TokenStream {
+    let ident = &variant.ident;
+    match variant.fields {
+        // Paths to struct-like variants cannot take lifetime arguments, so go through `Self`.
+        Fields::Named(_) => quote!(Self::#ident { .. }),
+        Fields::Unnamed(_) => quote!(#path::#ident(..)),
+        Fields::Unit => quote!(#path::#ident),
+    }
+}
+
+/// A constant expression for `variant`, with every field set to `PhantomData`.
+fn variant_value(path: &proc_macro2::TokenStream, variant: &Variant) -> proc_macro2::TokenStream {
+    let ident = &variant.ident;
+    let phantom = quote!(::core::marker::PhantomData);
+    match &variant.fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            quote!(Self::#ident { #(#names: #phantom),* })
+        }
+        Fields::Unnamed(fields) => {
+            let phantoms = fields.unnamed.iter().map(|_| &phantom);
+            quote!(#path::#ident(#(#phantoms),*))
+        }
+        Fields::Unit => quote!(#path::#ident),
+    }
+}
+
+/// Reads the discriminant of every variant, if each one is an integer literal or implicit, and
+/// returns the order of the variants by discriminant. Derived `Ord` compares discriminants, so
+/// this is also the order of indices. Returns `None` if some discriminant is another kind of
+/// expression, which cannot be evaluated here.
+fn discriminant_order<'a, I>(variants: I) -> Result<Option<Vec<usize>>>
+where
+    I: IntoIterator<Item = &'a Variant>,
+{
+    let mut next = 0;
+    let mut discriminants = Vec::new();
+    for variant in variants {
+        let (value, span) = match &variant.discriminant {
+            Some((_, expr)) => match int_literal(expr)? {
+                Some(value) => (value, expr.to_token_stream()),
+                None => return Ok(None),
+            },
+            None => (next, variant.ident.to_token_stream()),
+        };
+        if i64::try_from(value).is_err() {
+            return Err(syn::Error::new_spanned(
+                span,
+                "discriminant does not fit in `i64`",
+            ));
+        }
+        discriminants.push(value);
+        next = value + 1;
+    }
+    let mut order = (0..discriminants.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&i| discriminants[i]);
+    Ok(Some(order))
+}
+
+/// Evaluates an integer literal, possibly negated or parenthesized. Values too large for `i128`
+/// saturate, since they do not fit in `i64` either.
+fn int_literal(expr: &Expr) -> Result<Option<i128>> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => {
+            let value = int.base10_parse::<u128>()?;
+            Ok(Some(i128::try_from(value).unwrap_or(i128::MAX)))
+        }
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => Ok(int_literal(expr)?.map(|value| -value)),
+        Expr::Paren(ExprParen { expr, .. }) | Expr::Group(ExprGroup { expr, .. }) => {
+            int_literal(expr)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Collects the indices pinned by `#[enumeration(index = N)]`, or `None` if no variant is
+/// pinned. Like discriminants, a variant without the attribute follows the previous one.
+fn stable_indices<'a, I>(variants: I) -> Result<Option<Vec<usize>>>
+where
+    I: IntoIterator<Item = &'a Variant>,
+{
+    let mut pinned = false;
+    let mut next = Some(0);
+    let mut indices = Vec::new();
+    for variant in variants {
+        let index = match VariantOptions::parse(&variant.attrs)?.index {
+            Some(index) => {
+                pinned = true;
+                index
+            }
+            None => next.ok_or_else(|| {
+                syn::Error::new_spanned(&variant.ident, "stable index overflows usize")
+            })?,
+        };
+        if indices.contains(&index) {
+            return Err(syn::Error::new_spanned(
+                &variant.ident,
+                format!("duplicate stable index {index}"),
+            ));
+        }
+        indices.push(index);
+        next = index.checked_add(1);
+    }
+    Ok(if pinned { Some(indices) } else { None })
+}
+
+/// Collects the members of each `#[enumeration(group = "...")]` group, in order of first use.
+fn groups<'a, I>(variants: I) -> Result<Vec<(LitStr, Vec<&'a Ident>)>>
+where
+    I: IntoIterator<Item = &'a Variant>,
+{
+    let mut groups: Vec<(LitStr, Vec<&Ident>)> = Vec::new();
+    for variant in variants {
+        for group in VariantOptions::parse(&variant.attrs)?.groups {
+            let name = group_const_name(&group.value());
+            if parse_str::<Ident>(&name).is_err() {
+                return Err(syn::Error::new_spanned(group, "invalid group name"));
+            }
+            match groups
+                .iter_mut()
+                .find(|(other, _)| group_const_name(&other.value()) == name)
+            {
+                Some((_, members)) if members.contains(&&variant.ident) => {
+                    return Err(syn::Error::new_spanned(
+                        group,
+                        format!("variant is already in group {name}"),
+                    ));
+                }
+                Some((_, members)) => members.push(&variant.ident),
+                None => groups.push((group, vec![&variant.ident])),
+            }
+        }
+    }
+    Ok(groups)
+}
+
+/// Collects the variants marked `#[enumeration(deprecated)]`.
+fn deprecated_variants<'a, I>(variants: I) -> Result<Vec<&'a Ident>>
+where
+    I: IntoIterator<Item = &'a Variant>,
+{
+    let mut deprecated = Vec::new();
+    for variant in variants {
+        if VariantOptions::parse(&variant.attrs)?.deprecated {
+            deprecated.push(&variant.ident);
+        }
+    }
+    Ok(deprecated)
+}
+
+/// Collects each variant's `#[enumeration(NAME = value)]` entry for every table, in table
+/// order and then variant order.
+fn table_values<'a, I>(variants: I, tables: &[Table]) -> Result<Vec<Vec<Lit>>>
+where
+    I: IntoIterator<Item = &'a Variant>,
+{
+    let mut values = vec![Vec::new(); tables.len()];
+    for variant in variants {
+        let mut entries = VariantOptions::parse(&variant.attrs)?.table_values;
+        for (table, values) in tables.iter().zip(&mut values) {
+            let Some(i) = entries
+                .iter()
+                .position(|entry| entry.path.is_ident(&table.name))
+            else {
+                return Err(syn::Error::new_spanned(
+                    &variant.ident,
+                    format!("missing value for table {}", table.name),
+                ));
+            };
+            values.push(entries.swap_remove(i).lit);
+        }
+        if let Some(entry) = entries.first() {
+            return Err(syn::Error::new_spanned(
+                entry,
+                "unknown enumeration attribute",
+            ));
+        }
+    }
+    Ok(values)
+}
+
+/// `"sometimes vowel"` -> `SOMETIMES_VOWEL`.
+fn group_const_name(group: &str) -> String {
+    group
+        .trim()
+        .chars()
+        .map(|c| match c {
+            ' ' | '-' => '_',
+            c => c.to_ascii_uppercase(),
+        })
+        .collect()
+}
+
+/// Finds the integer type an enum is represented as, scanning every item of every `repr`
+/// attribute. Modifiers such as `align(N)`, `packed` and `transparent` do not affect the
+/// discriminant type and are skipped. An explicit integer type takes precedence over `C`.
 fn find_repr(attrs: &[Attribute]) -> Option<Ident> {
-    let repr = attrs
+    let items = attrs
         .iter()
+        .filter(|attr| attr.path.is_ident("repr"))
         .map(Attribute::parse_meta)
         .filter_map(Result::ok)
-        .filter(|x| x.path().is_ident("repr"))
-        .filter_map(|x| match x {
-            Meta::List(meta) => Some(meta.nested),
+        .filter_map(|meta| match meta {
+            Meta::List(list) => Some(list.nested),
             _ => None,
         })
         .flat_map(IntoIterator::into_iter)
-        .filter_map(|x| match x {
-            NestedMeta::Meta(Meta::Path(path)) => Some(path.segments),
+        .filter_map(|nested| match nested {
+            NestedMeta::Meta(Meta::Path(path)) => path.get_ident().cloned(),
             _ => None,
         })
-        .flat_map(IntoIterator::into_iter)
-        .map(|x| x.ident)
-        .next()?;
+        .collect::<Vec<_>>();
 
-    match repr.to_string().as_str() {
-        "C" => Some(Ident::new(&format!("u{C_ENUM_BITS}"), Span::call_site())),
-        "Rust" => None,
-        _ => Some(repr),
+    if let Some(int) = items.iter().find(|ident| is_int_repr(ident)) {
+        return Some(int.clone());
     }
+    if items.iter().any(|ident| ident == "C") {
+        return Some(Ident::new(&format!("u{C_ENUM_BITS}"), Span::call_site()));
+    }
+    None
+}
+
+fn is_int_repr(ident: &Ident) -> bool {
+    matches!(
+        ident.to_string().as_str(),
+        "u8" | "u16"
+            | "u32"
+            | "u64"
+            | "u128"
+            | "usize"
+            | "i8"
+            | "i16"
+            | "i32"
+            | "i64"
+            | "i128"
+            | "isize"
+    )
 }
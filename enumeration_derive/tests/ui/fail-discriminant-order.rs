@@ -0,0 +1,11 @@
+use enumeration::Enum;
+
+const FOUR: isize = 4;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Unordered {
+    A = FOUR,
+    B = 2,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use enumeration::Enum;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[repr(u64)]
+pub enum Big {
+    A = 1,
+    B = 0x8000_0000_0000_0000,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[repr(i128)]
+pub enum Implicit {
+    A = 0x7FFF_FFFF_FFFF_FFFF,
+    B,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use enumeration::Enum;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Shape {
+    Point,
+    Circle(u32),
+}
+
+fn main() {}
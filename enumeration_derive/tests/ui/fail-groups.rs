@@ -0,0 +1,24 @@
+use enumeration::Enum;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Invalid {
+    #[enumeration(group = "not valid!")]
+    A,
+    B,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Twice {
+    #[enumeration(group = "first", group = "first")]
+    A,
+    B,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum NotString {
+    #[enumeration(group = 1)]
+    A,
+    B,
+}
+
+fn main() {}
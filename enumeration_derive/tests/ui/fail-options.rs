@@ -0,0 +1,17 @@
+use enumeration::Enum;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[enumeration(display, serialize)]
+pub enum Unknown {
+    A,
+    B,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[enumeration(display = "yes")]
+pub enum Valued {
+    A,
+    B,
+}
+
+fn main() {}
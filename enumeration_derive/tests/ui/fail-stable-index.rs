@@ -0,0 +1,18 @@
+use enumeration::Enum;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Duplicate {
+    #[enumeration(index = 1)]
+    A,
+    #[enumeration(index = 0)]
+    B,
+    C,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Unknown {
+    #[enumeration(position = 1)]
+    A,
+}
+
+fn main() {}
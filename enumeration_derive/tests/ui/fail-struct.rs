@@ -0,0 +1,6 @@
+use enumeration::Enum;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub struct NotAnEnum;
+
+fn main() {}
@@ -0,0 +1,27 @@
+use enumeration::Enum;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[enumeration(table(name = "LABEL", type = "&'static str"))]
+pub enum Missing {
+    #[enumeration(LABEL = "a")]
+    A,
+    B,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[enumeration(table(name = "LABEL", type = "&'static str"))]
+pub enum Undeclared {
+    #[enumeration(LABEL = "a", COLOR = "red")]
+    A,
+    #[enumeration(LABEL = "b")]
+    B,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[enumeration(table(name = "LABEL"))]
+pub enum Untyped {
+    A,
+    B,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+//! `#[allow]` attributes on the enum also apply to the generated code.
+#![deny(deprecated)]
+
+use enumeration::Enum;
+
+#[deprecated]
+#[allow(deprecated)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Legacy {
+    A,
+    B,
+    C,
+}
+
+#[allow(deprecated)]
+fn main() {
+    assert_eq!(Legacy::SIZE, 3);
+}
@@ -0,0 +1,37 @@
+use enumeration::{enums, Enum};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Version {
+    #[enumeration(deprecated)]
+    V1,
+    #[enumeration(deprecated, group = "legacy")]
+    V2,
+    V3,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Discriminated {
+    Low = -5,
+    #[enumeration(deprecated)]
+    High = 5,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Current {
+    A,
+    B,
+}
+
+fn main() {
+    assert_eq!(Version::DEPRECATED, enums![Version::V1, Version::V2]);
+    assert_eq!(Version::V3.index(), 2);
+    assert_eq!(Version::enumerate(..).collect::<Vec<_>>(), [Version::V3]);
+    assert_eq!(Version::enumerate(..).len(), 1);
+    assert_eq!(Version::enumerate_rev(..).collect::<Vec<_>>(), [Version::V3]);
+    assert_eq!(Version::enumerate_all(..).count(), 3);
+    assert!(Discriminated::High.is_deprecated());
+    assert!(!Discriminated::Low.is_deprecated());
+    assert_eq!(Discriminated::enumerate(Discriminated::High..).count(), 0);
+    assert!(Current::DEPRECATED.is_empty());
+    assert_eq!(Current::enumerate(..).count(), 2);
+}
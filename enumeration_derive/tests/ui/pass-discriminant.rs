@@ -0,0 +1,49 @@
+use enumeration::Enum;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Discriminant {
+    A = 1,
+    B,
+    C = 8,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[enumeration(table(name = "LABEL", type = "&'static str"))]
+#[repr(u64)]
+pub enum Unordered {
+    #[enumeration(LABEL = "high")]
+    High = 0x7FFF_FFFF_FFFF_FFFF,
+    #[enumeration(LABEL = "low")]
+    Low = 3,
+    #[enumeration(LABEL = "mid")]
+    Mid = (10),
+}
+
+const TWO: i8 = 2;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[repr(i8)]
+pub enum Computed {
+    A = -TWO,
+    B = TWO,
+}
+
+fn main() {
+    assert_eq!(Discriminant::C.index(), 2);
+    assert_eq!(Discriminant::B.discriminant(), 2);
+    assert_eq!(Discriminant::from_discriminant(8), Some(Discriminant::C));
+
+    assert_eq!(
+        Unordered::enumerate(..).collect::<Vec<_>>(),
+        [Unordered::Low, Unordered::Mid, Unordered::High],
+    );
+    assert_eq!(Unordered::NAMES, ["Low", "Mid", "High"]);
+    assert_eq!(Unordered::LABELS, ["low", "mid", "high"]);
+    assert_eq!(Unordered::High.index(), 2);
+    assert_eq!(Unordered::High.discriminant(), i64::MAX);
+    assert_eq!(Unordered::from_discriminant(10), Some(Unordered::Mid));
+    enumeration::testing::assert_enum_laws::<Unordered>();
+
+    assert_eq!(Computed::A.discriminant(), -2);
+    assert_eq!(Computed::from_discriminant(2), Some(Computed::B));
+}
@@ -0,0 +1,51 @@
+use std::marker::PhantomData;
+
+use enumeration::{enums, Enum, EnumSet};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[enumeration(display, names, conversions)]
+pub enum Axis<T> {
+    #[enumeration(group = "planar")]
+    X,
+    #[enumeration(group = "planar")]
+    Y,
+    Z,
+    #[enumeration(deprecated)]
+    _Marker(PhantomData<T>),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Tagged<'a, A, B> {
+    First { a: PhantomData<&'a A> },
+    Second(PhantomData<A>, PhantomData<B>),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[repr(u8)]
+pub enum Plain {
+    A = 1,
+    B(PhantomData<()>) = 4,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Meters;
+
+fn main() {
+    type A = Axis<Meters>;
+    assert_eq!(A::SIZE, 4);
+    assert_eq!(A::enumerate(..).collect::<Vec<_>>(), [A::X, A::Y, A::Z]);
+    assert_eq!(A::PLANAR, enums![A::X, A::Y]);
+    assert_eq!(A::from_index(3), Some(A::_Marker(PhantomData)));
+    assert_eq!(A::_Marker(PhantomData).index(), 3);
+    assert_eq!(A::Z.to_string(), "Z");
+    assert_eq!("Y".parse(), Ok(A::Y));
+    assert_eq!(usize::from(A::Y), 1);
+    assert_eq!(EnumSet::<A>::all().len(), 4);
+
+    type T = Tagged<'static, u8, u16>;
+    assert_eq!(T::MIN, T::First { a: PhantomData });
+    assert_eq!(T::MIN.succ(), Some(T::Second(PhantomData, PhantomData)));
+
+    assert_eq!(Plain::B(PhantomData).index(), 1);
+    assert_eq!(Plain::VARIANT_COUNT, 2);
+}
@@ -0,0 +1,42 @@
+use enumeration::{enums, Enum, EnumSet};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Token {
+    #[enumeration(group = "literal")]
+    Int,
+    #[enumeration(group = "literal")]
+    Str,
+    #[enumeration(group = "operator", group = "prefix-operator")]
+    Minus,
+    #[enumeration(group = "operator")]
+    Plus,
+    Ident,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Discriminated {
+    #[enumeration(group = "edge")]
+    Low = -5,
+    Mid = 0,
+    #[enumeration(group = "edge")]
+    High = 5,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Pair {
+    #[enumeration(group = "first")]
+    First,
+    Second,
+}
+
+// Group constants are usable in const contexts.
+const LITERALS: EnumSet<Token> = Token::LITERAL;
+
+fn main() {
+    assert_eq!(Token::LITERAL, enums![Token::Int, Token::Str]);
+    assert_eq!(Token::OPERATOR, enums![Token::Minus, Token::Plus]);
+    assert_eq!(Token::PREFIX_OPERATOR, enums![Token::Minus]);
+    assert_eq!(LITERALS.len(), 2);
+    assert_eq!(Pair::FIRST, enums![Pair::First]);
+    assert_eq!(Discriminated::EDGE, enums![Discriminated::Low, Discriminated::High]);
+}
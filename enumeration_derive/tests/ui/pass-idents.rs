@@ -0,0 +1,63 @@
+//! Raw identifiers, enums declared inside functions and extra attributes are all supported.
+
+#[allow(non_camel_case_types)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, enumeration::Enum)]
+#[must_use]
+pub enum r#type {
+    r#struct,
+    r#fn,
+    r#match,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, enumeration::Enum)]
+pub enum Keyword {
+    r#As,
+    r#Async = 4,
+    r#Await,
+}
+
+fn local() -> usize {
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, enumeration::Enum)]
+    enum Local {
+        A,
+        B,
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, enumeration::Enum)]
+    #[repr(u8)]
+    enum LocalRepr {
+        A,
+        B,
+        C,
+    }
+
+    use enumeration::Enum;
+    Local::SIZE + LocalRepr::C.index()
+}
+
+mod shadowed {
+    #[allow(dead_code)]
+    struct Option;
+    #[allow(dead_code)]
+    struct Some;
+    #[allow(dead_code)]
+    struct Enum;
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, enumeration::Enum)]
+    pub enum Shadowed {
+        A,
+        B,
+        C,
+    }
+}
+
+fn main() {
+    use enumeration::Enum;
+
+    assert_eq!(r#type::NAMES, ["struct", "fn", "match"]);
+    assert_eq!(r#type::from_name("fn"), Some(r#type::r#fn));
+    assert_eq!(Keyword::r#Await.index(), 2);
+    assert_eq!(Keyword::NAMES[1], "Async");
+    assert_eq!(local(), 4);
+    assert_eq!(shadowed::Shadowed::B.succ(), Some(shadowed::Shadowed::C));
+}
@@ -0,0 +1,54 @@
+//! Generated code must compile in crates that forbid unsafe code and require docs.
+#![forbid(unsafe_code)]
+#![deny(missing_docs)]
+
+use enumeration::Enum;
+
+/// A single variant.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Single {
+    /// Only.
+    Only,
+}
+
+/// Two variants.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Double {
+    /// Off.
+    Off,
+    /// On.
+    On,
+}
+
+/// Several variants.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[enumeration(names, table(name = "WEIGHT", type = "u8"))]
+pub enum Level {
+    /// Low.
+    #[enumeration(group = "extreme", WEIGHT = 1)]
+    Low,
+    /// Medium.
+    #[enumeration(WEIGHT = 2)]
+    Medium,
+    /// High.
+    #[enumeration(group = "extreme", WEIGHT = 3)]
+    High,
+}
+
+/// Explicit discriminants.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Signed {
+    /// Negative.
+    Negative = -1,
+    /// Positive.
+    Positive = 1,
+}
+
+fn main() {
+    assert_eq!(Single::from_index(0), Some(Single::Only));
+    assert_eq!(Double::Off.succ(), Some(Double::On));
+    assert_eq!(Level::Medium.pred(), Some(Level::Low));
+    assert_eq!(Level::from_index(3), None);
+    assert_eq!(Level::VARIANT_COUNT, 3);
+    assert_eq!(Signed::VARIANT_COUNT, Signed::SIZE);
+}
@@ -0,0 +1,36 @@
+use std::convert::TryFrom;
+
+use enumeration::{Enum, OutOfRange, ParseEnumError};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[enumeration(display)]
+#[enumeration(names, conversions)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    r#Hearts,
+    Spades,
+}
+
+// Options are per enum, so this one gets none of the extra impls.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Plain {
+    A,
+    B,
+}
+
+impl std::fmt::Display for Plain {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("plain")
+    }
+}
+
+fn main() {
+    assert_eq!(format!("{:>8}", Suit::Hearts), "  Hearts");
+    assert_eq!(Suit::Clubs.as_str(), "Clubs");
+    assert_eq!("Hearts".parse::<Suit>(), Ok(Suit::Hearts));
+    assert_eq!("hearts".parse::<Suit>(), Err(ParseEnumError));
+    assert_eq!(usize::from(Suit::Spades), 3);
+    assert_eq!(Suit::try_from(4), Err(OutOfRange { index: 4, size: 4 }));
+    assert_eq!(Plain::B.to_string(), "plain");
+}
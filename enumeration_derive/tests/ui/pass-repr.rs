@@ -0,0 +1,63 @@
+//! Every item of every `repr` attribute is considered, and layout modifiers are ignored.
+use enumeration::Enum;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[repr(align(4), u8)]
+pub enum AlignFirst {
+    A,
+    B,
+    C,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[repr(u16, align(2))]
+pub enum AlignLast {
+    A,
+    B,
+    C,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[repr(align(8))]
+#[repr(i32)]
+pub enum SplitAttrs {
+    A,
+    B,
+    C,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[repr(C)]
+#[repr(align(4))]
+pub enum CAligned {
+    A,
+    B,
+    C,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[repr(usize)]
+pub enum Pointer {
+    A,
+    B,
+    C,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[repr(isize)]
+pub enum SignedPointer {
+    A,
+    B,
+    C,
+}
+
+fn main() {
+    assert_eq!(AlignFirst::C.index(), 2);
+    assert_eq!(AlignLast::C.bit(), 0b100);
+    assert_eq!(SplitAttrs::B.succ(), Some(SplitAttrs::C));
+    assert_eq!(CAligned::A.pred(), None);
+    assert_eq!(Pointer::C.bit(), 0b100);
+    assert_eq!(Pointer::from_index(1), Some(Pointer::B));
+    assert_eq!(SignedPointer::C.index(), 2);
+    assert_eq!(SignedPointer::C.bit(), 0b100);
+}
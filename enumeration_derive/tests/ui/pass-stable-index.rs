@@ -0,0 +1,28 @@
+use enumeration::Enum;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Pinned {
+    #[enumeration(index = 10)]
+    A,
+    B,
+    #[enumeration(index = 3)]
+    C,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Unpinned {
+    A,
+    B,
+}
+
+fn main() {
+    assert!(Pinned::STABLE_INDICES);
+    assert_eq!(Pinned::B.stable_index(), 11);
+    assert_eq!(Pinned::C.stable_index(), 3);
+    assert_eq!(Pinned::from_stable_index(10), Some(Pinned::A));
+    assert_eq!(Pinned::from_stable_index(0), None);
+    enumeration::testing::assert_enum_laws::<Pinned>();
+
+    assert!(!Unpinned::STABLE_INDICES);
+    assert_eq!(Unpinned::B.stable_index(), 1);
+}
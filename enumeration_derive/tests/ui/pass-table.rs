@@ -0,0 +1,35 @@
+use enumeration::Enum;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[enumeration(table(name = "SYMBOL", type = "char"), table(name = "RANK", type = "i8"))]
+pub enum Piece {
+    #[enumeration(SYMBOL = 'p', RANK = -1)]
+    Pawn,
+    #[enumeration(RANK = 3, SYMBOL = 'n')]
+    Knight,
+    #[enumeration(SYMBOL = 'k', RANK = 0, group = "royal")]
+    King,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[enumeration(table(name = "CODE", type = "&'static [u8]"))]
+pub enum Discriminated {
+    #[enumeration(CODE = b"lo")]
+    Low = -5,
+    #[enumeration(CODE = b"hi")]
+    High = 5,
+}
+
+const KING: char = Piece::King.symbol();
+
+fn main() {
+    assert_eq!(Piece::SYMBOLS, ['p', 'n', 'k']);
+    assert_eq!(Piece::RANKS, [-1, 3, 0]);
+    assert_eq!(KING, 'k');
+    assert_eq!(Piece::Knight.rank(), 3);
+    for piece in Piece::enumerate(..) {
+        assert_eq!(Piece::SYMBOLS[piece.index()], piece.symbol());
+    }
+    assert_eq!(Discriminated::High.code(), b"hi");
+    assert_eq!(Discriminated::CODES[0], b"lo");
+}
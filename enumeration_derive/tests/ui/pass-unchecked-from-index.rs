@@ -0,0 +1,30 @@
+#![deny(warnings, unsafe_op_in_unsafe_fn)]
+
+use enumeration::{Enum, EnumMap};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[enumeration(unchecked_from_index)]
+pub enum Plain {
+    A,
+    B,
+    C,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+#[enumeration(unchecked_from_index)]
+pub enum Discriminated {
+    Low = -5,
+    Mid = 0,
+    High = 5,
+}
+
+fn main() {
+    for x in Plain::enumerate(..) {
+        assert_eq!(unsafe { Plain::from_index_unchecked(x.index()) }, x);
+    }
+    for x in Discriminated::enumerate(..) {
+        assert_eq!(unsafe { Discriminated::from_index_unchecked(x.index()) }, x);
+    }
+    let map = EnumMap::from([(Plain::C, 3), (Plain::A, 1)]);
+    assert_eq!(map.keys().collect::<Vec<_>>(), [Plain::A, Plain::C]);
+}
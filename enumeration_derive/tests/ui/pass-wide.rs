@@ -0,0 +1,467 @@
+use enumeration::{enums, Enum, EnumMap, EnumSet, Words};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Wide {
+    #[enumeration(group = "ends", deprecated)]
+    V0,
+    V1,
+    V2,
+    V3,
+    V4,
+    V5,
+    V6,
+    V7,
+    V8,
+    V9,
+    V10,
+    V11,
+    V12,
+    V13,
+    V14,
+    V15,
+    V16,
+    V17,
+    V18,
+    V19,
+    V20,
+    V21,
+    V22,
+    V23,
+    V24,
+    V25,
+    V26,
+    V27,
+    V28,
+    V29,
+    V30,
+    V31,
+    V32,
+    V33,
+    V34,
+    V35,
+    V36,
+    V37,
+    V38,
+    V39,
+    V40,
+    V41,
+    V42,
+    V43,
+    V44,
+    V45,
+    V46,
+    V47,
+    V48,
+    V49,
+    V50,
+    V51,
+    V52,
+    V53,
+    V54,
+    V55,
+    V56,
+    V57,
+    V58,
+    V59,
+    V60,
+    V61,
+    V62,
+    V63,
+    V64,
+    V65,
+    V66,
+    V67,
+    V68,
+    V69,
+    V70,
+    V71,
+    V72,
+    V73,
+    V74,
+    V75,
+    V76,
+    V77,
+    V78,
+    V79,
+    V80,
+    V81,
+    V82,
+    V83,
+    V84,
+    V85,
+    V86,
+    V87,
+    V88,
+    V89,
+    V90,
+    V91,
+    V92,
+    V93,
+    V94,
+    V95,
+    V96,
+    V97,
+    V98,
+    V99,
+    V100,
+    V101,
+    V102,
+    V103,
+    V104,
+    V105,
+    V106,
+    V107,
+    V108,
+    V109,
+    V110,
+    V111,
+    V112,
+    V113,
+    V114,
+    V115,
+    V116,
+    V117,
+    V118,
+    V119,
+    V120,
+    V121,
+    V122,
+    V123,
+    V124,
+    V125,
+    V126,
+    V127,
+    V128,
+    V129,
+    V130,
+    V131,
+    V132,
+    V133,
+    V134,
+    V135,
+    V136,
+    V137,
+    V138,
+    V139,
+    V140,
+    V141,
+    V142,
+    V143,
+    V144,
+    V145,
+    V146,
+    V147,
+    V148,
+    V149,
+    V150,
+    V151,
+    V152,
+    V153,
+    V154,
+    V155,
+    V156,
+    V157,
+    V158,
+    V159,
+    V160,
+    V161,
+    V162,
+    V163,
+    V164,
+    V165,
+    V166,
+    V167,
+    V168,
+    V169,
+    V170,
+    V171,
+    V172,
+    V173,
+    V174,
+    V175,
+    V176,
+    V177,
+    V178,
+    V179,
+    V180,
+    V181,
+    V182,
+    V183,
+    V184,
+    V185,
+    V186,
+    V187,
+    V188,
+    V189,
+    V190,
+    V191,
+    V192,
+    V193,
+    V194,
+    V195,
+    V196,
+    V197,
+    V198,
+    V199,
+    V200,
+    V201,
+    V202,
+    V203,
+    V204,
+    V205,
+    V206,
+    V207,
+    V208,
+    V209,
+    V210,
+    V211,
+    V212,
+    V213,
+    V214,
+    V215,
+    V216,
+    V217,
+    V218,
+    V219,
+    V220,
+    V221,
+    V222,
+    V223,
+    V224,
+    V225,
+    V226,
+    V227,
+    V228,
+    V229,
+    V230,
+    V231,
+    V232,
+    V233,
+    V234,
+    V235,
+    V236,
+    V237,
+    V238,
+    V239,
+    V240,
+    V241,
+    V242,
+    V243,
+    V244,
+    V245,
+    V246,
+    V247,
+    V248,
+    V249,
+    V250,
+    V251,
+    V252,
+    V253,
+    V254,
+    V255,
+    V256,
+    V257,
+    V258,
+    V259,
+    V260,
+    V261,
+    V262,
+    V263,
+    V264,
+    V265,
+    V266,
+    V267,
+    V268,
+    V269,
+    V270,
+    V271,
+    V272,
+    V273,
+    V274,
+    V275,
+    V276,
+    V277,
+    V278,
+    V279,
+    V280,
+    V281,
+    V282,
+    V283,
+    V284,
+    V285,
+    V286,
+    V287,
+    V288,
+    V289,
+    V290,
+    V291,
+    V292,
+    V293,
+    V294,
+    V295,
+    V296,
+    V297,
+    V298,
+    #[enumeration(group = "ends")]
+    V299,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum)]
+pub enum Discriminated {
+    Low = -1,
+    D0 = 0,
+    D1 = 1,
+    D2 = 2,
+    D3 = 3,
+    D4 = 4,
+    D5 = 5,
+    D6 = 6,
+    D7 = 7,
+    D8 = 8,
+    D9 = 9,
+    D10 = 10,
+    D11 = 11,
+    D12 = 12,
+    D13 = 13,
+    D14 = 14,
+    D15 = 15,
+    D16 = 16,
+    D17 = 17,
+    D18 = 18,
+    D19 = 19,
+    D20 = 20,
+    D21 = 21,
+    D22 = 22,
+    D23 = 23,
+    D24 = 24,
+    D25 = 25,
+    D26 = 26,
+    D27 = 27,
+    D28 = 28,
+    D29 = 29,
+    D30 = 30,
+    D31 = 31,
+    D32 = 32,
+    D33 = 33,
+    D34 = 34,
+    D35 = 35,
+    D36 = 36,
+    D37 = 37,
+    D38 = 38,
+    D39 = 39,
+    D40 = 40,
+    D41 = 41,
+    D42 = 42,
+    D43 = 43,
+    D44 = 44,
+    D45 = 45,
+    D46 = 46,
+    D47 = 47,
+    D48 = 48,
+    D49 = 49,
+    D50 = 50,
+    D51 = 51,
+    D52 = 52,
+    D53 = 53,
+    D54 = 54,
+    D55 = 55,
+    D56 = 56,
+    D57 = 57,
+    D58 = 58,
+    D59 = 59,
+    D60 = 60,
+    D61 = 61,
+    D62 = 62,
+    D63 = 63,
+    D64 = 64,
+    D65 = 65,
+    D66 = 66,
+    D67 = 67,
+    D68 = 68,
+    D69 = 69,
+    D70 = 70,
+    D71 = 71,
+    D72 = 72,
+    D73 = 73,
+    D74 = 74,
+    D75 = 75,
+    D76 = 76,
+    D77 = 77,
+    D78 = 78,
+    D79 = 79,
+    D80 = 80,
+    D81 = 81,
+    D82 = 82,
+    D83 = 83,
+    D84 = 84,
+    D85 = 85,
+    D86 = 86,
+    D87 = 87,
+    D88 = 88,
+    D89 = 89,
+    D90 = 90,
+    D91 = 91,
+    D92 = 92,
+    D93 = 93,
+    D94 = 94,
+    D95 = 95,
+    D96 = 96,
+    D97 = 97,
+    D98 = 98,
+    D99 = 99,
+    D100 = 100,
+    D101 = 101,
+    D102 = 102,
+    D103 = 103,
+    D104 = 104,
+    D105 = 105,
+    D106 = 106,
+    D107 = 107,
+    D108 = 108,
+    D109 = 109,
+    D110 = 110,
+    D111 = 111,
+    D112 = 112,
+    D113 = 113,
+    D114 = 114,
+    D115 = 115,
+    D116 = 116,
+    D117 = 117,
+    D118 = 118,
+    D119 = 119,
+    D120 = 120,
+    D121 = 121,
+    D122 = 122,
+    D123 = 123,
+    D124 = 124,
+    D125 = 125,
+    D126 = 126,
+    High = 1000,
+}
+
+fn main() {
+    let _: Words<5> = Wide::V0.bit();
+    let _: Words<3> = Discriminated::Low.bit();
+    assert_eq!(Wide::SIZE, 300);
+    assert_eq!(Discriminated::SIZE, 129);
+    assert_eq!(Wide::V299.index(), 299);
+    assert_eq!(Wide::ENDS, enums![Wide::V0, Wide::V299]);
+    assert_eq!(Wide::DEPRECATED, enums![Wide::V0]);
+    assert_eq!(EnumSet::<Wide>::all().len(), 300);
+    assert_eq!((!Wide::ENDS).len(), 298);
+    assert_eq!(Wide::enumerate(..).count(), 299);
+    assert_eq!(Wide::enumerate(..).len(), 299);
+    assert_eq!(Wide::enumerate_all(..).count(), 300);
+    assert_eq!(
+        Wide::ENDS.into_iter().collect::<Vec<_>>(),
+        [Wide::V0, Wide::V299]
+    );
+    assert!(enums![Wide::V1] < enums![Wide::V299]);
+
+    let set = enums![Discriminated::Low, Discriminated::High];
+    assert_eq!(set.into_iter().next_back(), Some(Discriminated::High));
+
+    let mut map = EnumMap::new();
+    map.insert(Wide::V200, "two hundred");
+    assert_eq!(map.keys().collect::<Vec<_>>(), [Wide::V200]);
+}